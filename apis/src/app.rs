@@ -1,5 +1,7 @@
 use crate::{
-    components::layouts::base_layout::BaseLayout,
+    components::{
+        layouts::base_layout::BaseLayout, organisms::display_statistics::DisplayStatistics,
+    },
     pages::{
         account::Account,
         admin::Admin,
@@ -9,8 +11,10 @@ use crate::{
         display_games::DisplayGames,
         donate::Donate,
         faq::Faq,
+        forgot_password::ForgotPassword,
         home::Home,
         login::Login,
+        password_reset::PasswordReset,
         play::Play,
         profile_view::{ProfileGamesView, ProfileView},
         puzzles::Puzzles,
@@ -23,13 +27,14 @@ use crate::{
         tournament_create::TournamentCreate,
         tournaments::Tournaments,
         tutorial::Tutorial,
+        verify_email::VerifyEmail,
     },
     providers::{
         challenges::provide_challenges, chat::provide_chat, game_state::provide_game_state,
         games::provide_games, navigation_controller::provide_navigation_controller,
         online_users::provide_users, provide_alerts, provide_auth, provide_color_scheme,
-        provide_config, provide_notifications, provide_ping, refocus::provide_refocus,
-        timer::provide_timer, tournament_ready::provide_tournament_ready,
+        provide_config, provide_notifications, provide_ping, quick_pair::provide_quick_pair,
+        refocus::provide_refocus, timer::provide_timer, tournament_ready::provide_tournament_ready,
         tournaments::provide_tournaments, user_search::provide_user_search,
         websocket::provide_websocket,
     },
@@ -61,6 +66,7 @@ pub fn App() -> impl IntoView {
     provide_tournaments();
     provide_notifications();
     provide_tournament_ready();
+    provide_quick_pair();
 
     view! {
         <Stylesheet id="leptos" href="/pkg/HiveGame.css"/>
@@ -105,11 +111,15 @@ pub fn App() -> impl IntoView {
                             path="unstarted"
                             view=|| view! { <DisplayGames tab_view=ProfileGamesView::Unstarted/> }
                         />
+                        <Route path="statistics" view=|| view! { <DisplayStatistics/> }/>
                     </Route>
                     <Route path="/register" view=|| view! { <Register/> }/>
                     <Route path="/top_players" view=|| view! { <TopPlayers/> }/>
                     <Route path="/login" view=|| view! { <Login/> }/>
                     <Route path="/account" view=|| view! { <Account/> }/>
+                    <Route path="/forgot_password" view=|| view! { <ForgotPassword/> }/>
+                    <Route path="/reset_password" view=|| view! { <PasswordReset/> }/>
+                    <Route path="/verify_email" view=|| view! { <VerifyEmail/> }/>
                     <Route path="/challenge/:nanoid" view=|| view! { <ChallengeView/> }/>
                     <Route path="/analysis" view=|| view! { <Analysis/> }/>
                     <Route path="/config" view=|| view! { <Config/> }/>
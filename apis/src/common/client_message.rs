@@ -1,5 +1,5 @@
 use super::game_action::GameAction;
-use super::{challenge_action::ChallengeAction, TournamentAction};
+use super::{challenge_action::ChallengeAction, QuickPairAction, TournamentAction};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use shared_types::{ChatMessageContainer, GameId};
@@ -12,6 +12,7 @@ pub enum ClientRequest {
     Game { game_id: GameId, action: GameAction },
     Ping(DateTime<Utc>),
     Tournament(TournamentAction),
+    QuickPair(QuickPairAction),
     // leptos-use idle or window unfocused will send
     Away, // Online and Offline are not needed because they will be handled by the WS connection
           // being established/torn down
@@ -9,6 +9,27 @@ pub enum GameAction {
     Control(GameControl),
     Join,
     Turn(Turn),
+    Rematch(RematchAction),
+    Premove(Turn), // Queued on the opponent's turn, replaces an earlier premove
+    CancelPremove,
+}
+
+// Unlike GameControls these are only valid once the game is over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RematchAction {
+    Offer,
+    Accept,
+    Decline, // Also withdraws one's own offer
+}
+
+impl fmt::Display for RematchAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RematchAction::Offer => write!(f, "RematchOffer"),
+            RematchAction::Accept => write!(f, "RematchAccept"),
+            RematchAction::Decline => write!(f, "RematchDecline"),
+        }
+    }
 }
 
 impl fmt::Display for GameAction {
@@ -19,6 +40,9 @@ impl fmt::Display for GameAction {
             GameAction::Join => write!(f, "Join"),
             GameAction::Start => write!(f, "Start"),
             GameAction::Turn(ref turn) => write!(f, "{}", turn),
+            GameAction::Rematch(rematch) => write!(f, "{}", rematch),
+            GameAction::Premove(ref turn) => write!(f, "Premove {}", turn),
+            GameAction::CancelPremove => write!(f, "CancelPremove"),
         }
     }
 }
@@ -7,6 +7,7 @@ mod hex;
 mod hex_stack;
 mod move_info;
 mod piece_type;
+mod quick_pair_action;
 mod rating_change_info;
 mod server_result;
 mod svg_pos;
@@ -16,16 +17,17 @@ mod user_action;
 pub use challenge_action::ChallengeAction;
 pub use client_message::ClientRequest;
 pub use config_options::{MoveConfirm, TileDesign, TileDots, TileRotation};
-pub use game_action::GameAction;
+pub use game_action::{GameAction, RematchAction};
 pub use game_reaction::GameReaction;
 pub use hex::{ActiveState, Direction, Hex, HexType};
 pub use hex_stack::HexStack;
 pub use move_info::MoveInfo;
 pub use piece_type::PieceType;
+pub use quick_pair_action::QuickPairAction;
 pub use rating_change_info::RatingChangeInfo;
 pub use server_result::{
-    ChallengeUpdate, ExternalServerError, GameActionResponse, GameUpdate, ServerMessage,
-    ServerResult, TournamentUpdate, UserStatus, UserUpdate,
+    ChallengeUpdate, ExternalServerError, GameActionResponse, GameUpdate, QuickPairUpdate,
+    RematchUpdate, ServerMessage, ServerResult, TournamentUpdate, UserStatus, UserUpdate,
 };
 pub use svg_pos::SvgPos;
 pub use time_signals::TimeSignals;
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use shared_types::QuickPairDetails;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuickPairAction {
+    Join(QuickPairDetails), // Join the pool, replacing any previous search
+    Leave,                  // Stop searching
+}
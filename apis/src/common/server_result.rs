@@ -3,7 +3,7 @@ use crate::responses::{ChallengeResponse, GameResponse, HeartbeatResponse, Tourn
 use chrono::{DateTime, Utc};
 use http::StatusCode;
 use serde::{Deserialize, Serialize};
-use shared_types::{ChallengeId, ChatMessageContainer};
+use shared_types::{ChallengeId, ChatMessageContainer, QuickPairDetails};
 use shared_types::{GameId, TournamentId};
 use std::fmt;
 use uuid::Uuid;
@@ -46,6 +46,7 @@ pub enum ServerMessage {
     UserSearch(Vec<UserResponse>),
     UserStatus(UserUpdate),
     Tournament(TournamentUpdate),
+    QuickPair(QuickPairUpdate),
     // sent to everyone in the game when a user joins the game
     Join(UserResponse),
     Error(String),
@@ -71,6 +72,21 @@ pub enum GameUpdate {
     Urgent(Vec<GameResponse>),
     Tv(GameResponse),
     Heartbeat(HeartbeatResponse),
+    Rematch(RematchUpdate),
+    PremoveCleared(GameId), // The queued premove was dropped instead of played
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RematchUpdate {
+    Offered {
+        game_id: GameId,
+        user_id: Uuid,
+    },
+    Withdrawn(GameId), // Declined, withdrawn or expired
+    Accepted {
+        game_id: GameId,
+        new_game_id: GameId,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +106,12 @@ pub enum ChallengeUpdate {
     Challenges(Vec<ChallengeResponse>), //
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QuickPairUpdate {
+    Searching(QuickPairDetails), // The user is waiting in the pool
+    Stopped,                     // The user left the pool or got paired
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserUpdate {
     pub status: UserStatus,
@@ -1,10 +1,11 @@
 use leptos::*;
-use shared_types::{CorrespondenceMode, TimeMode};
+use shared_types::{ClockType, CorrespondenceMode, TimeMode};
 
 #[derive(Debug, Copy, Clone)]
 pub struct TimeSignals {
     pub time_control: RwSignal<TimeMode>,
     pub corr_mode: RwSignal<CorrespondenceMode>,
+    pub clock_type: RwSignal<ClockType>,
     pub corr_days: RwSignal<i32>,
     pub step_sec: RwSignal<i32>,
     pub step_min: RwSignal<i32>,
@@ -22,6 +23,7 @@ impl TimeSignals {
     pub fn new() -> Self {
         let time_control = RwSignal::new(TimeMode::RealTime);
         let corr_mode = RwSignal::new(CorrespondenceMode::DaysPerMove);
+        let clock_type = RwSignal::new(ClockType::Fischer);
         let corr_days = RwSignal::new(2_i32);
         let step_min = RwSignal::new(10_i32);
         let step_sec = RwSignal::new(10_i32);
@@ -48,6 +50,7 @@ impl TimeSignals {
         Self {
             time_control,
             corr_mode,
+            clock_type,
             corr_days,
             step_sec,
             step_min,
@@ -0,0 +1,17 @@
+use crate::responses::UserResponse;
+use leptos::*;
+
+#[component]
+pub fn Avatar(
+    user: StoredValue<UserResponse>,
+    #[prop(optional)] extend_tw_classes: &'static str,
+) -> impl IntoView {
+    view! {
+        <img
+            src=user().avatar_url()
+            alt=""
+            loading="lazy"
+            class=format!("rounded {extend_tw_classes}")
+        />
+    }
+}
@@ -0,0 +1,10 @@
+use leptos::*;
+use shared_types::{country_flag, country_name};
+
+#[component]
+pub fn Flag(country: Option<String>) -> impl IntoView {
+    country.map(|country| {
+        let name = country_name(&country).unwrap_or_default();
+        view! { <span title=name>{country_flag(&country)}</span> }
+    })
+}
@@ -174,6 +174,8 @@ fn get_icon_and_title(game_control: GameControl) -> (Icon, &'static str) {
         GameControl::DrawOffer(_) => (icondata::FaHandshakeSimpleSolid, "Offer Draw"),
         GameControl::DrawReject(_) => (icondata::FaHandshakeSimpleSolid, "Reject Draw"),
         GameControl::Resign(_) => (icondata::AiFlagOutlined, "Resign"),
+        GameControl::ClaimRepetition(_) => (icondata::BiRepeatRegular, "Claim draw by repetition"),
+        GameControl::ClaimVictory(_) => (icondata::AiTrophyOutlined, "Claim victory"),
         GameControl::TakebackAccept(_) => (icondata::BiUndoRegular, "Accept Takeback"),
         GameControl::TakebackReject(_) => (icondata::BiUndoRegular, "Reject Takeback"),
         GameControl::TakebackRequest(_) => (icondata::BiUndoRegular, "Request Takeback"),
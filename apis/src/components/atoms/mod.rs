@@ -1,7 +1,9 @@
 pub mod active;
+pub mod avatar;
 pub mod create_challenge_button;
 pub mod direct_challenge_button;
 pub mod download_pgn;
+pub mod flag;
 pub mod game_type;
 pub mod gc_button;
 pub mod hex;
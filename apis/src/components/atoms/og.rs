@@ -1,25 +1,50 @@
 use leptos::*;
+use leptos_router::use_location;
 
 #[component]
 pub fn OG() -> impl IntoView {
     let content = "Free online hive server. Play hive in a clean interface. No ads, no plugin required. Play hive with friends or random opponents.";
+    let location = use_location();
+    // Games get a preview of their position instead of the logo
+    let game_id = Memo::new(move |_| {
+        location
+            .pathname
+            .get()
+            .strip_prefix("/game/")
+            .and_then(|rest| rest.split('/').next())
+            .filter(|nanoid| !nanoid.is_empty())
+            .map(str::to_owned)
+    });
+    let url = move || match game_id() {
+        Some(nanoid) => format!("https://hivegame.com/game/{nanoid}"),
+        None => String::from("https://hivegame.com"),
+    };
+    let title = move || match game_id() {
+        Some(nanoid) => format!("Hive game {nanoid}"),
+        None => String::from("The best free, adless Hive server"),
+    };
+    let og_image = move || match game_id() {
+        Some(nanoid) => format!("https://hivegame.com/game/{nanoid}/image.png"),
+        None => String::from("https://hivegame.com/assets/stacked_3D.png"),
+    };
+    let twitter_image = move || match game_id() {
+        Some(nanoid) => format!("https://hivegame.com/game/{nanoid}/image.png"),
+        None => String::from("https://hivegame.com/assets/android-chrome-512x512.png"),
+    };
     view! {
         <meta name="description" content=content/>
 
-        <meta property="og:url" content="https://hivegame.com"/>
+        <meta property="og:url" content=url/>
         <meta property="og:type" content="website"/>
-        <meta property="og:title" content="The best free, adless Hive server"/>
+        <meta property="og:title" content=title/>
         <meta property="og:description" content=content/>
-        <meta property="og:image" content="https://hivegame.com/assets/stacked_3D.png"/>
+        <meta property="og:image" content=og_image/>
 
         <meta name="twitter:card" content="summary_large_image"/>
         <meta property="twitter:domain" content="hivegame.com"/>
-        <meta property="twitter:url" content="https://hivegame.com"/>
-        <meta name="twitter:title" content="The best free, adless Hive server"/>
+        <meta property="twitter:url" content=url/>
+        <meta name="twitter:title" content=title/>
         <meta name="twitter:description" content=content/>
-        <meta
-            name="twitter:image"
-            content="https://hivegame.com/assets/android-chrome-512x512.png"
-        />
+        <meta name="twitter:image" content=twitter_image/>
     }
 }
@@ -76,7 +76,10 @@ pub fn Piece(
             GameStatus::Finished(_)
         );
 
-        if (in_analysis && !is_finished) || game_state.is_move_allowed() {
+        if (in_analysis && !is_finished)
+            || game_state.is_move_allowed()
+            || game_state.is_premove_allowed()
+        {
             match piece_type {
                 PieceType::Board => {
                     game_state.show_moves(piece, position);
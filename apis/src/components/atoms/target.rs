@@ -22,7 +22,7 @@ pub fn Target(
     // Select the target position and make a move if it's the correct mode
     let onclick = move |_| {
         let in_analysis = analysis.get().is_some();
-        if in_analysis || game_state.is_move_allowed() {
+        if in_analysis || game_state.is_move_allowed() || game_state.is_premove_allowed() {
             batch(move || {
                 game_state.set_target(position);
                 if current_confirm() == MoveConfirm::Single || in_analysis {
@@ -8,12 +8,13 @@ use crate::{
     providers::{AuthContext, ColorScheme},
     responses::ChallengeResponse,
 };
-use hive_lib::ColorChoice;
+use hive_lib::{ColorChoice, GameType};
 use leptos::*;
 use leptos_icons::*;
 use leptos_router::*;
 use leptos_use::use_window;
 use shared_types::{ChallengeVisibility, TimeInfo};
+use std::str::FromStr;
 
 #[component]
 pub fn ChallengeRow(challenge: StoredValue<ChallengeResponse>, single: bool) -> impl IntoView {
@@ -119,7 +120,8 @@ pub fn ChallengeRow(challenge: StoredValue<ChallengeResponse>, single: bool) ->
     let rating = move || {
         if let (Some(uid), Some(opponent)) = (uid(), challenge().opponent) {
             if challenge().challenger.uid == uid {
-                view! { <p>{opponent.rating_for_speed(&challenge().speed)}</p> }
+                let game_type = GameType::from_str(&challenge().game_type).unwrap_or(GameType::MLP);
+                view! { <p>{opponent.rating_for_speed(&game_type, &challenge().speed)}</p> }
             } else {
                 view! { <p>{challenge().challenger_rating}</p> }
             }
@@ -132,6 +134,7 @@ pub fn ChallengeRow(challenge: StoredValue<ChallengeResponse>, single: bool) ->
         mode: time_mode,
         base: challenge().time_base,
         increment: challenge().time_increment,
+        clock: challenge().clock_type,
     };
 
     view! {
@@ -0,0 +1,109 @@
+use crate::{
+    functions::games::conditional::{
+        add_conditional_moves, clear_conditional_moves, get_conditional_moves,
+    },
+    providers::{game_state::GameStateSignal, AuthContext},
+};
+use leptos::*;
+use shared_types::{GameId, TimeMode};
+
+#[component]
+pub fn ConditionalMoves() -> impl IntoView {
+    let game_state = expect_context::<GameStateSignal>();
+    let auth_context = expect_context::<AuthContext>();
+    let user_id = move || match (auth_context.user)() {
+        Some(Ok(Some(user))) => Some(user.id),
+        _ => None,
+    };
+    let game = create_read_slice(game_state.signal, |gs| {
+        gs.game_response.as_ref().map(|gr| {
+            (
+                gr.game_id.clone(),
+                gr.time_mode,
+                gr.finished,
+                gr.white_player.uid,
+                gr.black_player.uid,
+            )
+        })
+    });
+    // Only players of ongoing correspondence games get to prepare lines
+    let game_id = Memo::new(move |_| match (game(), user_id()) {
+        (Some((game_id, TimeMode::Correspondence, false, white, black)), Some(user))
+            if user == white || user == black =>
+        {
+            Some(game_id)
+        }
+        _ => None,
+    });
+    let turn = create_read_slice(game_state.signal, |gs| gs.state.turn);
+    let lines = RwSignal::new(Vec::new());
+    let error = RwSignal::new(None::<String>);
+    let input = RwSignal::new(String::new());
+    let _ = Resource::new(
+        move || (game_id(), turn()),
+        move |(game_id, _)| async move {
+            if let Some(game_id) = game_id {
+                lines.set(get_conditional_moves(game_id).await.unwrap_or_default());
+            }
+        },
+    );
+    let add = move |game_id: GameId| {
+        spawn_local(async move {
+            match add_conditional_moves(game_id, input.get_untracked()).await {
+                Ok(new_lines) => {
+                    lines.set(new_lines);
+                    input.set(String::new());
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let clear = move |game_id: GameId| {
+        spawn_local(async move {
+            if clear_conditional_moves(game_id).await.is_ok() {
+                lines.set(Vec::new());
+            }
+        });
+    };
+    view! {
+        {move || {
+            game_id()
+                .map(|game_id| {
+                    let add_id = game_id.clone();
+                    view! {
+                        <div class="flex flex-col gap-1 p-2 border-t-2 border-black dark:border-white">
+                            <div class="font-bold">Conditional moves</div>
+                            <For each=lines key=|line| line.clone() let:line>
+                                <div class="text-sm break-words">{line}</div>
+                            </For>
+                            <input
+                                class="shadow appearance-none border rounded w-full py-1 px-2 leading-tight focus:outline-none"
+                                type="text"
+                                placeholder="wA1;bG1 wA1-;"
+                                prop:value=input
+                                on:input=move |evt| input.set(event_target_value(&evt))
+                            />
+                            <Show when=move || error().is_some()>
+                                <div class="text-sm text-ladybug-red">{error}</div>
+                            </Show>
+                            <div class="flex gap-1 [&>*]:grow">
+                                <button
+                                    class="bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal transform transition-transform duration-300 active:scale-95 rounded px-2 py-1"
+                                    on:click=move |_| add(add_id.clone())
+                                >
+                                    Add line
+                                </button>
+                                <button
+                                    class="bg-button-dawn dark:bg-button-twilight hover:bg-ladybug-red transform transition-transform duration-300 active:scale-95 rounded px-2 py-1"
+                                    on:click=move |_| clear(game_id.clone())
+                                >
+                                    Clear
+                                </button>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
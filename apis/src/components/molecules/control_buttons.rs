@@ -1,14 +1,17 @@
 use crate::{
-    common::ChallengeAction,
+    common::{ChallengeAction, RematchAction},
     components::atoms::gc_button::{AcceptDenyGc, ConfirmButton},
+    functions::games::get::get_claim_victory_after,
     providers::{
-        challenges::ChallengeStateSignal, game_state::GameStateSignal, ApiRequests, AuthContext,
+        game_state::GameStateSignal, online_users::OnlineUsersSignal, ApiRequests, AuthContext,
     },
 };
-use hive_lib::{ColorChoice, GameControl};
+use hive_lib::{Color, ColorChoice, GameControl};
 use leptos::*;
 use leptos_router::use_navigate;
-use shared_types::{ChallengeDetails, ChallengeVisibility};
+use shared_types::{
+    ChallengeDetails, ChallengeVisibility, RepetitionRule, TimeMode, CLAIM_VICTORY_AFTER,
+};
 
 #[component]
 pub fn ControlButtons() -> impl IntoView {
@@ -66,6 +69,55 @@ pub fn ControlButtons() -> impl IntoView {
         _ => false,
     };
 
+    let can_claim_repetition = create_read_slice(game_state.signal, |gs| {
+        gs.game_response
+            .as_ref()
+            .is_some_and(|gr| gr.repetition_rule == RepetitionRule::Claimed)
+            && gs.state.repetition_claimable()
+    });
+
+    // A realtime opponent who has been offline for a while can be claimed victory against
+    let online_users = expect_context::<OnlineUsersSignal>();
+    let opponent_gone = create_memo(move |_| {
+        game_state
+            .signal
+            .with(|gs| {
+                gs.game_response.as_ref().map(|gr| {
+                    let opponent = if color() == Color::White {
+                        gr.black_player.username.clone()
+                    } else {
+                        gr.white_player.username.clone()
+                    };
+                    (gr.time_mode == TimeMode::RealTime, opponent)
+                })
+            })
+            .is_some_and(|(realtime, opponent)| {
+                realtime
+                    && online_users
+                        .signal
+                        .with(|users| !users.username_status.contains_key(&opponent))
+            })
+    });
+    let claim_victory_after = Resource::once(get_claim_victory_after);
+    let can_claim_victory = RwSignal::new(false);
+    let claim_timeout = store_value(None::<TimeoutHandle>);
+    create_effect(move |_| {
+        let gone = opponent_gone();
+        let after = claim_victory_after
+            .get()
+            .and_then(Result::ok)
+            .unwrap_or(CLAIM_VICTORY_AFTER);
+        if let Some(handle) = claim_timeout.get_value() {
+            handle.clear();
+        }
+        can_claim_victory.set(false);
+        if gone {
+            claim_timeout.set_value(
+                set_timeout_with_handle(move || can_claim_victory.set(true), after).ok(),
+            );
+        }
+    });
+
     let new_opponent = move |_| {
         let game_state = expect_context::<GameStateSignal>();
 
@@ -79,6 +131,8 @@ pub fn ControlButtons() -> impl IntoView {
                 time_mode: game.time_mode,
                 time_base: game.time_base,
                 time_increment: game.time_increment,
+                clock_type: game.clock_type,
+                repetition_rule: game.repetition_rule,
                 band_upper: None,
                 band_lower: None,
             };
@@ -90,104 +144,52 @@ pub fn ControlButtons() -> impl IntoView {
         }
     };
 
-    let rematch_present = move || {
-        let challenge_state_signal = expect_context::<ChallengeStateSignal>();
-        let game_state = game_state.signal.get();
-        if let Some(game_response) = game_state.game_response {
-            challenge_state_signal
-                .signal
-                .get()
-                .challenges
-                .values()
-                .find(|challenge| {
-                    challenge.visibility == ChallengeVisibility::Direct
-                        && challenge.opponent.clone().is_some_and(|ref opponent| {
-                            opponent.uid == game_response.black_player.uid
-                                || opponent.uid == game_response.white_player.uid
-                        })
-                        && (challenge.challenger.uid == game_response.black_player.uid
-                            || challenge.challenger.uid == game_response.white_player.uid)
-                        && challenge.game_type == game_response.game_type.to_string()
-                        && challenge.time_mode == game_response.time_mode
-                        && challenge.time_base == game_response.time_base
-                        && challenge.time_increment == game_response.time_increment
-                })
-                .cloned()
-        } else {
-            None
-        }
+    let rematch_offer = create_read_slice(game_state.signal, |gs| gs.rematch_offer);
+
+    let sent_rematch = move || {
+        rematch_offer().is_some_and(|offered_by| user().is_some_and(|user| user.id == offered_by))
     };
 
-    let sent_challenge = move || {
-        if let Some(challenge) = rematch_present() {
-            if let Some(user) = user() {
-                return challenge.challenger.uid == user.id;
-            }
-        }
-        false
+    let received_rematch = move || {
+        rematch_offer().is_some_and(|offered_by| user().is_some_and(|user| user.id != offered_by))
     };
 
     let rematch_button_color = move || {
-        if let Some(challenge) = rematch_present() {
-            if let Some(user) = user() {
-                if challenge.challenger.uid != user.id {
-                    return "bg-grasshopper-green hover:bg-green-500";
-                }
-            }
+        if received_rematch() {
+            "bg-grasshopper-green hover:bg-green-500"
+        } else {
+            "bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal"
         }
-        "bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal"
     };
 
     let rematch_text = move || {
-        if let Some(challenge) = rematch_present() {
-            if let Some(user) = user() {
-                if challenge.challenger.uid == user.id {
-                    "Sent"
-                } else {
-                    "Accept"
-                }
-            } else {
-                "Not logged in"
-            }
+        if sent_rematch() {
+            "Cancel rematch"
+        } else if received_rematch() {
+            "Accept rematch"
         } else {
             "Rematch"
         }
     };
 
     let rematch = move |_| {
-        if let Some(challenge) = rematch_present() {
-            let api = ApiRequests::new();
-            api.challenge_accept(challenge.challenge_id);
-        } else {
-            let game_state = expect_context::<GameStateSignal>();
-            let auth_context = expect_context::<AuthContext>();
-            if let Some(Ok(Some(user))) = untrack(auth_context.user) {
-                if let Some(game) = game_state.signal.get_untracked().game_response {
-                    // TODO: color and opponent
-                    let (color_choice, opponent) = if user.id == game.black_player.uid {
-                        (ColorChoice::White, Some(game.white_player.username))
-                    } else if user.id == game.white_player.uid {
-                        (ColorChoice::Black, Some(game.black_player.username))
-                    } else {
-                        unreachable!();
-                    };
-                    let details = ChallengeDetails {
-                        rated: game.rated,
-                        game_type: game.game_type,
-                        visibility: ChallengeVisibility::Direct,
-                        opponent,
-                        color_choice,
-                        time_mode: game.time_mode,
-                        time_base: game.time_base,
-                        time_increment: game.time_increment,
-                        band_upper: None,
-                        band_lower: None,
-                    };
-                    let challenge_action = ChallengeAction::Create(details);
-                    let api = ApiRequests::new();
-                    api.challenge(challenge_action);
-                }
-            }
+        if let Some(game_id) = game_state.signal.get_untracked().game_id {
+            let action = if sent_rematch() {
+                RematchAction::Decline
+            } else if received_rematch() {
+                RematchAction::Accept
+            } else {
+                RematchAction::Offer
+            };
+            ApiRequests::new().rematch(game_id, action);
+        }
+    };
+
+    let premove = create_read_slice(game_state.signal, |gs| gs.premove.clone());
+
+    let decline_rematch = move |_| {
+        if let Some(game_id) = game_state.signal.get_untracked().game_id {
+            ApiRequests::new().rematch(game_id, RematchAction::Decline);
         }
     };
 
@@ -260,6 +262,21 @@ pub fn ControlButtons() -> impl IntoView {
                                     game_control=store_value(GameControl::Resign(color()))
                                     user_id=user_id()
                                 />
+                                <Show when=can_claim_repetition>
+                                    <ConfirmButton
+                                        game_control=store_value(
+                                            GameControl::ClaimRepetition(color()),
+                                        )
+
+                                        user_id=user_id()
+                                    />
+                                </Show>
+                                <Show when=can_claim_victory>
+                                    <ConfirmButton
+                                        game_control=store_value(GameControl::ClaimVictory(color()))
+                                        user_id=user_id()
+                                    />
+                                </Show>
                             </div>
 
                             <div class="flex justify-center w-full h-5">
@@ -269,6 +286,22 @@ pub fn ControlButtons() -> impl IntoView {
                                 <Show when=pending_draw>
                                     <span class="font-bold">"Opponent offers a draw"</span>
                                 </Show>
+                                <Show when=move || premove().is_some()>
+                                    <span class="font-bold">
+                                        {move || {
+                                            premove()
+                                                .map(|turn| format!("Premove: {turn}"))
+                                                .unwrap_or_default()
+                                        }}
+
+                                    </span>
+                                    <button
+                                        class="ml-2 px-1 rounded hover:bg-ladybug-red"
+                                        on:click=move |_| game_state.cancel_premove()
+                                    >
+                                        "✕"
+                                    </button>
+                                </Show>
                             </div>
                         </div>
                     }
@@ -297,11 +330,18 @@ pub fn ControlButtons() -> impl IntoView {
                             )
                         }
 
-                        prop:disabled=sent_challenge
                         on:click=rematch
                     >
                         {rematch_text}
                     </button>
+                    <Show when=received_rematch>
+                        <button
+                            class="flex-shrink-0 px-2 py-1 m-1 h-7 font-bold text-white rounded transition-transform duration-300 transform grow bg-ladybug-red hover:bg-red-400 active:scale-95"
+                            on:click=decline_rematch
+                        >
+                            Decline
+                        </button>
+                    </Show>
                     <button
                         class="flex-shrink-0 px-2 py-1 m-1 h-7 font-bold text-white rounded transition-transform duration-300 transform grow bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95"
                         on:click=new_opponent
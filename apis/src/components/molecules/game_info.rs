@@ -1,5 +1,5 @@
 use leptos::*;
-use shared_types::{TimeInfo, TournamentId};
+use shared_types::{PrettyString, RepetitionRule, TimeInfo, TournamentId};
 
 use crate::{components::molecules::time_row::TimeRow, providers::game_state::GameStateSignal};
 
@@ -13,8 +13,11 @@ pub fn GameInfo(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoV
                     mode: gr.time_mode.clone(),
                     base: gr.time_base,
                     increment: gr.time_increment,
+                    clock: gr.clock_type,
                 },
                 gr.rated,
+                gr.repetition_rule,
+                gr.annulled,
             )
         })
     });
@@ -28,10 +31,21 @@ pub fn GameInfo(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoV
         })
     });
     move || {
-        if let (Some((time_info, rated)), Some((is_tournament, name, nanoid))) =
-            (game_info(), tournaemnt_info())
+        if let (
+            Some((time_info, rated, repetition_rule, annulled)),
+            Some((is_tournament, name, nanoid)),
+        ) = (game_info(), tournaemnt_info())
         {
-            let rated = format!("• {}", if rated { "Rated" } else { "Casual" });
+            let rated = format!(
+                "• {}",
+                match (annulled, rated) {
+                    (true, _) => "Annulled",
+                    (false, true) => "Rated",
+                    (false, false) => "Casual",
+                }
+            );
+            let repetition = (repetition_rule == RepetitionRule::Claimed)
+                .then(|| format!("• {}", repetition_rule.pretty_string()));
             let name = store_value(name);
             let name = move || {
                 if let Some(name) = name() {
@@ -53,6 +67,7 @@ pub fn GameInfo(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoV
                     <div class="flex gap-1 items-center">
                         <TimeRow time_info extend_tw_classes="whitespace-nowrap"/>
                         {rated}
+                        {repetition}
                         <Show when=move || is_tournament>
                             <a href=link>{name()}</a>
                         </Show>
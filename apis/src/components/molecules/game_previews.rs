@@ -7,7 +7,7 @@ use crate::{
 };
 use hive_lib::{Color, GameStatus};
 use leptos::*;
-use shared_types::{GameSpeed, PrettyString, TimeInfo};
+use shared_types::{PrettyString, TimeInfo};
 
 #[component]
 pub fn GamePreviews(
@@ -41,19 +41,9 @@ pub fn GamePreviews(
                 {format!(
                     "{} {} vs {} {}",
                     game.white_player.username,
-                    game
-                        .white_player
-                        .ratings
-                        .get(&GameSpeed::from_base_increment(game.time_base, game.time_increment))
-                        .expect("White has a rating")
-                        .rating,
+                    game.white_rating(),
                     game.black_player.username,
-                    game
-                        .black_player
-                        .ratings
-                        .get(&GameSpeed::from_base_increment(game.time_base, game.time_increment))
-                        .expect("Black has a rating")
-                        .rating,
+                    game.black_rating(),
                 )}
             }
             .into_view()
@@ -67,6 +57,7 @@ pub fn GamePreviews(
                     mode: game.time_mode.clone(),
                     base: game.time_base,
                     increment: game.time_increment,
+                    clock: game.clock_type,
                 });
                 let game = store_value(game);
                 let needs_start = move || {
@@ -91,6 +91,7 @@ pub fn GameRow(game: StoredValue<GameResponse>) -> impl IntoView {
         mode: game().time_mode.clone(),
         base: game().time_base,
         increment: game().time_increment,
+        clock: game().clock_type,
     };
 
     view! {
@@ -1,22 +1,42 @@
-use crate::components::atoms::rating::RatingWithIcon;
+use crate::components::atoms::{avatar::Avatar, flag::Flag, rating::RatingWithIcon};
 use crate::responses::UserResponse;
 use leptos::*;
-use shared_types::GameSpeed;
+use shared_types::{rated_game_types, GameSpeed};
 
 #[component]
 pub fn HoverRating(user: StoredValue<UserResponse>) -> impl IntoView {
-    let ratings = GameSpeed::all_rated_games()
-        .iter()
-        .map(|speed| {
-            if let Some(rating) = user().ratings.get(speed) {
-                view! { <RatingWithIcon rating=store_value(rating.clone())/> }
-            } else {
-                "".into_view()
+    let ratings = rated_game_types()
+        .into_iter()
+        .map(|game_type| {
+            let ratings = GameSpeed::all_rated_games()
+                .iter()
+                .map(|speed| {
+                    if let Some(rating) = user().rating_response(&game_type, speed) {
+                        view! { <RatingWithIcon rating=store_value(rating.clone())/> }
+                    } else {
+                        "".into_view()
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class="font-bold">{game_type.to_string()}</div>
+                {ratings}
             }
         })
         .collect_view();
     view! {
         <div class="absolute bottom-0 -left-24 z-40 p-2 rounded bg-even-light dark:bg-gray-950">
+            <div class="flex gap-2 items-center mb-1">
+                <Avatar user extend_tw_classes="w-10 h-10"/>
+                <div>
+                    <div class="font-bold">
+                        {user().username} " " <Flag country=user().country/>
+                    </div>
+                    {user()
+                        .preferred_game_type
+                        .map(|game_type| format!("Prefers {game_type}"))}
+                </div>
+            </div>
             {ratings}
         </div>
     }
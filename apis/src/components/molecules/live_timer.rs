@@ -36,6 +36,7 @@ pub fn LiveTimer(side: Color) -> impl IntoView {
             String::new()
         }
     });
+    let delay_left = create_rw_signal(Duration::ZERO);
     let tick_rate = Duration::from_millis(100);
     let Pausable {
         pause,
@@ -43,6 +44,10 @@ pub fn LiveTimer(side: Color) -> impl IntoView {
         is_active,
     } = use_interval_fn_with_options(
         move || {
+            if delay_left.get_untracked() > Duration::ZERO {
+                delay_left.update(|d| *d = d.saturating_sub(tick_rate));
+                return;
+            }
             batch(move || {
                 time_left.update(|t| {
                     *t = t.checked_sub(tick_rate).unwrap_or(Duration::from_millis(0));
@@ -61,6 +66,9 @@ pub fn LiveTimer(side: Color) -> impl IntoView {
         let timer = timer_signal.signal.get();
         if in_progress() {
             if (side == Color::White) == (timer.turn % 2 == 0) && !timer.finished {
+                if !is_active() {
+                    delay_left.set(timer.delay());
+                }
                 resume();
             } else if is_active() {
                 pause();
@@ -0,0 +1,47 @@
+use crate::{components::atoms::profile_link::ProfileLink, responses::UserResponse};
+use leptos::*;
+use shared_types::MatchScore;
+
+#[component]
+pub fn MatchRow(
+    one: StoredValue<UserResponse>,
+    two: StoredValue<UserResponse>,
+    score: MatchScore,
+) -> impl IntoView {
+    let armageddon = match score.armageddon_winner {
+        Some(winner) if winner == one().uid => "(A) 1-0",
+        Some(_) => "(A) 0-1",
+        None if score.armageddons > 0 => "(A) *",
+        None => "",
+    };
+    let result = if score.finished() {
+        format!("{} - {}", score.one_game_points, score.two_game_points)
+    } else {
+        format!("({} - {})", score.one_game_points, score.two_game_points)
+    };
+
+    view! {
+        <div class="flex justify-between items-center p-1 w-80 h-10 dark:odd:bg-header-twilight dark:even:bg-reserve-twilight odd:bg-odd-light even:bg-even-light">
+            <div class="flex justify-end w-28">
+                <ProfileLink
+                    patreon=one().patreon
+                    username=one().username
+                    extend_tw_classes="truncate max-w-[110px]"
+                    user_is_hoverable=one
+                />
+            </div>
+            <div class="flex flex-col items-center text-sm">
+                <div class="font-bold">{result}</div>
+                <div class="text-xs">{armageddon}</div>
+            </div>
+            <div class="flex justify-start w-28">
+                <ProfileLink
+                    patreon=two().patreon
+                    username=two().username
+                    extend_tw_classes="truncate max-w-[110px]"
+                    user_is_hoverable=two
+                />
+            </div>
+        </div>
+    }
+}
@@ -4,6 +4,7 @@ pub mod banner;
 pub mod board_pieces;
 pub mod challenge_row;
 pub mod chat_and_controls;
+pub mod conditional_moves;
 pub mod control_buttons;
 pub mod game_info;
 pub mod game_previews;
@@ -14,6 +15,7 @@ pub mod history_pieces;
 pub mod hover_ratings;
 pub mod invite_user;
 pub mod live_timer;
+pub mod match_row;
 pub mod modal;
 pub mod ping;
 pub mod rating_and_change;
@@ -21,6 +23,7 @@ pub mod rl_banner;
 pub mod score_row;
 pub mod simple_hex_stack;
 pub mod thumbnail_pieces;
+pub mod time_chart;
 pub mod time_row;
 pub mod tournament_invitation_row;
 pub mod tournament_row;
@@ -1,5 +1,7 @@
 use crate::{
-    components::atoms::{profile_link::ProfileLink, status_indicator::StatusIndicator},
+    components::atoms::{
+        avatar::Avatar, flag::Flag, profile_link::ProfileLink, status_indicator::StatusIndicator,
+    },
     responses::UserResponse,
 };
 use leptos::*;
@@ -28,9 +30,11 @@ pub fn ScoreRow(
             <div class="flex justify-between mr-2 w-full">
                 <div class="flex items-center w-6">{standing}</div>
 
-                <div class="flex items-center">
+                <div class="flex gap-1 items-center">
+                    <Avatar user extend_tw_classes="w-6 h-6"/>
                     <StatusIndicator username=user().username/>
                     {profile_link()}
+                    <Flag country=user().country/>
                 </div>
 
                 {tiebreakers
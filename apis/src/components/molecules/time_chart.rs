@@ -0,0 +1,76 @@
+use crate::providers::game_state::GameStateSignal;
+use leptos::*;
+
+const HEIGHT: f64 = 60.0;
+const BAR_WIDTH: f64 = 4.0;
+
+// Time used per move, white above the middle line and black below it
+#[component]
+pub fn TimeChart() -> impl IntoView {
+    let game_state = expect_context::<GameStateSignal>();
+    let durations = create_read_slice(game_state.signal, |gs| {
+        gs.game_response
+            .as_ref()
+            .map(|gr| gr.move_durations())
+            .unwrap_or_default()
+    });
+    let bars = move || {
+        let durations = durations();
+        let longest = durations
+            .iter()
+            .flatten()
+            .map(|duration| duration.as_secs_f64())
+            .fold(0.0, f64::max);
+        durations
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, duration)| {
+                let duration = duration?;
+                let height = if longest > 0.0 {
+                    duration.as_secs_f64() / longest * HEIGHT / 2.0
+                } else {
+                    0.0
+                };
+                let (y, class) = if i % 2 == 0 {
+                    (HEIGHT / 2.0 - height, "fill-gray-300")
+                } else {
+                    (HEIGHT / 2.0, "fill-gray-700 dark:fill-gray-500")
+                };
+                Some(view! {
+                    <rect
+                        x=i as f64 * BAR_WIDTH
+                        y=y
+                        width=BAR_WIDTH - 1.0
+                        height=height
+                        class=class
+                    >
+                        <title>{format!("{}. {:.1}s", i + 1, duration.as_secs_f64())}</title>
+                    </rect>
+                })
+            })
+            .collect_view()
+    };
+    let width = move || (durations().len() as f64 * BAR_WIDTH).max(BAR_WIDTH);
+    view! {
+        <Show when=move || durations().iter().any(|duration| duration.is_some())>
+            <div class="p-2 border-t-2 border-black dark:border-white">
+                <div class="font-bold">Time per move</div>
+                <svg
+                    class="w-full h-16"
+                    viewBox=move || format!("0 0 {} {HEIGHT}", width())
+                    preserveAspectRatio="none"
+                >
+                    <line
+                        x1=0
+                        y1=HEIGHT / 2.0
+                        x2=width
+                        y2=HEIGHT / 2.0
+                        class="stroke-black dark:stroke-white"
+                        stroke-width=0.5
+                    />
+                    {bars}
+                </svg>
+            </div>
+        </Show>
+    }
+}
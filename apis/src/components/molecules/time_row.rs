@@ -14,15 +14,14 @@ pub fn TimeRow(
             TimeMode::Untimed => GameSpeed::Untimed,
             TimeMode::Correspondence => GameSpeed::Correspondence,
             TimeMode::RealTime => {
-                GameSpeed::from_base_increment(time_info.base, time_info.increment)
+                GameSpeed::from_base_increment(time_info.base, time_info.increment, time_info.clock)
             }
         };
         view! { <Icon icon=icon_for_speed(&speed) class="w-4 h-4"/> }
     };
     let text = move || match time_mode() {
         TimeMode::Untimed => "No time limit".to_owned(),
-        TimeMode::RealTime => format!(
-            "{}m + {}s",
+        TimeMode::RealTime => time_info.clock.describe(
             time_info.base.expect("Time exists") / 60,
             time_info.increment.expect("Increment exists"),
         ),
@@ -28,6 +28,7 @@ pub fn TournamentInvitationNotification(tournament: RwSignal<TournamentResponse>
         mode: tournament().time_mode,
         base: tournament().time_base,
         increment: tournament().time_increment,
+        clock: tournament().clock_type,
     };
 
     view! {
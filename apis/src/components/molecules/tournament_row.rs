@@ -39,6 +39,7 @@ pub fn TournamentRow(tournament: TournamentResponse) -> impl IntoView {
         mode: tournament.time_mode,
         base: tournament.time_base,
         increment: tournament.time_increment,
+        clock: tournament.clock_type,
     };
     view! {
         <article class="flex relative flex-col justify-between items-center px-2 py-4 mx-2 w-5/6 h-32 duration-300 dark:odd:bg-header-twilight dark:even:bg-reserve-twilight odd:bg-odd-light even:bg-even-light hover:bg-blue-light hover:dark:bg-teal-900">
@@ -1,12 +1,13 @@
 use crate::{
     common::UserAction,
     components::atoms::{
-        direct_challenge_button::DirectChallengeButton, invite_button::InviteButton,
-        kick_button::KickButton, profile_link::ProfileLink, rating::Rating,
-        status_indicator::StatusIndicator, uninvite_button::UninviteButton,
+        avatar::Avatar, direct_challenge_button::DirectChallengeButton, flag::Flag,
+        invite_button::InviteButton, kick_button::KickButton, profile_link::ProfileLink,
+        rating::Rating, status_indicator::StatusIndicator, uninvite_button::UninviteButton,
     },
     responses::UserResponse,
 };
+use hive_lib::GameType;
 use leptos::*;
 use shared_types::GameSpeed;
 
@@ -16,11 +17,15 @@ pub fn UserRow(
     actions: Vec<UserAction>,
     #[prop(optional)] end_str: String,
     #[prop(optional)] game_speed: Option<StoredValue<GameSpeed>>,
+    #[prop(optional)] game_type: Option<GameType>,
     #[prop(optional)] on_profile: bool,
+    #[prop(optional)] with_avatar: bool,
 ) -> impl IntoView {
     let rating = move || {
         if let Some(speed) = game_speed {
-            user().ratings.get(&speed()).cloned()
+            user()
+                .rating_response(&game_type.unwrap_or(GameType::MLP), &speed())
+                .cloned()
         } else {
             None
         }
@@ -76,9 +81,13 @@ pub fn UserRow(
     view! {
         <div class=format!("flex p-1 items-center justify-between h-10 w-64 {color}")>
             <div class="flex justify-between mr-2 w-48">
-                <div class="flex items-center">
+                <div class="flex gap-1 items-center">
+                    <Show when=move || with_avatar>
+                        <Avatar user extend_tw_classes="w-6 h-6"/>
+                    </Show>
                     <StatusIndicator username=user().username/>
                     {profile_link()}
+                    <Flag country=user().country/>
                 </div>
                 <Show when=move || { rating().is_some() }>
                     <Rating rating=rating().expect("Rating is some")/>
@@ -30,9 +30,10 @@ pub fn UserWithRating(
     };
     let username = move || player().map_or(String::new(), |p| p.username);
     let patreon = move || player().map_or(false, |p| p.patreon);
-    let rating = move || match (player(), speed()) {
-        (Some(player), Some(speed)) => {
-            view! { <Rating rating=player.ratings.get(&speed).expect("Valid rating from speed").clone()/> }
+    let game_type = move || game_response().map(|resp| resp.game_type);
+    let rating = move || match (player(), speed(), game_type()) {
+        (Some(player), Some(speed), Some(game_type)) => {
+            view! { <Rating rating=player.rating_response(&game_type, &speed).expect("Valid rating from speed").clone()/> }
         }
         _ => view! { "" }.into_view(),
     };
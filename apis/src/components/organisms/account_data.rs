@@ -0,0 +1,64 @@
+use crate::{functions::accounts::delete::DeleteAccount, providers::AuthContext};
+use leptos::*;
+use leptos_router::ActionForm;
+
+const BUTTON_STYLE: &str = "bg-button-dawn dark:bg-button-twilight transform transition-transform duration-300 active:scale-95 hover:bg-pillbug-teal text-white font-bold py-2 px-4 rounded focus:outline-none cursor-pointer";
+
+#[component]
+pub fn AccountData() -> impl IntoView {
+    let user = expect_context::<AuthContext>().user;
+    let delete = create_server_action::<DeleteAccount>();
+    let confirm = RwSignal::new(false);
+    create_effect(move |_| {
+        if delete.value().with(|v| matches!(v, Some(Ok(_)))) {
+            user.refetch();
+        }
+    });
+    let error = move || match delete.value().get() {
+        Some(Err(ServerFnError::ServerError(error))) => error,
+        Some(Err(_)) => String::from("Something went wrong, please try again"),
+        _ => String::new(),
+    };
+
+    view! {
+        <div class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800">
+            <p class="mb-3 font-bold">"Your data"</p>
+            <p class="mb-3">
+                "Download your profile, ratings, games, tournaments and chat messages as JSON, with your games as PGN."
+            </p>
+            <a class=format!("block mb-6 w-fit {BUTTON_STYLE}") href="/account/export" rel="external">
+                "Download my data"
+            </a>
+            <p class="mb-3">
+                "Deleting your account can't be undone. Your games stay, but under a generated name instead of yours."
+            </p>
+            <ActionForm action=delete>
+                <input
+                    class="shadow appearance-none border rounded w-full py-2 px-3 mb-3 leading-tight focus:outline-none"
+                    name="password"
+                    type="password"
+                    autocomplete="current-password"
+                    placeholder="Current password"
+                />
+                <label class="flex items-center mb-3">
+                    <input
+                        type="checkbox"
+                        class="w-4 h-4"
+                        on:change=move |_| confirm.update(|b| *b = !*b)
+                        prop:checked=confirm
+                    />
+                    <span class="ml-2 text-sm">"I want to delete my account"</span>
+                </label>
+                <p class="h-5">
+                    <small class="text-ladybug-red">{error}</small>
+                </p>
+                <input
+                    type="submit"
+                    class=BUTTON_STYLE
+                    prop:disabled=move || !confirm()
+                    value="Delete account"
+                />
+            </ActionForm>
+        </div>
+    }
+}
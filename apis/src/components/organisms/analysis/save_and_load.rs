@@ -92,7 +92,7 @@ pub fn LoadTree() -> impl IntoView {
                         let res = string
                             .as_string()
                             .and_then(|string| { History::from_pgn_str(string).ok() })
-                            .and_then(|history| hive_lib::State::new_from_history(&history).ok())
+                            .and_then(|history| hive_lib::State::new_from_history_with(&history, false).ok())
                             .and_then(|state| {
                                 game_state.signal.update(|gs| gs.state = state);
                                 AnalysisTree::from_state(game_state)
@@ -70,11 +70,15 @@ impl AnalysisTree {
                 (a.piece, a.position)
             })
             .collect::<Vec<_>>();
-        let state = State::new_from_history(&History {
-            moves,
-            game_type: self.game_type,
-            ..History::new()
-        })
+        // Repetitions don't end the analysis
+        let state = State::new_from_history_with(
+            &History {
+                moves,
+                game_type: self.game_type,
+                ..History::new()
+            },
+            false,
+        )
         .ok()?;
 
         let history_turn = self
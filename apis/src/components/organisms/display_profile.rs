@@ -1,29 +1,89 @@
 use crate::{
     common::UserAction,
-    components::{atoms::rating::RatingWithIcon, molecules::user_row::UserRow},
+    components::{
+        atoms::{avatar::Avatar, rating::RatingWithIcon},
+        molecules::user_row::UserRow,
+    },
     responses::UserResponse,
 };
+use hive_lib::GameType;
 use leptos::*;
-use shared_types::GameSpeed;
+use shared_types::{bio_html, country_flag, country_name, rated_game_types, GameSpeed};
+
+// Rendered markdown comes without styles, these bring back the ones a bio needs
+const BIO_STYLE: &str = "break-words [&_a]:text-blue-500 [&_a]:underline [&_ol]:ml-4 [&_ol]:list-decimal [&_p]:mb-1 [&_ul]:ml-4 [&_ul]:list-disc";
+
+#[component]
+fn About(user: StoredValue<UserResponse>) -> impl IntoView {
+    let country = user().country.map(|country| {
+        format!(
+            "{} {}",
+            country_flag(&country),
+            country_name(&country).unwrap_or_default()
+        )
+    });
+    let preferred = user()
+        .preferred_game_type
+        .map(|game_type| format!("Prefers {game_type}"));
+    let links = user()
+        .links
+        .into_iter()
+        .map(|link| {
+            let text = link.trim_start_matches("mailto:").to_owned();
+            view! {
+                <a
+                    class="text-blue-500 break-all hover:underline"
+                    href=link
+                    rel="nofollow noopener"
+                    target="_blank"
+                >
+                    {text}
+                </a>
+            }
+        })
+        .collect_view();
+    view! {
+        <div class="flex gap-3 items-start mb-2">
+            <Avatar user extend_tw_classes="w-24 h-24"/>
+            <div class="flex flex-col max-w-prose">
+                {country}
+                {preferred}
+                <div class=BIO_STYLE inner_html=bio_html(&user().bio)></div>
+                <div class="flex flex-col">{links}</div>
+            </div>
+        </div>
+    }
+}
 
 #[component]
 pub fn DisplayProfile(user: StoredValue<UserResponse>) -> impl IntoView {
-    let ratings = GameSpeed::all_rated_games()
-        .iter()
-        .map(|speed| {
-            if let Some(rating) = user().ratings.get(speed) {
-                view! {
-                    <div class="p-2 border border-dark dark:border-white">
-                        <RatingWithIcon rating=store_value(rating.clone())/>
-                        <div>{format!("Total: {}", rating.played)}</div>
-                        <div>{format!("Wins: {}", rating.win)}</div>
-                        <div>{format!("Losses: {}", rating.loss)}</div>
-                        <div>{format!("Draws: {}", rating.draw)}</div>
-                    </div>
+    let ratings_for = move |game_type: GameType| {
+        GameSpeed::all_rated_games()
+            .iter()
+            .map(|speed| {
+                if let Some(rating) = user().rating_response(&game_type, speed) {
+                    view! {
+                        <div class="p-2 border border-dark dark:border-white">
+                            <RatingWithIcon rating=store_value(rating.clone())/>
+                            <div>{format!("Total: {}", rating.played)}</div>
+                            <div>{format!("Wins: {}", rating.win)}</div>
+                            <div>{format!("Losses: {}", rating.loss)}</div>
+                            <div>{format!("Draws: {}", rating.draw)}</div>
+                        </div>
+                    }
+                    .into_view()
+                } else {
+                    "".into_view()
                 }
-                .into_view()
-            } else {
-                "".into_view()
+            })
+            .collect_view()
+    };
+    let ratings = rated_game_types()
+        .into_iter()
+        .map(|game_type| {
+            view! {
+                <div class="font-bold">{game_type.to_string()}</div>
+                <div class="flex flex-wrap gap-1">{ratings_for(game_type)}</div>
             }
         })
         .collect_view();
@@ -34,7 +94,8 @@ pub fn DisplayProfile(user: StoredValue<UserResponse>) -> impl IntoView {
                 <div class="max-w-fit">
                     <UserRow actions=vec![UserAction::Challenge] user=user on_profile=true/>
                 </div>
-                <div class="flex flex-wrap gap-1">{ratings}</div>
+                <About user/>
+                {ratings}
             </div>
 
         </div>
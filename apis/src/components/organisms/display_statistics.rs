@@ -0,0 +1,174 @@
+use crate::{functions::users::get::get_statistics, pages::profile_view::ProfileGamesView};
+use hive_lib::Color;
+use leptos::*;
+use leptos_router::*;
+use shared_types::{PrettyString, Score, Statistics, RATING_BUCKET};
+
+const TABLE_STYLE: &str = "m-2 text-sm text-left";
+
+#[derive(Params, PartialEq, Eq)]
+struct UsernameParams {
+    username: String,
+}
+
+fn percentage(score: &Score) -> String {
+    score
+        .percentage()
+        .map_or(String::from("-"), |percentage| format!("{percentage:.0}%"))
+}
+
+#[component]
+fn ScoreTable(title: &'static str, rows: Vec<(String, Score)>) -> impl IntoView {
+    let rows = rows
+        .into_iter()
+        .map(|(label, score)| {
+            view! {
+                <tr>
+                    <td class="pr-3">{label}</td>
+                    <td class="pr-3">{score.games()}</td>
+                    <td class="pr-3">{score.wins}</td>
+                    <td class="pr-3">{score.draws}</td>
+                    <td class="pr-3">{score.losses}</td>
+                    <td>{percentage(&score)}</td>
+                </tr>
+            }
+        })
+        .collect_view();
+    view! {
+        <table class=TABLE_STYLE>
+            <tr>
+                <th class="pr-3">{title}</th>
+                <th class="pr-3">"Games"</th>
+                <th class="pr-3">"Won"</th>
+                <th class="pr-3">"Drawn"</th>
+                <th class="pr-3">"Lost"</th>
+                <th>"Score"</th>
+            </tr>
+            {rows}
+        </table>
+    }
+}
+
+#[component]
+fn StatisticsTables(statistics: Statistics) -> impl IntoView {
+    if statistics.score.games() == 0 {
+        return view! { <p class="m-2">"No finished games yet"</p> }.into_view();
+    }
+    let average_turns = statistics
+        .average_turns
+        .map_or(String::from("-"), |turns| format!("{turns:.1}"));
+    let conclusions = statistics
+        .conclusions
+        .iter()
+        .map(|(conclusion, count)| {
+            view! {
+                <tr>
+                    <td class="pr-3">{conclusion.pretty_string()}</td>
+                    <td>{*count}</td>
+                </tr>
+            }
+        })
+        .collect_view();
+    let by_color = statistics
+        .by_color
+        .into_iter()
+        .map(|(color, score)| {
+            let color = match color {
+                Color::White => "White",
+                Color::Black => "Black",
+            };
+            (color.to_owned(), score)
+        })
+        .collect();
+    let by_game_type = statistics
+        .by_game_type
+        .into_iter()
+        .map(|(game_type, score)| (game_type.to_string(), score))
+        .collect();
+    let by_speed = statistics
+        .by_speed
+        .into_iter()
+        .map(|(speed, score)| (speed.to_string(), score))
+        .collect();
+    let by_opponent_rating = statistics
+        .by_opponent_rating
+        .into_iter()
+        .map(|(bucket, score)| (format!("{bucket}-{}", bucket + RATING_BUCKET - 1), score))
+        .collect();
+    let openings = statistics
+        .openings
+        .into_iter()
+        .map(|opening| (opening.moves, opening.score))
+        .collect();
+    view! {
+        <div class="flex flex-wrap">
+            <table class=TABLE_STYLE>
+                <tr>
+                    <td class="pr-3">"Games"</td>
+                    <td>{statistics.score.games()}</td>
+                </tr>
+                <tr>
+                    <td class="pr-3">"Score"</td>
+                    <td>{percentage(&statistics.score)}</td>
+                </tr>
+                <tr>
+                    <td class="pr-3">"Average length"</td>
+                    <td>{average_turns} " turns"</td>
+                </tr>
+                <tr>
+                    <td class="pr-3">"Longest winning streak"</td>
+                    <td>{statistics.longest_win_streak}</td>
+                </tr>
+                <tr>
+                    <td class="pr-3">"Longest losing streak"</td>
+                    <td>{statistics.longest_losing_streak}</td>
+                </tr>
+            </table>
+            <table class=TABLE_STYLE>
+                <tr>
+                    <th class="pr-3">"Ended by"</th>
+                    <th>"Games"</th>
+                </tr>
+                {conclusions}
+            </table>
+            <ScoreTable title="Color" rows=by_color/>
+            <ScoreTable title="Game type" rows=by_game_type/>
+            <ScoreTable title="Speed" rows=by_speed/>
+            <ScoreTable title="Opponent rating" rows=by_opponent_rating/>
+            <ScoreTable title="Opening" rows=openings/>
+        </div>
+    }
+    .into_view()
+}
+
+#[component]
+pub fn DisplayStatistics() -> impl IntoView {
+    let params = use_params::<UsernameParams>();
+    let username = move || {
+        params.with(|params| {
+            params
+                .as_ref()
+                .map(|params| params.username.clone())
+                .unwrap_or_default()
+        })
+    };
+    let statistics = Resource::new(username, get_statistics);
+    let is_active = expect_context::<RwSignal<ProfileGamesView>>();
+    let elem = create_node_ref::<html::Div>();
+    elem.on_load(move |_| is_active.update(|v| *v = ProfileGamesView::Statistics));
+    view! {
+        <div ref=elem class="flex flex-col items-center w-full">
+            <Transition>
+                {move || {
+                    statistics
+                        .get()
+                        .map(|statistics| match statistics {
+                            Ok(statistics) => view! { <StatisticsTables statistics/> }.into_view(),
+                            Err(_) => view! { "Could not load the statistics" }.into_view(),
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
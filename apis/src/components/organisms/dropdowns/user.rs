@@ -6,7 +6,7 @@ use crate::components::organisms::logout::Logout;
 use leptos::*;
 
 #[component]
-pub fn UserDropdown(username: String) -> impl IntoView {
+pub fn UserDropdown(username: String, guest: bool) -> impl IntoView {
     let hamburger_show = create_rw_signal(false);
     let onclick_close = move || hamburger_show.update(|b| *b = false);
     view! {
@@ -16,22 +16,39 @@ pub fn UserDropdown(username: String) -> impl IntoView {
             dropdown_style="mr-1 xs:mt-0 mt-1 flex flex-col items-stretch absolute bg-even-light dark:bg-gray-950 text-black border border-gray-300 rounded-md p-2 right-0 lg:right-10"
             content=username.clone()
         >
-            <a
-                class=COMMON_LINK_STYLE
-                href=format!("/@/{}", username)
-
-                on:click=move |_| onclick_close()
+            <Show
+                when=move || !guest
+                fallback=move || {
+                    view! {
+                        <a
+                            class=COMMON_LINK_STYLE
+                            href="/register"
+                            on:focus=move |_| set_redirect()
+                            on:click=move |_| onclick_close()
+                        >
+                            Sign up to keep your games
+                        </a>
+                    }
+                }
             >
-                Profile
-            </a>
-            <a
-                class=COMMON_LINK_STYLE
-                href="/account"
-                on:focus=move |_| set_redirect()
-                on:click=move |_| onclick_close()
-            >
-                Edit Account
-            </a>
+
+                <a
+                    class=COMMON_LINK_STYLE
+                    href=format!("/@/{}", username)
+
+                    on:click=move |_| onclick_close()
+                >
+                    Profile
+                </a>
+                <a
+                    class=COMMON_LINK_STYLE
+                    href="/account"
+                    on:focus=move |_| set_redirect()
+                    on:click=move |_| onclick_close()
+                >
+                    Edit Account
+                </a>
+            </Show>
             <a
                 class=COMMON_LINK_STYLE
                 href="/config"
@@ -0,0 +1,223 @@
+use crate::{
+    functions::accounts::edit::{EditProfile as EditProfileAction, RemoveAvatar, UploadAvatar},
+    providers::AuthContext,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hive_lib::GameType;
+use leptos::*;
+use shared_types::{country_flag, COUNTRIES, MAX_BIO_LENGTH, MAX_LINKS};
+use wasm_bindgen::{closure::Closure, JsValue};
+use web_sys::js_sys::Uint8Array;
+
+const INPUT_STYLE: &str =
+    "shadow appearance-none border rounded w-full py-2 px-3 mb-3 leading-tight focus:outline-none";
+const BUTTON_STYLE: &str = "bg-button-dawn dark:bg-button-twilight transform transition-transform duration-300 active:scale-95 hover:bg-pillbug-teal text-white font-bold py-2 px-4 rounded focus:outline-none cursor-pointer";
+const GAME_TYPES: [GameType; 8] = [
+    GameType::Base,
+    GameType::M,
+    GameType::L,
+    GameType::P,
+    GameType::ML,
+    GameType::MP,
+    GameType::LP,
+    GameType::MLP,
+];
+
+fn error_message(error: ServerFnError) -> String {
+    match error {
+        ServerFnError::ServerError(error) => error,
+        _ => String::from("Something went wrong, please try again"),
+    }
+}
+
+// Shows the new avatar once it changed, or why it could not
+fn avatar_changed<I: 'static>(
+    action: Action<I, Result<(), ServerFnError>>,
+    status: RwSignal<String>,
+    avatar_version: RwSignal<usize>,
+) {
+    create_effect(move |_| match action.value().get() {
+        Some(Ok(())) => {
+            status.set(String::new());
+            avatar_version.update(|version| *version += 1);
+        }
+        Some(Err(error)) => status.set(error_message(error)),
+        None => {}
+    });
+}
+
+#[component]
+pub fn EditProfile() -> impl IntoView {
+    let user = expect_context::<AuthContext>().user;
+    let account = move || match user() {
+        Some(Ok(Some(account))) if !account.user.guest => Some(account),
+        _ => None,
+    };
+    let bio = RwSignal::new(String::new());
+    let country = RwSignal::new(String::new());
+    let preferred_game_type = RwSignal::new(String::new());
+    let links = RwSignal::new(vec![String::new(); MAX_LINKS]);
+    let status = RwSignal::new(String::new());
+    // Uploads keep the same address, the version makes the preview fetch the new image
+    let avatar_version = RwSignal::new(0_usize);
+
+    create_effect(move |_| {
+        if let Some(account) = account() {
+            bio.set(account.user.bio);
+            country.set(account.user.country.unwrap_or_default());
+            preferred_game_type.set(
+                account
+                    .user
+                    .preferred_game_type
+                    .map(|game_type| game_type.to_string())
+                    .unwrap_or_default(),
+            );
+            let mut saved = account.user.links;
+            saved.resize(MAX_LINKS, String::new());
+            links.set(saved);
+        }
+    });
+
+    let edit = create_server_action::<EditProfileAction>();
+    let upload = create_server_action::<UploadAvatar>();
+    let remove = create_server_action::<RemoveAvatar>();
+    create_effect(move |_| match edit.value().get() {
+        Some(Ok(_)) => {
+            status.set(String::from("Saved"));
+            user.refetch();
+        }
+        Some(Err(error)) => status.set(error_message(error)),
+        None => {}
+    });
+    avatar_changed(upload, status, avatar_version);
+    avatar_changed(remove, status, avatar_version);
+
+    let avatar_src = move || {
+        account()
+            .map(|account| format!("{}?v={}", account.user.avatar_url(), avatar_version()))
+            .unwrap_or_default()
+    };
+    let on_avatar = move |ev: ev::Event| {
+        let Some(file) = event_target::<web_sys::HtmlInputElement>(&ev)
+            .files()
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+        let read: Closure<dyn FnMut(JsValue)> = Closure::once(move |buffer: JsValue| {
+            let image = Uint8Array::new(&buffer).to_vec();
+            upload.dispatch(UploadAvatar {
+                image: STANDARD.encode(image),
+            });
+        });
+        let _ = file.array_buffer().then(&read);
+        // Runs once the file is read, long after this handler returned
+        read.forget();
+    };
+    let save = move |_| {
+        edit.dispatch(EditProfileAction {
+            bio: bio(),
+            country: country(),
+            preferred_game_type: preferred_game_type(),
+            links: links(),
+        })
+    };
+    let link_inputs = move || {
+        (0..MAX_LINKS)
+            .map(|index| {
+                view! {
+                    <input
+                        class=INPUT_STYLE
+                        type="url"
+                        placeholder="https://"
+                        prop:value=move || links.with(|links| links[index].clone())
+                        on:input=move |ev| {
+                            links.update(|links| links[index] = event_target_value(&ev))
+                        }
+                    />
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <Show when=move || account().is_some()>
+            <div class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800">
+                <p class="mb-3 font-bold">"Profile"</p>
+                <div class="flex gap-3 items-center mb-3">
+                    <img src=avatar_src alt="" class="w-16 h-16 rounded"/>
+                    <div class="flex flex-col gap-1">
+                        <label class=BUTTON_STYLE>
+                            "Upload avatar"
+                            <input
+                                class="hidden"
+                                type="file"
+                                accept="image/png,image/jpeg,image/webp"
+                                on:change=on_avatar
+                            />
+                        </label>
+                        <button
+                            class=BUTTON_STYLE
+                            on:click=move |_| remove.dispatch(RemoveAvatar {})
+                        >
+                            "Use generated"
+                        </button>
+                    </div>
+                </div>
+                <label class="block mb-2 font-bold">"Bio"</label>
+                <textarea
+                    class=INPUT_STYLE
+                    rows="4"
+                    maxlength=MAX_BIO_LENGTH
+                    placeholder="Markdown works here"
+                    prop:value=bio
+                    on:input=move |ev| bio.set(event_target_value(&ev))
+                ></textarea>
+                <label class="block mb-2 font-bold">"Country"</label>
+                <select
+                    class=INPUT_STYLE
+                    on:change=move |ev| country.set(event_target_value(&ev))
+                >
+                    <option value="">"None"</option>
+                    {COUNTRIES
+                        .into_iter()
+                        .map(|(code, name)| {
+                            view! {
+                                <option value=code selected=move || country() == code>
+                                    {format!("{} {name}", country_flag(code))}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <label class="block mb-2 font-bold">"Preferred game type"</label>
+                <select
+                    class=INPUT_STYLE
+                    on:change=move |ev| preferred_game_type.set(event_target_value(&ev))
+                >
+                    <option value="">"None"</option>
+                    {GAME_TYPES
+                        .into_iter()
+                        .map(|game_type| {
+                            let value = game_type.to_string();
+                            let selected = move || preferred_game_type() == game_type.to_string();
+                            view! {
+                                <option value=value.clone() selected=selected>
+                                    {value}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <label class="block mb-2 font-bold">"Links"</label>
+                {link_inputs}
+                <button class=BUTTON_STYLE on:click=save>
+                    "Save profile"
+                </button>
+                <p class="mt-3 h-5">
+                    <small>{status}</small>
+                </p>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    functions::accounts::notifications::SetNotifications, providers::AuthContext,
+    responses::NotificationSettingsResponse,
+};
+use leptos::*;
+
+const CHECKBOX_STYLE: &str = "w-4 h-4 text-blue-600 bg-gray-100 rounded border-gray-300 focus:ring-blue-500 dark:focus:ring-blue-600 dark:ring-offset-gray-800 focus:ring-2 dark:bg-gray-700 dark:border-gray-600";
+
+#[component]
+fn NotificationToggle(
+    text: &'static str,
+    checked: Signal<bool>,
+    toggle: fn(&mut NotificationSettingsResponse),
+    set: Callback<fn(&mut NotificationSettingsResponse)>,
+) -> impl IntoView {
+    view! {
+        <div class="flex">
+            <input
+                on:change=move |_| set.call(toggle)
+                type="checkbox"
+                class=CHECKBOX_STYLE
+                prop:checked=checked
+            />
+            <label class="ml-2 text-sm font-medium text-gray-900 dark:text-gray-300">{text}</label>
+        </div>
+    }
+}
+
+#[component]
+pub fn EmailNotifications() -> impl IntoView {
+    let user = expect_context::<AuthContext>().user;
+    let action = create_server_action::<SetNotifications>();
+    let settings = Signal::derive(move || match user() {
+        Some(Ok(Some(account))) => Some((account.notifications, account.email_verified)),
+        _ => None,
+    });
+    let notifications = move || {
+        settings()
+            .map(|(notifications, _)| notifications)
+            .unwrap_or_default()
+    };
+    create_effect(move |_| {
+        if action.value().with(|v| matches!(v, Some(Ok(_)))) {
+            user.refetch();
+        }
+    });
+    let set = Callback::new(move |toggle: fn(&mut NotificationSettingsResponse)| {
+        let mut notifications = notifications();
+        toggle(&mut notifications);
+        action.dispatch(SetNotifications {
+            your_turn: notifications.your_turn,
+            time_running_low: notifications.time_running_low,
+            tournament_starting: notifications.tournament_starting,
+            tournament_invitation: notifications.tournament_invitation,
+        });
+    });
+
+    view! {
+        <Show when=move || settings().is_some()>
+            <div class="flex flex-col gap-1 px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800">
+                <p class="mb-2 font-bold">"E-mail notifications"</p>
+                <Show when=move || settings().is_some_and(|(_, verified)| !verified)>
+                    <p class="mb-2 text-sm text-ladybug-red">
+                        "We only send e-mails once your address is verified."
+                    </p>
+                </Show>
+                <NotificationToggle
+                    text="It's my turn in a game"
+                    checked=Signal::derive(move || notifications().your_turn)
+                    toggle=|n| n.your_turn = !n.your_turn
+                    set=set
+                />
+                <NotificationToggle
+                    text="My time is running low"
+                    checked=Signal::derive(move || notifications().time_running_low)
+                    toggle=|n| n.time_running_low = !n.time_running_low
+                    set=set
+                />
+                <NotificationToggle
+                    text="A tournament I joined is starting"
+                    checked=Signal::derive(move || notifications().tournament_starting)
+                    toggle=|n| n.tournament_starting = !n.tournament_starting
+                    set=set
+                />
+                <NotificationToggle
+                    text="I got invited to a tournament"
+                    checked=Signal::derive(move || notifications().tournament_invitation)
+                    toggle=|n| n.tournament_invitation = !n.tournament_invitation
+                    set=set
+                />
+            </div>
+        </Show>
+    }
+}
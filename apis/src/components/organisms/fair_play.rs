@@ -0,0 +1,96 @@
+use crate::{
+    functions::admin::fair_play::get_fair_play_reports, responses::FairPlayReportResponse,
+};
+use leptos::*;
+use shared_types::PrettyString;
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or(String::from("-"), |value| format!("{:.0}%", value * 100.0))
+}
+
+fn number(value: Option<f64>) -> String {
+    value.map_or(String::from("-"), |value| format!("{value:.2}"))
+}
+
+#[component]
+fn FairPlayRow(report: FairPlayReportResponse) -> impl IntoView {
+    let href = format!("/@/{}", report.username);
+    let flags = report
+        .flags
+        .iter()
+        .map(|flag| flag.pretty_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let gap = report
+        .performance_gap
+        .map_or(String::from("-"), |gap| format!("{gap:+.0}"));
+    let row_class = if report.flags.is_empty() {
+        ""
+    } else {
+        "text-ladybug-red"
+    };
+    view! {
+        <tr class=row_class>
+            <td class="pr-3">
+                <a href=href>{report.username}</a>
+            </td>
+            <td class="pr-3">{report.games}</td>
+            <td class="pr-3">{report.moves}</td>
+            <td class="pr-3">{percent(report.engine_match_rate)}</td>
+            <td class="pr-3">{number(report.move_time_variation)}</td>
+            <td class="pr-3">{gap}</td>
+            <td class="pr-3">{flags}</td>
+            <td class="whitespace-nowrap">{report.analysed_at.format("%Y-%m-%d").to_string()}</td>
+        </tr>
+    }
+}
+
+#[component]
+pub fn FairPlayReports() -> impl IntoView {
+    let reports = Resource::once(get_fair_play_reports);
+    view! {
+        <div class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800">
+            <p class="mb-3 font-bold">"Fair play"</p>
+            <p class="mb-3">
+                "Refreshed daily from the latest games of everyone who played. Flags only mark accounts worth a closer look, they are not proof of anything."
+            </p>
+            <p class="mb-3">
+                "Heuristic matches compare the moves with a shallow heuristic that looks a single move ahead, not with a real engine. Strong players agree with it often, so read it together with the other columns."
+            </p>
+            <Transition>
+                {move || {
+                    reports
+                        .get()
+                        .map(|reports| match reports {
+                            Ok(reports) => {
+                                view! {
+                                    <table class="text-sm text-left">
+                                        <tr>
+                                            <th class="pr-3">"Player"</th>
+                                            <th class="pr-3">"Games"</th>
+                                            <th class="pr-3">"Moves"</th>
+                                            <th class="pr-3">"Heuristic matches"</th>
+                                            <th class="pr-3">"Move time variation"</th>
+                                            <th class="pr-3">"Performance gap"</th>
+                                            <th class="pr-3">"Flags"</th>
+                                            <th>"Analysed"</th>
+                                        </tr>
+                                        <For
+                                            each=move || reports.clone()
+                                            key=|report| report.username.clone()
+                                            let:report
+                                        >
+                                            <FairPlayRow report/>
+                                        </For>
+                                    </table>
+                                }
+                                    .into_view()
+                            }
+                            Err(_) => view! { "Could not load the fair-play reports" }.into_view(),
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
@@ -124,7 +124,10 @@ pub fn Header() -> impl IntoView {
                             <div class="flex items-center lg:mr-10">
                                 <ChatAndControls/>
                                 <NotificationDropdown/>
-                                <UserDropdown username=user().expect("User is some").username/>
+                                <UserDropdown
+                                    username=user().expect("User is some").username
+                                    guest=user().expect("User is some").user.guest
+                                />
                             </div>
                         </Show>
                     }
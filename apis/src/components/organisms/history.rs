@@ -1,5 +1,6 @@
 use crate::components::{
     atoms::history_button::{HistoryButton, HistoryNavigation},
+    molecules::{conditional_moves::ConditionalMoves, time_chart::TimeChart},
     organisms::reserve::{Alignment, Reserve},
 };
 use crate::providers::game_state::GameStateSignal;
@@ -14,6 +15,7 @@ pub fn HistoryMove(
     piece: String,
     position: String,
     repetition: bool,
+    #[prop(optional)] clock: Option<String>,
     parent_div: NodeRef<html::Div>,
 ) -> impl IntoView {
     let mut game_state = expect_context::<GameStateSignal>();
@@ -48,6 +50,7 @@ pub fn HistoryMove(
     view! {
         <div ref=div_ref class=get_class on:click=onclick>
             {format!("{}. {piece} {position}{}", turn + 1, rep)}
+            {clock.map(|clock| view! { <span class="float-right pr-1 text-xs leading-6">{clock}</span> })}
         </div>
     }
 }
@@ -59,6 +62,20 @@ pub fn History(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoVi
     let repetitions = create_read_slice(game_state.signal, |gs| {
         gs.game_response.as_ref().map(|gr| gr.repetitions.clone())
     });
+    let clocks = create_read_slice(game_state.signal, |gs| {
+        gs.game_response
+            .as_ref()
+            .map(|gr| (gr.time_mode.clone(), gr.move_times.clone()))
+    });
+    let clock = move |turn: usize| {
+        clocks().and_then(|(time_mode, move_times)| {
+            move_times
+                .get(turn)
+                .copied()
+                .flatten()
+                .map(|time_left| time_mode.time_remaining(time_left))
+        })
+    };
     let history_moves = move || {
         state()
             .history
@@ -83,6 +100,7 @@ pub fn History(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoVi
                 Conclusion::Resigned => String::from("Resigned"),
                 Conclusion::Timeout => String::from("Timeout"),
                 Conclusion::Repetition => String::from("3 move repetition"),
+                Conclusion::Forfeit => String::from("Opponent left"),
                 Conclusion::Unknown => String::from("Unknown"),
             }
         } else {
@@ -188,6 +206,7 @@ pub fn History(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoVi
                         position=history_move.2
                         parent_div=parent
                         repetition=is_repetition(history_move.0)
+                        clock=clock(history_move.0)
                     />
                 </For>
 
@@ -196,6 +215,8 @@ pub fn History(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoVi
                     <div class="col-span-4 text-center">{conclusion}</div>
                 </Show>
             </div>
+            <TimeChart/>
+            <ConditionalMoves/>
         </div>
     }
 }
@@ -1,15 +1,46 @@
 use crate::common::UserAction;
 use crate::components::atoms::rating::icon_for_speed;
-use crate::{components::molecules::user_row::UserRow, functions::users::get::get_top_users};
+use crate::{
+    components::molecules::user_row::UserRow,
+    functions::users::get::{get_monthly_leaderboard, get_top_users},
+};
+use hive_lib::GameType;
 use leptos::logging::log;
 use leptos::*;
 use leptos_icons::Icon;
-use shared_types::GameSpeed;
+use shared_types::{GameSpeed, LeaderboardKind};
 
 #[component]
-pub fn Leaderboard(speed: GameSpeed) -> impl IntoView {
+pub fn Leaderboard(
+    game_type: GameType,
+    speed: GameSpeed,
+    #[prop(default = LeaderboardKind::TopRated)] kind: LeaderboardKind,
+) -> impl IntoView {
     let speed = store_value(speed);
-    let top_users = Resource::once(move || get_top_users(speed(), 10));
+    // Monthly boards show what put the player there next to them
+    let top_users = Resource::once(move || async move {
+        match kind {
+            LeaderboardKind::TopRated => get_top_users(game_type, speed(), 10).await.map(|users| {
+                users
+                    .into_iter()
+                    .map(|user| (user, String::new()))
+                    .collect()
+            }),
+            LeaderboardKind::MostGames | LeaderboardKind::RatingGain => {
+                get_monthly_leaderboard(kind, game_type, speed(), 10)
+                    .await
+                    .map(|board| {
+                        board
+                            .into_iter()
+                            .map(|(user, value)| match kind {
+                                LeaderboardKind::MostGames => (user, format!("{value} games")),
+                                _ => (user, format!("+{value:.0}")),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+            }
+        }
+    });
     view! {
         <Transition>
             {move || {
@@ -40,13 +71,15 @@ pub fn Leaderboard(speed: GameSpeed) -> impl IntoView {
                                         <For
                                             each=move || { users() }
 
-                                            key=|users| (users.uid)
-                                            let:user
+                                            key=|(user, _)| (user.uid)
+                                            let:entry
                                         >
                                             <UserRow
                                                 actions=vec![UserAction::Challenge]
-                                                user=store_value(user)
+                                                user=store_value(entry.0)
+                                                end_str=entry.1
                                                 game_speed=speed
+                                                game_type=game_type
                                             />
                                         </For>
                                     </div>
@@ -1,3 +1,4 @@
+pub mod account_data;
 pub mod analysis;
 pub mod board;
 pub mod challenges;
@@ -5,15 +6,21 @@ pub mod chat;
 pub mod confirm_mode_toggle;
 pub mod darkmode_toggle;
 pub mod display_profile;
+pub mod display_statistics;
 pub mod display_timer;
 pub mod dropdowns;
+pub mod edit_profile;
+pub mod email_notifications;
+pub mod fair_play;
 pub mod header;
 pub mod history;
 pub mod leaderboard;
 pub mod logout;
+pub mod moderation;
 pub mod online_users;
 pub mod preview_tiles;
 pub mod quickplay;
+pub mod rating_chart;
 pub mod reserve;
 pub mod side_board;
 pub mod tile_design_toggle;
@@ -21,4 +28,5 @@ pub mod tile_dots_toggle;
 pub mod tile_rotation_toggle;
 pub mod time_select;
 pub mod tv;
+pub mod two_factor;
 pub mod unstarted;
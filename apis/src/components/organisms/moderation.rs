@@ -0,0 +1,240 @@
+use crate::{
+    functions::admin::moderation::{
+        get_audit_log, AnnulGame, BanUser, CloseChallenges, SuspendUser, UnbanUser,
+        WithdrawFromTournaments,
+    },
+    responses::AdminActionResponse,
+};
+use leptos::*;
+use shared_types::GameId;
+
+const BOX_STYLE: &str = "px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800";
+const INPUT_STYLE: &str =
+    "shadow appearance-none border rounded w-full py-2 px-3 mb-3 leading-tight focus:outline-none";
+const BUTTON_STYLE: &str = "bg-button-dawn dark:bg-button-twilight transform transition-transform duration-300 active:scale-95 hover:bg-pillbug-teal text-white font-bold py-2 px-4 rounded focus:outline-none cursor-pointer";
+
+// Reports how a moderation step went and refreshes the audit log once it went through
+fn report<I: 'static>(
+    action: Action<I, Result<(), ServerFnError>>,
+    status: RwSignal<String>,
+    audit_log: Resource<(), Result<Vec<AdminActionResponse>, ServerFnError>>,
+) {
+    create_effect(move |_| match action.value().get() {
+        Some(Ok(())) => {
+            status.set(String::from("Done"));
+            audit_log.refetch();
+        }
+        Some(Err(ServerFnError::ServerError(error))) => status.set(error),
+        Some(Err(_)) => status.set(String::from("Something went wrong, please try again")),
+        None => {}
+    });
+}
+
+#[component]
+fn AuditLogEntry(entry: AdminActionResponse) -> impl IntoView {
+    let target = match (entry.target_user, entry.target_game) {
+        (Some(username), _) => {
+            let href = format!("/@/{username}");
+            view! { <a href=href>{username}</a> }.into_view()
+        }
+        (None, Some(GameId(nanoid))) => {
+            let href = format!("/game/{nanoid}");
+            view! { <a href=href>{nanoid}</a> }.into_view()
+        }
+        (None, None) => "".into_view(),
+    };
+    view! {
+        <tr>
+            <td class="pr-3 whitespace-nowrap">
+                {entry.created_at.format("%Y-%m-%d %H:%M").to_string()}
+            </td>
+            <td class="pr-3">{entry.admin}</td>
+            <td class="pr-3">{entry.action}</td>
+            <td class="pr-3">{target}</td>
+            <td>{entry.reason}</td>
+        </tr>
+    }
+}
+
+#[component]
+pub fn Moderation() -> impl IntoView {
+    let audit_log = Resource::once(get_audit_log);
+    let username = RwSignal::new(String::new());
+    let user_reason = RwSignal::new(String::new());
+    let days = RwSignal::new(7_i64);
+    let user_status = RwSignal::new(String::new());
+    let game_id = RwSignal::new(String::new());
+    let game_reason = RwSignal::new(String::new());
+    let game_status = RwSignal::new(String::new());
+
+    let suspend = create_server_action::<SuspendUser>();
+    let ban = create_server_action::<BanUser>();
+    let unban = create_server_action::<UnbanUser>();
+    let close = create_server_action::<CloseChallenges>();
+    let withdraw = create_server_action::<WithdrawFromTournaments>();
+    let annul = create_server_action::<AnnulGame>();
+    report(suspend, user_status, audit_log);
+    report(ban, user_status, audit_log);
+    report(unban, user_status, audit_log);
+    report(close, user_status, audit_log);
+    report(withdraw, user_status, audit_log);
+    report(annul, game_status, audit_log);
+
+    view! {
+        <div class=BOX_STYLE>
+            <p class="mb-3 font-bold">"Moderate a user"</p>
+            <input
+                class=INPUT_STYLE
+                type="text"
+                placeholder="Username"
+                prop:value=username
+                on:input=move |ev| username.set(event_target_value(&ev))
+            />
+            <input
+                class=INPUT_STYLE
+                type="text"
+                placeholder="Reason"
+                prop:value=user_reason
+                on:input=move |ev| user_reason.set(event_target_value(&ev))
+            />
+            <label class="flex gap-2 items-center mb-3">
+                "Suspend for"
+                <input
+                    class="py-2 px-3 w-20 rounded border shadow appearance-none focus:outline-none"
+                    type="number"
+                    min="1"
+                    prop:value=move || days().to_string()
+                    on:input=move |ev| days.set(event_target_value(&ev).parse().unwrap_or(1))
+                />
+                "days"
+            </label>
+            <div class="flex flex-wrap gap-2">
+                <button
+                    class=BUTTON_STYLE
+                    on:click=move |_| {
+                        suspend
+                            .dispatch(SuspendUser {
+                                username: username(),
+                                days: days(),
+                                reason: user_reason(),
+                            })
+                    }
+                >
+                    "Suspend"
+                </button>
+                <button
+                    class=BUTTON_STYLE
+                    on:click=move |_| {
+                        ban.dispatch(BanUser {
+                            username: username(),
+                            reason: user_reason(),
+                        })
+                    }
+                >
+                    "Ban"
+                </button>
+                <button
+                    class=BUTTON_STYLE
+                    on:click=move |_| {
+                        unban
+                            .dispatch(UnbanUser {
+                                username: username(),
+                                reason: user_reason(),
+                            })
+                    }
+                >
+                    "Lift ban"
+                </button>
+                <button
+                    class=BUTTON_STYLE
+                    on:click=move |_| {
+                        close
+                            .dispatch(CloseChallenges {
+                                username: username(),
+                                reason: user_reason(),
+                            })
+                    }
+                >
+                    "Close challenges"
+                </button>
+                <button
+                    class=BUTTON_STYLE
+                    on:click=move |_| {
+                        withdraw
+                            .dispatch(WithdrawFromTournaments {
+                                username: username(),
+                                reason: user_reason(),
+                            })
+                    }
+                >
+                    "Withdraw from tournaments"
+                </button>
+            </div>
+            <p class="mt-3 h-5">
+                <small>{user_status}</small>
+            </p>
+        </div>
+        <div class=BOX_STYLE>
+            <p class="mb-3 font-bold">"Annul a game"</p>
+            <p class="mb-3">
+                "Takes a finished game out of both players' ratings, the game itself stays viewable."
+            </p>
+            <input
+                class=INPUT_STYLE
+                type="text"
+                placeholder="Game id"
+                prop:value=game_id
+                on:input=move |ev| game_id.set(event_target_value(&ev))
+            />
+            <input
+                class=INPUT_STYLE
+                type="text"
+                placeholder="Reason"
+                prop:value=game_reason
+                on:input=move |ev| game_reason.set(event_target_value(&ev))
+            />
+            <button
+                class=BUTTON_STYLE
+                on:click=move |_| {
+                    annul
+                        .dispatch(AnnulGame {
+                            game_id: game_id(),
+                            reason: game_reason(),
+                        })
+                }
+            >
+                "Annul"
+            </button>
+            <p class="mt-3 h-5">
+                <small>{game_status}</small>
+            </p>
+        </div>
+        <div class=BOX_STYLE>
+            <p class="mb-3 font-bold">"Audit log"</p>
+            <Transition>
+                {move || {
+                    audit_log
+                        .get()
+                        .map(|log| match log {
+                            Ok(log) => {
+                                view! {
+                                    <table class="text-sm text-left">
+                                        <For
+                                            each=move || log.clone()
+                                            key=|entry| entry.id
+                                            let:entry
+                                        >
+                                            <AuditLogEntry entry/>
+                                        </For>
+                                    </table>
+                                }
+                                    .into_view()
+                            }
+                            Err(_) => view! { "Could not load the audit log" }.into_view(),
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
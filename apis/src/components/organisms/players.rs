@@ -7,7 +7,10 @@ pub fn PlayersView() -> impl IntoView {
         <div class="pt-2 md:pt-6 flex flex-col items-center">
             <OnlineUsers/>
             // TODO: move this out to its own component "leaderboard_s_"
-            <Leaderboard speed=shared_types::game_speed::GameSpeed::Correspondence/>
+            <Leaderboard
+                game_type=hive_lib::GameType::MLP
+                speed=shared_types::game_speed::GameSpeed::Correspondence
+            />
         </div>
     }
 }
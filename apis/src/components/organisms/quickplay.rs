@@ -1,16 +1,14 @@
 use crate::{
-    common::ChallengeAction,
     components::{atoms::rating::icon_for_speed, molecules::modal::Modal},
     pages::challenge_create::ChallengeCreate,
-    providers::{ApiRequests, AuthContext},
+    providers::{quick_pair::QuickPairSignal, ApiRequests, AuthContext},
 };
 use core::panic;
-use hive_lib::{ColorChoice, GameType};
+use hive_lib::GameType;
 use leptos::{html::Dialog, *};
 use leptos_icons::*;
 use leptos_router::use_navigate;
-use shared_types::ChallengeVisibility;
-use shared_types::{ChallengeDetails, GameSpeed::*, TimeMode};
+use shared_types::{rated_game_types, GameSpeed::*, QuickPairDetails};
 pub enum QuickPlayTimeControl {
     Bullet1p2,
     Blitz3p3,
@@ -22,9 +20,13 @@ pub enum QuickPlayTimeControl {
 }
 use QuickPlayTimeControl::*;
 const BUTTON_STYLE: &str = "flex w-full gap-1 justify-center items-center px-4 py-2 font-bold text-white rounded bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95";
+const TOGGLE_STYLE: &str = "px-3 py-1 rounded transition-transform duration-300 transform hover:bg-pillbug-teal active:scale-95";
 
 #[component]
-pub fn GridButton(time_control: QuickPlayTimeControl) -> impl IntoView {
+pub fn GridButton(
+    time_control: QuickPlayTimeControl,
+    game_type: RwSignal<GameType>,
+) -> impl IntoView {
     let (display_text, icon_data, base, increment) = match time_control {
         Bullet1p2 => ("1+2".to_owned(), icon_for_speed(&Bullet), 1, 2),
         Blitz3p3 => ("3+3".to_owned(), icon_for_speed(&Blitz), 3, 3),
@@ -34,31 +36,40 @@ pub fn GridButton(time_control: QuickPlayTimeControl) -> impl IntoView {
         Classic20p20 => ("20+20".to_owned(), icon_for_speed(&Classic), 20, 20),
         Classic30p30 => ("30+30".to_owned(), icon_for_speed(&Classic), 30, 30),
     };
+    let quick_pair = expect_context::<QuickPairSignal>();
+    let auth_context = expect_context::<AuthContext>();
+    // Guests get paired for casual games only
+    let guest =
+        move || matches!((auth_context.user)(), Some(Ok(Some(account))) if account.user.guest);
+    let details = move || QuickPairDetails {
+        game_type: game_type(),
+        rated: !guest(),
+        time_base: base * 60,
+        time_increment: increment,
+    };
+    let searching = move || quick_pair.signal.get() == Some(details());
     view! {
         <button
-            class=BUTTON_STYLE
+            class=move || {
+                if searching() {
+                    format!("{BUTTON_STYLE} animate-pulse bg-pillbug-teal")
+                } else {
+                    BUTTON_STYLE.to_string()
+                }
+            }
+
             on:click=move |_| {
-                let auth_context = expect_context::<AuthContext>();
                 let account = match (auth_context.user)() {
                     Some(Ok(Some(account))) => Some(account),
                     _ => None,
                 };
                 if account.is_some() {
                     let api = ApiRequests::new();
-                    let details = ChallengeDetails {
-                        rated: true,
-                        game_type: GameType::MLP,
-                        visibility: ChallengeVisibility::Public,
-                        opponent: None,
-                        color_choice: ColorChoice::Random,
-                        time_mode: TimeMode::RealTime,
-                        time_base: Some(base * 60),
-                        time_increment: Some(increment),
-                        band_upper: None,
-                        band_lower: None,
-                    };
-                    let challenge_action = ChallengeAction::Create(details);
-                    api.challenge(challenge_action);
+                    if searching() {
+                        api.quick_pair_leave();
+                    } else {
+                        api.quick_pair_join(details());
+                    }
                 } else {
                     let navigate = use_navigate();
                     navigate("/login", Default::default());
@@ -81,18 +92,41 @@ pub fn QuickPlay() -> impl IntoView {
             .expect("dialog to have been created")
             .close();
     });
+    let game_type = RwSignal::new(GameType::MLP);
+    let quick_pair = expect_context::<QuickPairSignal>();
+    let game_type_toggles = rated_game_types()
+        .into_iter()
+        .map(|toggle_type| {
+            view! {
+                <button
+                    class=move || {
+                        if game_type() == toggle_type {
+                            format!("{TOGGLE_STYLE} bg-pillbug-teal text-white")
+                        } else {
+                            TOGGLE_STYLE.to_string()
+                        }
+                    }
+
+                    on:click=move |_| game_type.set(toggle_type)
+                >
+                    {toggle_type.to_string()}
+                </button>
+            }
+        })
+        .collect_view();
     view! {
         <div class="flex flex-col items-center m-2 grow">
             <Modal open=open dialog_el=dialog_el>
                 <ChallengeCreate close=close_modal/>
             </Modal>
-            <span class="flex justify-center mb-4 text-xl font-bold">Quick Play</span>
+            <span class="flex justify-center mb-2 text-xl font-bold">Quick Play</span>
+            <div class="flex gap-1 justify-center mb-2">{game_type_toggles}</div>
             <div class="grid grid-cols-2 gap-2 place-items-center w-full sm:gap-4 sm:grid-cols-3">
-                <GridButton time_control=Bullet1p2/>
-                <GridButton time_control=Blitz3p3/>
-                <GridButton time_control=Blitz5p4/>
-                <GridButton time_control=Rapid10p10/>
-                <GridButton time_control=Classic20p20/>
+                <GridButton time_control=Bullet1p2 game_type/>
+                <GridButton time_control=Blitz3p3 game_type/>
+                <GridButton time_control=Blitz5p4 game_type/>
+                <GridButton time_control=Rapid10p10 game_type/>
+                <GridButton time_control=Classic20p20 game_type/>
                 <button
                     class=BUTTON_STYLE
                     on:click=move |_| {
@@ -113,6 +147,14 @@ pub fn QuickPlay() -> impl IntoView {
                     Custom
                 </button>
             </div>
+            <Show when=move || quick_pair.signal.get().is_some()>
+                <button
+                    class="mt-2 text-sm hover:underline"
+                    on:click=move |_| ApiRequests::new().quick_pair_leave()
+                >
+                    "Looking for an opponent, click to stop"
+                </button>
+            </Show>
         </div>
     }
 }
@@ -0,0 +1,150 @@
+use crate::{
+    components::atoms::rating::icon_for_speed,
+    functions::users::get::get_rating_history,
+    responses::{RatingHistoryResponse, UserResponse},
+};
+use hive_lib::GameType;
+use leptos::*;
+use leptos_icons::Icon;
+use shared_types::{rated_game_types, GameSpeed};
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 200.0;
+const PADDING: f64 = 30.0;
+const BUTTON_STYLE: &str = "flex gap-1 items-center px-2 py-1 rounded transition-transform duration-300 transform hover:bg-pillbug-teal active:scale-95";
+
+#[component]
+pub fn RatingChart(user: StoredValue<UserResponse>) -> impl IntoView {
+    // Start with the rating the user has played the most games in
+    let (most_played_type, most_played_speed) = rated_game_types()
+        .into_iter()
+        .flat_map(|game_type| {
+            GameSpeed::all_rated_games()
+                .into_iter()
+                .map(move |speed| (game_type, speed))
+        })
+        .max_by_key(|(game_type, speed)| {
+            user()
+                .rating_response(game_type, speed)
+                .map_or(0, |rating| rating.played)
+        })
+        .unwrap_or((GameType::MLP, GameSpeed::Correspondence));
+    let game_type = RwSignal::new(most_played_type);
+    let speed = RwSignal::new(most_played_speed);
+    let history = Resource::new(
+        move || (game_type(), speed()),
+        move |(game_type, speed)| get_rating_history(user().username, game_type, speed),
+    );
+    let active = move |is_active: bool| {
+        if is_active {
+            format!("{BUTTON_STYLE} bg-pillbug-teal text-white")
+        } else {
+            BUTTON_STYLE.to_string()
+        }
+    };
+    let game_type_buttons = rated_game_types()
+        .into_iter()
+        .map(|button_type| {
+            view! {
+                <button
+                    class=move || active(game_type() == button_type)
+                    on:click=move |_| game_type.set(button_type)
+                >
+                    {button_type.to_string()}
+                </button>
+            }
+        })
+        .collect_view();
+    let speed_buttons = GameSpeed::all_rated_games()
+        .into_iter()
+        .map(|button_speed| {
+            let button_speed = store_value(button_speed);
+            view! {
+                <button
+                    class=move || active(speed() == button_speed())
+                    on:click=move |_| speed.set(button_speed())
+                >
+                    <Icon icon=icon_for_speed(&button_speed())/>
+                </button>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="flex flex-col gap-1 m-1 ml-3">
+            <div class="flex flex-wrap gap-1 items-center">{game_type_buttons} {speed_buttons}</div>
+            <Transition>
+                {move || {
+                    history()
+                        .map(|data| match data {
+                            Err(_) => {
+                                view! { <p>"Couldn't fetch the rating history"</p> }.into_view()
+                            }
+                            Ok(points) if points.len() < 2 => {
+                                view! { <p>"Not enough rated games yet"</p> }.into_view()
+                            }
+                            Ok(points) => view! { <RatingGraph points/> }.into_view(),
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn RatingGraph(points: Vec<RatingHistoryResponse>) -> impl IntoView {
+    let first = points.first().map_or(0, |p| p.created_at.timestamp()) as f64;
+    let last = points.last().map_or(0, |p| p.created_at.timestamp()) as f64;
+    let min = points.iter().map(|p| p.rating).fold(f64::MAX, f64::min);
+    let max = points.iter().map(|p| p.rating).fold(f64::MIN, f64::max);
+    let time_span = (last - first).max(1.0);
+    let rating_span = (max - min).max(1.0);
+    let polyline = points
+        .iter()
+        .map(|p| {
+            let x = PADDING
+                + (p.created_at.timestamp() as f64 - first) / time_span * (WIDTH - 2.0 * PADDING);
+            let y = HEIGHT - PADDING - (p.rating - min) / rating_span * (HEIGHT - 2.0 * PADDING);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    let first_date = points
+        .first()
+        .map(|p| p.created_at.format("%d/%m/%Y").to_string());
+    let last_date = points
+        .last()
+        .map(|p| p.created_at.format("%d/%m/%Y").to_string());
+    let top = PADDING.to_string();
+    let bottom = (HEIGHT - PADDING).to_string();
+    let baseline = (HEIGHT - 5.0).to_string();
+    let left = PADDING.to_string();
+    let right = (WIDTH - PADDING).to_string();
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+            class="w-full max-w-2xl bg-even-light dark:bg-gray-900"
+        >
+            <text x="2" y=top class="text-xs fill-current">
+                {max.floor()}
+            </text>
+            <text x="2" y=bottom class="text-xs fill-current">
+                {min.floor()}
+            </text>
+            <text x=left y=baseline.clone() class="text-xs fill-current">
+                {first_date}
+            </text>
+            <text x=right y=baseline text-anchor="end" class="text-xs fill-current">
+                {last_date}
+            </text>
+            <polyline
+                points=polyline
+                fill="none"
+                stroke-width="2"
+                class="stroke-pillbug-teal"
+            ></polyline>
+        </svg>
+    }
+}
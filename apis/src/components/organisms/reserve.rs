@@ -103,7 +103,8 @@ pub fn Reserve(
                     history.moves = game_state.state.history.moves[0..=turn].into();
                 }
                 let history_state =
-                    State::new_from_history(&history).expect("Got state from history");
+                    State::new_from_history_with(&history, game_state.state.automatic_repetition)
+                        .expect("Got state from history");
                 history_state
                     .board
                     .reserve(color(), game_state.state.game_type)
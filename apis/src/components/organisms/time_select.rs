@@ -8,7 +8,7 @@ use crate::{
 use leptos::ev::Event;
 use leptos::*;
 use leptos_icons::*;
-use shared_types::{GameSpeed, TimeMode};
+use shared_types::{ClockType, GameSpeed, TimeMode};
 
 #[component]
 pub fn TimeSelect(
@@ -24,6 +24,7 @@ pub fn TimeSelect(
             TimeMode::RealTime => GameSpeed::from_base_increment(
                 Some(time_signals.total_seconds.get()),
                 Some(time_signals.sec_per_move.get()),
+                time_signals.clock_type.get(),
             ),
         };
         view! { <Icon width="50" height="50" class="p-2" icon=icon_for_speed(&speed)/> }
@@ -87,6 +88,19 @@ pub fn TimeSelect(
             >
 
                 <div class="flex flex-col justify-center">
+                    <label class="flex gap-1 items-center p-1">
+                        Clock:
+                        <select
+                            class="bg-odd-light dark:bg-gray-700"
+                            name="Clock"
+                            on:change=update_from_input_parsed(time_signals.clock_type)
+                        >
+                            <SelectOption value=time_signals.clock_type is="Fischer"/>
+                            <SelectOption value=time_signals.clock_type is="Delay"/>
+                            <SelectOption value=time_signals.clock_type is="Bronstein"/>
+                            <SelectOption value=time_signals.clock_type is="Hourglass"/>
+                        </select>
+                    </label>
                     <label class="flex-col items-center">
                         <div>
                             {move || {
@@ -105,21 +119,27 @@ pub fn TimeSelect(
                             step=1
                         />
                     </label>
-                    <label class="flex-col items-center">
-                        <div>
-                            {move || {
-                                format!("Increment in sec: {}", time_signals.sec_per_move.get())
-                            }}
+                    <Show when=move || time_signals.clock_type.get().uses_increment()>
+                        <label class="flex-col items-center">
+                            <div>
+                                {move || {
+                                    let label = match time_signals.clock_type.get() {
+                                        ClockType::Fischer => "Increment",
+                                        _ => "Delay",
+                                    };
+                                    format!("{label} in sec: {}", time_signals.sec_per_move.get())
+                                }}
 
-                        </div>
-                        <InputSlider
-                            signal_to_update=time_signals.step_sec
-                            name="increment"
-                            min=0
-                            max=32
-                            step=1
-                        />
-                    </label>
+                            </div>
+                            <InputSlider
+                                signal_to_update=time_signals.step_sec
+                                name="increment"
+                                min=0
+                                max=32
+                                step=1
+                            />
+                        </label>
+                    </Show>
                 </div>
             </Show>
         </Show>
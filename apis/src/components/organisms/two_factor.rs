@@ -0,0 +1,138 @@
+use crate::{
+    functions::accounts::two_factor::{ConfirmTotp, DisableTotp, StartTotp},
+    providers::AuthContext,
+    responses::TotpEnrolmentResponse,
+};
+use leptos::*;
+use leptos_router::ActionForm;
+
+const BOX_STYLE: &str = "px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800";
+const INPUT_STYLE: &str =
+    "shadow appearance-none border rounded w-full py-2 px-3 mb-3 leading-tight focus:outline-none";
+const BUTTON_STYLE: &str = "bg-button-dawn dark:bg-button-twilight transform transition-transform duration-300 active:scale-95 hover:bg-pillbug-teal text-white font-bold py-2 px-4 rounded focus:outline-none cursor-pointer";
+
+fn error_message<T>(result: Option<Result<T, ServerFnError>>) -> String {
+    match result {
+        Some(Err(ServerFnError::ServerError(error))) => error,
+        Some(Err(_)) => String::from("Something went wrong, please try again"),
+        _ => String::new(),
+    }
+}
+
+#[component]
+pub fn TwoFactor() -> impl IntoView {
+    let user = expect_context::<AuthContext>().user;
+    let start = create_server_action::<StartTotp>();
+    let confirm = create_server_action::<ConfirmTotp>();
+    let disable = create_server_action::<DisableTotp>();
+    let account = move || match user() {
+        Some(Ok(Some(account))) => Some(account),
+        _ => None,
+    };
+    create_effect(move |_| {
+        if confirm.value().with(|v| matches!(v, Some(Ok(_))))
+            || disable.value().with(|v| matches!(v, Some(Ok(_))))
+        {
+            user.refetch();
+        }
+    });
+
+    let recovery_codes = move || {
+        confirm.value().get().and_then(Result::ok).map(|codes| {
+            view! {
+                <p class="mb-3">
+                    "Two-factor authentication is on. Keep these recovery codes somewhere safe, each of them lets you log in once without your device:"
+                </p>
+                <ul class="font-mono">
+                    {codes.into_iter().map(|code| view! { <li>{code}</li> }).collect_view()}
+                </ul>
+            }
+        })
+    };
+    let enabled = move || {
+        view! {
+            <p class="mb-3">"Two-factor authentication is on."</p>
+            <Show when=move || account().is_some_and(|account| !account.totp_required)>
+                <ActionForm action=disable>
+                    <input
+                        class=INPUT_STYLE
+                        name="password"
+                        type="password"
+                        autocomplete="current-password"
+                        placeholder="Current password"
+                    />
+                    <input
+                        class=INPUT_STYLE
+                        name="code"
+                        type="text"
+                        inputmode="numeric"
+                        autocomplete="one-time-code"
+                        placeholder="Authentication or recovery code"
+                    />
+                    <p class="h-5">
+                        <small class="text-ladybug-red">{move || error_message(disable.value().get())}</small>
+                    </p>
+                    <input type="submit" class=BUTTON_STYLE value="Turn off"/>
+                </ActionForm>
+            </Show>
+        }
+    };
+    let start_button = move || {
+        view! {
+            <Show when=move || account().is_some_and(|account| account.totp_required)>
+                <p class="mb-3 text-ladybug-red">
+                    "Admin rights only apply once two-factor authentication is on."
+                </p>
+            </Show>
+            <p class="h-5">
+                <small class="text-ladybug-red">{move || error_message(start.value().get())}</small>
+            </p>
+            <button class=BUTTON_STYLE on:click=move |_| start.dispatch(StartTotp {})>
+                "Set up two-factor authentication"
+            </button>
+        }
+    };
+    let enrolment = move |enrolment: TotpEnrolmentResponse| {
+        view! {
+            <p class="mb-3">
+                "Scan this code with your authenticator app, or enter the secret by hand. Then confirm with the code the app shows."
+            </p>
+            <div class="mb-3 bg-white w-fit" inner_html=enrolment.qr_code></div>
+            <p class="mb-3 font-mono break-all">{enrolment.secret}</p>
+            <ActionForm action=confirm>
+                <input
+                    class=INPUT_STYLE
+                    name="code"
+                    type="text"
+                    inputmode="numeric"
+                    autocomplete="one-time-code"
+                    placeholder="123456"
+                />
+                <p class="h-5">
+                    <small class="text-ladybug-red">{move || error_message(confirm.value().get())}</small>
+                </p>
+                <input type="submit" class=BUTTON_STYLE value="Confirm"/>
+            </ActionForm>
+        }
+    };
+
+    view! {
+        <Show when=move || account().is_some()>
+            <div class=BOX_STYLE>
+                <p class="mb-3 font-bold">"Two-factor authentication"</p>
+                {move || {
+                    if let Some(codes) = recovery_codes() {
+                        codes.into_view()
+                    } else if account().is_some_and(|account| account.totp_enabled) {
+                        enabled().into_view()
+                    } else if let Some(Ok(response)) = start.value().get() {
+                        enrolment(response).into_view()
+                    } else {
+                        start_button().into_view()
+                    }
+                }}
+
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{responses::GameResponse, websockets::chat::Chats};
+use actix_identity::Identity;
+use actix_web::{
+    error::{ErrorInternalServerError, ErrorUnauthorized},
+    get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    web::Data,
+    Error, HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use db_lib::{
+    get_conn,
+    models::{Avatar, Game, Rating, RatingHistory, Tournament, User},
+    DbPool,
+};
+use serde::Serialize;
+use shared_types::ChatMessageContainer;
+use std::io::{Cursor, Write};
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+#[derive(Serialize)]
+struct Profile {
+    username: String,
+    email: Option<String>,
+    email_verified: bool,
+    totp_enabled: bool,
+    patreon: bool,
+    created_at: DateTime<Utc>,
+    bio: String,
+    country: Option<String>,
+    preferred_game_type: Option<String>,
+    links: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct AccountExport {
+    profile: Profile,
+    ratings: Vec<Rating>,
+    rating_history: Vec<RatingHistory>,
+    games: Vec<Game>,
+    tournaments: Vec<Tournament>,
+    chat_messages: Vec<ChatMessageContainer>,
+}
+
+fn zip_archive(files: &[(String, &[u8])]) -> Result<Vec<u8>, Error> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        zip.start_file(name.as_str(), SimpleFileOptions::default())
+            .map_err(ErrorInternalServerError)?;
+        zip.write_all(content).map_err(ErrorInternalServerError)?;
+    }
+    Ok(zip.finish().map_err(ErrorInternalServerError)?.into_inner())
+}
+
+// Everything we store about the logged in user, as JSON plus their games as PGN
+#[get("/account/export")]
+pub async fn account_export(
+    identity: Option<Identity>,
+    pool: Data<DbPool>,
+    chats: Data<Chats>,
+) -> Result<HttpResponse, Error> {
+    let id = identity
+        .ok_or_else(|| ErrorUnauthorized("Log in to export your data"))?
+        .id()
+        .map_err(ErrorUnauthorized)?;
+    let uuid = Uuid::parse_str(&id).map_err(ErrorUnauthorized)?;
+    let mut conn = get_conn(&pool).await.map_err(ErrorInternalServerError)?;
+    let user = User::find_by_uuid(&uuid, &mut conn)
+        .await
+        .map_err(ErrorUnauthorized)?;
+    let games = user
+        .get_all_games(&mut conn)
+        .await
+        .map_err(ErrorInternalServerError)?;
+    let mut pgns = Vec::new();
+    for game in games.iter() {
+        let response = GameResponse::from_model(game, &mut conn)
+            .await
+            .map_err(ErrorInternalServerError)?;
+        pgns.push(response.pgn());
+    }
+    let export = AccountExport {
+        profile: Profile {
+            username: user.username.clone(),
+            email: user.email.clone(),
+            email_verified: user.email_verified,
+            totp_enabled: user.totp_enabled,
+            patreon: user.patreon,
+            created_at: user.created_at,
+            bio: user.bio.clone(),
+            country: user.country.clone(),
+            preferred_game_type: user.preferred_game_type.clone(),
+            links: user.links(),
+        },
+        ratings: user
+            .get_ratings(&mut conn)
+            .await
+            .map_err(ErrorInternalServerError)?,
+        rating_history: user
+            .get_rating_history(&mut conn)
+            .await
+            .map_err(ErrorInternalServerError)?,
+        games,
+        tournaments: user
+            .get_tournaments(&mut conn)
+            .await
+            .map_err(ErrorInternalServerError)?,
+        chat_messages: chats.messages_of(user.id),
+    };
+    let json = serde_json::to_string_pretty(&export).map_err(ErrorInternalServerError)?;
+    let pgn = pgns.join("\n");
+    let avatar = Avatar::find_by_user(&user.id, &mut conn)
+        .await
+        .map_err(ErrorInternalServerError)?;
+    let mut files = vec![
+        (String::from("account.json"), json.as_bytes()),
+        (String::from("games.pgn"), pgn.as_bytes()),
+    ];
+    if let Some(avatar) = &avatar {
+        let extension = avatar.content_type.trim_start_matches("image/");
+        files.push((format!("avatar.{extension}"), avatar.image.as_slice()));
+    }
+    let archive = zip_archive(&files)?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "hivegame_{}.zip",
+                user.username
+            ))],
+        })
+        .body(archive))
+}
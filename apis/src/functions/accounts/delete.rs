@@ -1,15 +1,38 @@
 use leptos::*;
 
+// Anonymises the account, games and tournaments it took part in stay around under a generated name
 #[server]
-pub async fn delete_account() -> Result<(), ServerFnError> {
-    use crate::functions::auth::{identity::uuid, logout::logout};
+pub async fn delete_account(password: String) -> Result<(), ServerFnError> {
+    use crate::functions::auth::{
+        identity::ensure_active, logout::logout, password::verify_password,
+    };
     use crate::functions::db::pool;
+    use crate::resignations::announce_resignations;
+    use crate::sessions::end_sessions;
+    use db_lib::db_error::DbError;
     use db_lib::get_conn;
-    use db_lib::models::User;
-    let uuid = uuid()?;
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use diesel_async::AsyncConnection;
+    use shared_types::GameId;
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
-    let user = User::find_by_uuid(&uuid, &mut conn).await?;
-    user.delete(&mut conn).await?;
-    logout().await
+    let user = ensure_active(&mut conn).await?;
+    verify_password(&password, &user.password)?;
+    let user_id = user.id;
+    let (_, resigned) = conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move { user.anonymize(tc).await }.scope_boxed()
+        })
+        .await?;
+    announce_resignations(
+        user_id,
+        resigned
+            .into_iter()
+            .map(|game| GameId(game.nanoid))
+            .collect(),
+    )?;
+    end_sessions(user_id)?;
+    logout().await?;
+    leptos_actix::redirect("/");
+    Ok(())
 }
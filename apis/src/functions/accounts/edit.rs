@@ -9,23 +9,22 @@ pub async fn edit_account(
     password: String,
     pathname: String,
 ) -> Result<AccountResponse, ServerFnError> {
-    use crate::functions::auth::identity::uuid;
+    use crate::functions::auth::identity::ensure_active;
     use crate::functions::db::pool;
+    use crate::mailer::{mailer, send_verification};
     use argon2::{
         password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
         Argon2,
     };
     use db_lib::get_conn;
-    use db_lib::models::User;
     use rand_core::OsRng;
 
     if new_password != new_password_confirmation {
         return Err(ServerFnError::new("Passwords don't match."));
     }
-    let uuid = uuid()?;
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
-    let user = User::find_by_uuid(&uuid, &mut conn).await?;
+    let user = ensure_active(&mut conn).await?;
     let argon2 = Argon2::default();
     let parsed_hash = PasswordHash::new(&user.password).map_err(ServerFnError::new)?;
 
@@ -42,7 +41,102 @@ pub async fn edit_account(
         .map_err(ServerFnError::new)?
         .to_string();
 
-    user.edit(&hashed_password, &new_email, &mut conn).await?;
+    // Entering the current address again changes nothing, it stays verified
+    let new_email = if user
+        .email
+        .as_ref()
+        .is_some_and(|email| email.eq_ignore_ascii_case(new_email.trim()))
+    {
+        String::new()
+    } else {
+        new_email
+    };
+    let user = user.edit(&hashed_password, &new_email, &mut conn).await?;
+    if !new_email.is_empty() {
+        send_verification(&user, &**mailer()?, &mut conn).await?;
+    }
     leptos_actix::redirect(&pathname);
     AccountResponse::from_uuid(&user.id, &mut conn).await
 }
+
+// Shows why a profile change was refused rather than a generic error
+#[cfg(feature = "ssr")]
+fn refusal(err: db_lib::db_error::DbError) -> ServerFnError {
+    use db_lib::db_error::DbError;
+    match err {
+        DbError::InvalidInput { info, .. } => ServerFnError::new(info),
+        err => err.into(),
+    }
+}
+
+#[server]
+pub async fn edit_profile(
+    bio: String,
+    country: String,
+    preferred_game_type: String,
+    links: Vec<String>,
+) -> Result<AccountResponse, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use hive_lib::GameType;
+    use std::str::FromStr;
+
+    let country = Some(country.trim()).filter(|country| !country.is_empty());
+    let preferred_game_type = match preferred_game_type.as_str() {
+        "" => None,
+        game_type => Some(GameType::from_str(game_type).map_err(ServerFnError::new)?),
+    };
+    let links = links
+        .iter()
+        .map(|link| link.trim().to_owned())
+        .filter(|link| !link.is_empty())
+        .collect::<Vec<_>>();
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.guest {
+        return Err(ServerFnError::new("Register to set up a profile."));
+    }
+    let user = user
+        .edit_profile(bio.trim(), country, preferred_game_type, &links, &mut conn)
+        .await
+        .map_err(refusal)?;
+    AccountResponse::from_uuid(&user.id, &mut conn).await
+}
+
+// The image comes base64 encoded, server functions only take text
+#[server]
+pub async fn upload_avatar(image: String) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use db_lib::get_conn;
+    use db_lib::models::Avatar;
+
+    let image = STANDARD.decode(image).map_err(ServerFnError::new)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.guest {
+        return Err(ServerFnError::new("Register to set up a profile."));
+    }
+    let avatar = Avatar::new(&user.id, image).map_err(refusal)?;
+    avatar.save(&mut conn).await?;
+    Ok(())
+}
+
+// Goes back to the generated avatar
+#[server]
+pub async fn remove_avatar() -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::Avatar;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    Avatar::delete_for_user(&user.id, &mut conn).await?;
+    Ok(())
+}
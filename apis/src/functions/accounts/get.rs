@@ -3,9 +3,10 @@ use leptos::*;
 
 #[server]
 pub async fn get_account() -> Result<Option<AccountResponse>, ServerFnError> {
-    use crate::functions::auth::identity::uuid;
+    use crate::functions::auth::identity::{identity, uuid};
     use crate::functions::db::pool;
     use db_lib::get_conn;
+    use db_lib::models::User;
 
     let uuid = match uuid() {
         Ok(uuid) => uuid,
@@ -13,6 +14,12 @@ pub async fn get_account() -> Result<Option<AccountResponse>, ServerFnError> {
     };
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
+    // Sessions of a deleted or banned account end with it
+    let user = User::find_by_uuid(&uuid, &mut conn).await?;
+    if user.deleted || user.is_banned() {
+        identity()?.logout();
+        return Ok(None);
+    }
     let account_response = AccountResponse::from_uuid(&uuid, &mut conn).await?;
     Ok(Some(account_response))
 }
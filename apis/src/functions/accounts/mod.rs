@@ -1,3 +1,6 @@
 pub mod delete;
 pub mod edit;
 pub mod get;
+pub mod notifications;
+pub mod two_factor;
+pub mod verify_email;
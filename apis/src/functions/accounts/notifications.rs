@@ -0,0 +1,28 @@
+use leptos::*;
+
+#[server]
+pub async fn set_notifications(
+    your_turn: bool,
+    time_running_low: bool,
+    tournament_starting: bool,
+    tournament_invitation: bool,
+) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::NotificationSettings;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    NotificationSettings::new(
+        user.id,
+        your_turn,
+        time_running_low,
+        tournament_starting,
+        tournament_invitation,
+    )
+    .save(&mut conn)
+    .await?;
+    Ok(())
+}
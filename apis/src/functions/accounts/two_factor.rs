@@ -0,0 +1,79 @@
+use crate::responses::TotpEnrolmentResponse;
+use leptos::*;
+
+#[server]
+pub async fn start_totp() -> Result<TotpEnrolmentResponse, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::auth::totp::{enrolment, new_secret};
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.totp_enabled {
+        return Err(ServerFnError::new(
+            "Two-factor authentication is already enabled.",
+        ));
+    }
+    let secret = new_secret();
+    user.set_totp_secret(&secret, &mut conn).await?;
+    enrolment(&secret, &user.username)
+}
+
+// Turns two-factor authentication on once the app produced a valid code, returns the recovery codes
+#[server]
+pub async fn confirm_totp(code: String) -> Result<Vec<String>, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::auth::totp::check_code;
+    use crate::functions::db::pool;
+    use db_lib::db_error::DbError;
+    use db_lib::get_conn;
+    use db_lib::models::RecoveryCode;
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use diesel_async::AsyncConnection;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.totp_enabled {
+        return Err(ServerFnError::new(
+            "Two-factor authentication is already enabled.",
+        ));
+    }
+    if !check_code(&user, &code, &mut conn).await? {
+        return Err(ServerFnError::new("Wrong code, please try again."));
+    }
+    let codes = conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move {
+                user.enable_totp(tc).await?;
+                RecoveryCode::generate(&user.id, tc).await
+            }
+            .scope_boxed()
+        })
+        .await?;
+    Ok(codes)
+}
+
+#[server]
+pub async fn disable_totp(password: String, code: String) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::auth::password::verify_password;
+    use crate::functions::auth::totp::check_second_factor;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.admin {
+        return Err(ServerFnError::new("Admins need two-factor authentication."));
+    }
+    verify_password(&password, &user.password)?;
+    if !check_second_factor(&user, &code, &mut conn).await? {
+        return Err(ServerFnError::new("Wrong code."));
+    }
+    user.disable_totp(&mut conn).await?;
+    Ok(())
+}
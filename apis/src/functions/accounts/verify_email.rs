@@ -0,0 +1,45 @@
+use leptos::*;
+
+#[server]
+pub async fn verify_email(token: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::db_error::DbError;
+    use db_lib::get_conn;
+    use db_lib::models::{EmailToken, TokenPurpose, User};
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use diesel_async::AsyncConnection;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            let uuid = EmailToken::redeem(&token, TokenPurpose::VerifyEmail, tc).await?;
+            User::find_by_uuid(&uuid, tc)
+                .await?
+                .verify_email(tc)
+                .await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(())
+}
+
+#[server]
+pub async fn resend_verification() -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use crate::mailer::{mailer, send_verification};
+    use db_lib::get_conn;
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = ensure_active(&mut conn).await?;
+    if user.email_verified {
+        return Err(ServerFnError::new(
+            "Your e-mail address is already verified.",
+        ));
+    }
+    send_verification(&user, &**mailer()?, &mut conn).await
+}
@@ -0,0 +1,17 @@
+use crate::responses::FairPlayReportResponse;
+use leptos::*;
+
+#[server]
+pub async fn get_fair_play_reports() -> Result<Vec<FairPlayReportResponse>, ServerFnError> {
+    use crate::functions::{admin::moderation::ensure_admin, db::pool};
+    use db_lib::{get_conn, models::FairPlayReport};
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    ensure_admin(&mut conn).await?;
+    Ok(FairPlayReport::latest(&mut conn)
+        .await?
+        .iter()
+        .map(|(report, user)| FairPlayReportResponse::from_model(report, user))
+        .collect())
+}
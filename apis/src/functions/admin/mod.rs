@@ -0,0 +1,2 @@
+pub mod fair_play;
+pub mod moderation;
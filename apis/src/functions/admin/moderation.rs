@@ -0,0 +1,250 @@
+use crate::responses::AdminActionResponse;
+#[cfg(feature = "ssr")]
+use db_lib::{models::User, DbConn};
+use leptos::*;
+
+#[cfg(feature = "ssr")]
+const AUDIT_LOG_LENGTH: i64 = 100;
+// Anything longer is a ban
+#[cfg(feature = "ssr")]
+const MAX_SUSPENSION_DAYS: i64 = 3650;
+
+#[cfg(feature = "ssr")]
+pub async fn ensure_admin(conn: &mut DbConn<'_>) -> Result<User, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    let user = ensure_active(conn).await?;
+    if !user.is_admin() {
+        return Err(ServerFnError::new("Only admins can do that."));
+    }
+    Ok(user)
+}
+
+// Every step ends up in the audit log, so it needs to say why
+#[cfg(feature = "ssr")]
+fn ensure_reason(reason: &str) -> Result<(), ServerFnError> {
+    if reason.trim().is_empty() {
+        return Err(ServerFnError::new("Please give a reason."));
+    }
+    Ok(())
+}
+
+// A suspended user can't log in or play until it runs out, their challenges get closed
+#[server]
+pub async fn suspend_user(
+    username: String,
+    days: i64,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use crate::sessions::end_sessions;
+    use chrono::{Duration, Utc};
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+
+    ensure_reason(&reason)?;
+    if days < 1 {
+        return Err(ServerFnError::new("A suspension lasts at least a day."));
+    }
+    if days > MAX_SUSPENSION_DAYS {
+        return Err(ServerFnError::new(
+            "A suspension lasts at most ten years, ban the account instead.",
+        ));
+    }
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    let user_id = user.id;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            user.suspend(Utc::now() + Duration::days(days), tc).await?;
+            user.close_challenges(tc).await?;
+            let reason = format!("{days} days: {reason}");
+            let action = ModerationAction::Suspend;
+            AdminAction::log(&admin.id, action, Some(user.id), None, &reason, tc).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    end_sessions(user_id)
+}
+
+#[server]
+pub async fn ban_user(username: String, reason: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use crate::sessions::end_sessions;
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+
+    ensure_reason(&reason)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    let user_id = user.id;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            user.ban(tc).await?;
+            user.close_challenges(tc).await?;
+            let action = ModerationAction::Ban;
+            AdminAction::log(&admin.id, action, Some(user.id), None, &reason, tc).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    end_sessions(user_id)
+}
+
+// Lifts a ban as well as a suspension
+#[server]
+pub async fn unban_user(username: String, reason: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+
+    ensure_reason(&reason)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            user.unban(tc).await?;
+            let action = ModerationAction::Unban;
+            AdminAction::log(&admin.id, action, Some(user.id), None, &reason, tc).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(())
+}
+
+#[server]
+pub async fn close_challenges(username: String, reason: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+
+    ensure_reason(&reason)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            user.close_challenges(tc).await?;
+            let action = ModerationAction::CloseChallenges;
+            AdminAction::log(&admin.id, action, Some(user.id), None, &reason, tc).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(())
+}
+
+// Leaves tournaments that have not started and resigns the open games of running ones
+#[server]
+pub async fn withdraw_from_tournaments(
+    username: String,
+    reason: String,
+) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use crate::resignations::announce_resignations;
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+    use shared_types::GameId;
+
+    ensure_reason(&reason)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    let user_id = user.id;
+    let resigned = conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move {
+                let (_, resigned) = user.withdraw_from_tournaments(tc).await?;
+                let action = ModerationAction::WithdrawFromTournaments;
+                AdminAction::log(&admin.id, action, Some(user.id), None, &reason, tc).await?;
+                Ok(resigned)
+            }
+            .scope_boxed()
+        })
+        .await?;
+    announce_resignations(
+        user_id,
+        resigned
+            .into_iter()
+            .map(|game| GameId(game.nanoid))
+            .collect(),
+    )
+}
+
+// Takes a finished game out of both players' ratings and marks it as annulled
+#[server]
+pub async fn annul_game(game_id: String, reason: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{AdminAction, Game, ModerationAction},
+    };
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+    use shared_types::GameId;
+
+    ensure_reason(&reason)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let admin = ensure_admin(&mut conn).await?;
+    let game = Game::find_by_game_id(&GameId(game_id.trim().to_owned()), &mut conn).await?;
+    conn.transaction::<_, DbError, _>(move |tc| {
+        async move {
+            game.annul(tc).await?;
+            let action = ModerationAction::AnnulGame;
+            AdminAction::log(&admin.id, action, None, Some(game.id), &reason, tc).await?;
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(())
+}
+
+#[server]
+pub async fn get_audit_log() -> Result<Vec<AdminActionResponse>, ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::{get_conn, models::AdminAction};
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    ensure_admin(&mut conn).await?;
+    let mut log = Vec::new();
+    for action in AdminAction::recent(AUDIT_LOG_LENGTH, &mut conn).await? {
+        log.push(AdminActionResponse::from_model(&action, &mut conn).await?);
+    }
+    Ok(log)
+}
@@ -0,0 +1,41 @@
+use leptos::*;
+
+// Starts a session as a guest, who can play casual games until they register
+#[server]
+pub async fn play_as_guest(pathname: String) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::{sign_in, uuid};
+    use crate::functions::db::pool;
+    use crate::guest_limits::GuestLimits;
+    use actix_web::web::Data;
+    use chrono::Utc;
+    use db_lib::get_conn;
+    use db_lib::models::User;
+
+    if uuid().is_ok() {
+        return Err(ServerFnError::new("You are already logged in"));
+    }
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    let address = req
+        .connection_info()
+        .realip_remote_addr()
+        .unwrap_or_default()
+        .to_owned();
+    let started = req
+        .app_data::<Data<GuestLimits>>()
+        .ok_or("Failed to get guest limits")
+        .map_err(ServerFnError::new)?
+        .try_start(&address, Utc::now());
+    if !started {
+        return Err(ServerFnError::new(
+            "Too many guests from this address, please try again later.",
+        ));
+    }
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = User::create_guest(&mut conn).await?;
+    sign_in(&user)?;
+    leptos_actix::redirect(&pathname);
+    Ok(())
+}
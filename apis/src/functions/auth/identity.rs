@@ -1,7 +1,12 @@
 use actix_identity::Identity;
+use actix_session::SessionExt;
+use db_lib::{models::User, DbConn};
 use leptos::*;
 use uuid::Uuid;
 
+// The session version of the user at sign in, sessions from before a password reset don't match
+pub const SESSION_VERSION: &str = "session_version";
+
 pub fn identity() -> Result<Identity, ServerFnError> {
     use actix_identity::IdentityExt;
     use actix_web::HttpRequest;
@@ -9,8 +14,62 @@ pub fn identity() -> Result<Identity, ServerFnError> {
     IdentityExt::get_identity(&req).map_err(ServerFnError::new)
 }
 
+pub fn session() -> Result<actix_session::Session, ServerFnError> {
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    Ok(req.get_session())
+}
+
+// Attaches the identity of the user to the session
+pub fn sign_in(user: &User) -> Result<(), ServerFnError> {
+    use actix_web::HttpMessage;
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    Identity::login(&req.extensions(), user.id.to_string())?;
+    session()?.insert(SESSION_VERSION, user.session_version)?;
+    Ok(())
+}
+
 pub fn uuid() -> Result<Uuid, ServerFnError> {
     let id_str = identity()?.id()?;
     Uuid::parse_str(&id_str)
         .map_err(|e| ServerFnError::new(format!("Could not retrieve Uuid from identity: {e}")))
 }
+
+pub fn ensure_not_banned(user: &User) -> Result<(), ServerFnError> {
+    if user.banned {
+        return Err(ServerFnError::new("This account has been banned."));
+    }
+    match user.suspended_until {
+        Some(until) if user.is_banned() => Err(ServerFnError::new(format!(
+            "This account is suspended until {}.",
+            until.format("%Y-%m-%d %H:%M UTC")
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// The user of the session, as long as the account is still in use. A session that outlived its
+// account or password or was banned is ended here.
+pub async fn ensure_active(conn: &mut DbConn<'_>) -> Result<User, ServerFnError> {
+    let user = User::find_by_uuid(&uuid()?, conn).await?;
+    // Sessions from before versions were recorded started at the first one
+    let version = session()?.get::<i32>(SESSION_VERSION)?.unwrap_or(0);
+    if version != user.session_version {
+        identity()?.logout();
+        return Err(ServerFnError::new(
+            "The password of this account has changed, please log in again.",
+        ));
+    }
+    if user.deleted {
+        identity()?.logout();
+        return Err(ServerFnError::new("This account has been deleted."));
+    }
+    if let Err(err) = ensure_not_banned(&user) {
+        identity()?.logout();
+        return Err(err);
+    }
+    Ok(user)
+}
@@ -1,35 +1,68 @@
-use crate::responses::AccountResponse;
+use crate::responses::{AccountResponse, LoginResponse};
+#[cfg(feature = "ssr")]
+use db_lib::models::User;
 use leptos::*;
 
+#[cfg(feature = "ssr")]
+use crate::functions::auth::identity::{ensure_not_banned, sign_in};
+
 #[server]
 pub async fn login(
     email: String,
     password: String,
     pathname: String,
-) -> Result<AccountResponse, ServerFnError> {
+) -> Result<LoginResponse, ServerFnError> {
+    use crate::functions::auth::totp::start_pending_login;
     use crate::functions::db::pool;
-    use actix_identity::Identity;
-    use actix_web::HttpMessage;
     use argon2::{password_hash::PasswordHash, Argon2, PasswordVerifier};
     use db_lib::get_conn;
-    use db_lib::models::User;
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
     let user: User = User::find_by_email(&email, &mut conn)
         .await
         .map_err(ServerFnError::new)?;
+    ensure_not_banned(&user)?;
 
     let argon2 = Argon2::default();
     let parsed_hash = PasswordHash::new(&user.password).map_err(ServerFnError::new)?;
     match argon2.verify_password(password.as_bytes(), &parsed_hash) {
+        Ok(_) if user.totp_enabled => {
+            start_pending_login(user.id)?;
+            Ok(LoginResponse::SecondFactor)
+        }
         Ok(_) => {
-            let req = use_context::<actix_web::HttpRequest>()
-                .ok_or("Failed to get HttpRequest")
-                .map_err(ServerFnError::new)?;
-            Identity::login(&req.extensions(), user.id.to_string())?;
+            sign_in(&user)?;
             leptos_actix::redirect(&pathname);
-            AccountResponse::from_uuid(&user.id, &mut conn).await
+            Ok(LoginResponse::LoggedIn(
+                AccountResponse::from_uuid(&user.id, &mut conn).await?,
+            ))
         }
         Err(_) => Err(ServerFnError::new("Password does not match.")),
     }
 }
+
+// The second step of a login with two-factor authentication, only now the identity gets attached
+#[server]
+pub async fn login_second_factor(
+    code: String,
+    pathname: String,
+) -> Result<AccountResponse, ServerFnError> {
+    use crate::functions::auth::totp::{
+        check_second_factor, finish_pending_login, pending_login_user,
+    };
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+
+    let uuid = pending_login_user()?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = User::find_by_uuid(&uuid, &mut conn).await?;
+    ensure_not_banned(&user)?;
+    if !check_second_factor(&user, &code, &mut conn).await? {
+        return Err(ServerFnError::new("Wrong code."));
+    }
+    finish_pending_login()?;
+    sign_in(&user)?;
+    leptos_actix::redirect(&pathname);
+    AccountResponse::from_uuid(&user.id, &mut conn).await
+}
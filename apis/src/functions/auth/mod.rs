@@ -1,5 +1,11 @@
+pub mod guest;
 #[cfg(feature = "ssr")]
 pub mod identity;
 pub mod login;
 pub mod logout;
+#[cfg(feature = "ssr")]
+pub mod password;
+pub mod password_reset;
 pub mod register;
+#[cfg(feature = "ssr")]
+pub mod totp;
@@ -0,0 +1,44 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use leptos::*;
+use rand_core::OsRng;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+pub fn validate_password(password: &str, password_confirmation: &str) -> Result<(), ServerFnError> {
+    if password != password_confirmation {
+        return Err(ServerFnError::new("Passwords don't match."));
+    }
+    let password_length = password.len();
+    if password_length < MIN_PASSWORD_LENGTH {
+        return Err(ServerFnError::new(format!(
+            "Password is too short, it must be at least {}",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+    if password_length > MAX_PASSWORD_LENGTH {
+        return Err(ServerFnError::new(format!(
+            "Password is too long it must not exceed {}",
+            MAX_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+pub fn hash_password(password: &str) -> Result<String, ServerFnError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(ServerFnError::new)?
+        .to_string())
+}
+
+pub fn verify_password(password: &str, hashed_password: &str) -> Result<(), ServerFnError> {
+    let parsed_hash = PasswordHash::new(hashed_password).map_err(ServerFnError::new)?;
+    Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .map_err(|_| ServerFnError::new("Password does not match."))
+}
@@ -0,0 +1,66 @@
+use leptos::*;
+
+#[server]
+pub async fn request_password_reset(email: String) -> Result<(), ServerFnError> {
+    use crate::functions::db::pool;
+    use crate::mailer::{mailer, send_password_reset};
+    use db_lib::{
+        db_error::DbError,
+        get_conn,
+        models::{EmailToken, TokenPurpose, User},
+    };
+
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    match User::find_by_email(&email, &mut conn).await {
+        // The link that went out a moment ago still works, asking again doesn't flood the inbox
+        Ok(user)
+            if EmailToken::recently_issued(&user.id, TokenPurpose::ResetPassword, &mut conn)
+                .await? =>
+        {
+            Ok(())
+        }
+        Ok(user) => send_password_reset(&user, &**mailer()?, &mut conn).await,
+        // Nobody gets to find out which addresses have an account
+        Err(DbError::NotFound { .. }) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[server]
+pub async fn reset_password(
+    token: String,
+    password: String,
+    password_confirmation: String,
+) -> Result<(), ServerFnError> {
+    use crate::functions::auth::password::{hash_password, validate_password};
+    use crate::functions::db::pool;
+    use crate::sessions::end_sessions;
+    use db_lib::db_error::DbError;
+    use db_lib::get_conn;
+    use db_lib::models::{EmailToken, TokenPurpose, User};
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use diesel_async::AsyncConnection;
+
+    validate_password(&password, &password_confirmation)?;
+    let password = hash_password(&password)?;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let uuid = conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move {
+                let uuid = EmailToken::redeem(&token, TokenPurpose::ResetPassword, tc).await?;
+                let user = User::find_by_uuid(&uuid, tc).await?;
+                let user = user.set_password(&password, tc).await?;
+                // The link arrived in the inbox, so the address works
+                user.verify_email(tc).await?;
+                Ok(uuid)
+            }
+            .scope_boxed()
+        })
+        .await?;
+    // Whoever knew the old password is signed out everywhere
+    end_sessions(uuid)?;
+    leptos_actix::redirect("/login");
+    Ok(())
+}
@@ -8,60 +8,51 @@ pub async fn register(
     password_confirmation: String,
     pathname: String,
 ) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::{sign_in, uuid};
+    use crate::functions::auth::password::{hash_password, validate_password};
     use crate::functions::db::pool;
-    use actix_identity::Identity;
-    use actix_web::HttpMessage;
-    use argon2::{
-        password_hash::{PasswordHasher, SaltString},
-        Argon2,
-    };
+    use crate::mailer::{mailer, send_verification};
     use db_lib::db_error::DbError;
     use db_lib::get_conn;
     use db_lib::models::{NewUser, User};
     use diesel_async::scoped_futures::ScopedFutureExt;
     use diesel_async::AsyncConnection;
-    use rand_core::OsRng;
-    const MIN_PASSWORD_LENGTH: usize = 8;
-    const MAX_PASSWORD_LENGTH: usize = 128;
-
-    if password != password_confirmation {
-        return Err(ServerFnError::new("Passwords don't match."));
-    }
-    let password_length = password.len();
-    if password_length < MIN_PASSWORD_LENGTH {
-        return Err(ServerFnError::new(format!(
-            "Password is too short, it must be at least {}",
-            MIN_PASSWORD_LENGTH
-        )));
-    }
-    if password_length > MAX_PASSWORD_LENGTH {
-        return Err(ServerFnError::new(format!(
-            "Password is too long it must not exceed {}",
-            MAX_PASSWORD_LENGTH
-        )));
-    }
 
+    validate_password(&password, &password_confirmation)?;
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
-    let argon2 = Argon2::default();
-    let salt = SaltString::generate(&mut OsRng);
-    let password = argon2
-        .hash_password(password.as_bytes(), &salt)
-        .map_err(ServerFnError::new)?
-        .to_string();
+    let password = hash_password(&password)?;
     let email = email.to_lowercase();
     let new_user = NewUser::new(&username, &password, &email)?;
 
+    // Guests who register keep the games they played
+    let guest = match uuid() {
+        Ok(uuid) => User::find_by_uuid(&uuid, &mut conn)
+            .await
+            .ok()
+            .filter(|user| user.guest),
+        Err(_) => None,
+    };
     let user = conn
         .transaction::<_, DbError, _>(move |tc| {
-            async move { User::create(new_user, tc).await }.scope_boxed()
+            async move {
+                match guest {
+                    Some(guest) => guest.claim_guest(new_user, tc).await,
+                    None => User::create(new_user, tc).await,
+                }
+            }
+            .scope_boxed()
         })
         .await?;
+    // The account works without a verified address, so a failed mail can be resent later
+    if let Err(err) = send_verification(&user, &**mailer()?, &mut conn).await {
+        log::error!(
+            "Could not send the verification mail to {}: {err}",
+            user.username
+        );
+    }
 
-    let req = use_context::<actix_web::HttpRequest>()
-        .ok_or("Failed to get HttpRequest")
-        .map_err(ServerFnError::new)?;
-    Identity::login(&req.extensions(), user.id.to_string()).expect("To have logged in");
+    sign_in(&user)?;
     leptos_actix::redirect(&pathname);
 
     Ok(())
@@ -0,0 +1,206 @@
+use crate::functions::auth::identity::session;
+use crate::responses::TotpEnrolmentResponse;
+use chrono::{DateTime, Duration, Utc};
+use db_lib::{
+    db_error::DbError,
+    models::{RecoveryCode, User},
+    DbConn,
+};
+use leptos::*;
+use qrcode::{render::svg, QrCode};
+use serde::{Deserialize, Serialize};
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+const ISSUER: &str = "HiveGame";
+const PENDING_LOGIN: &str = "pending_login";
+
+// A login waiting for its second factor, kept in the session until then
+#[derive(Serialize, Deserialize)]
+struct PendingLogin {
+    user_id: Uuid,
+    started: DateTime<Utc>,
+}
+
+fn totp(secret: &str, username: &str) -> Result<TOTP, ServerFnError> {
+    let secret = Secret::Encoded(secret.to_owned())
+        .to_bytes()
+        .map_err(ServerFnError::new)?;
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some(ISSUER.to_owned()),
+        username.to_owned(),
+    )
+    .map_err(ServerFnError::new)
+}
+
+// A base32 secret, the form authenticator apps expect
+pub fn new_secret() -> String {
+    Secret::generate_secret().to_encoded().to_string()
+}
+
+pub fn enrolment(secret: &str, username: &str) -> Result<TotpEnrolmentResponse, ServerFnError> {
+    let totp = totp(secret, username)?;
+    let qr_code = QrCode::new(totp.get_url().as_bytes())
+        .map_err(ServerFnError::new)?
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .build();
+    Ok(TotpEnrolmentResponse {
+        secret: totp.get_secret_base32(),
+        qr_code,
+    })
+}
+
+// The time step of the authenticator app that produced the code at the given time, if any
+fn matching_step(
+    secret: &str,
+    username: &str,
+    code: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<i64>, ServerFnError> {
+    let code = code
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    let mut totp = totp(secret, username)?;
+    let skew = i64::from(totp.skew);
+    let step = totp.step;
+    totp.skew = 0;
+    let current = (now.timestamp() as u64 / step) as i64;
+    Ok((current - skew..=current + skew)
+        .find(|candidate| totp.check(&code, *candidate as u64 * step)))
+}
+
+// A step is only good if it is newer than the last one used, so a code that was seen can't be
+// played again
+fn fresh_step(last: Option<i64>, step: i64) -> bool {
+    last.is_none_or(|last| step > last)
+}
+
+// Checks a code of the user's authenticator app, each code is only accepted once
+pub async fn check_code(
+    user: &User,
+    code: &str,
+    conn: &mut DbConn<'_>,
+) -> Result<bool, ServerFnError> {
+    let Some(secret) = &user.totp_secret else {
+        return Ok(false);
+    };
+    match matching_step(secret, &user.username, code, Utc::now())? {
+        Some(step) if fresh_step(user.totp_last_step, step) => {
+            Ok(user.use_totp_step(step, conn).await?)
+        }
+        _ => Ok(false),
+    }
+}
+
+// A code of the authenticator app, or else one of the recovery codes. Wrong codes count against
+// the user, not the session, so logging in again doesn't give more guesses.
+pub async fn check_second_factor(
+    user: &User,
+    code: &str,
+    conn: &mut DbConn<'_>,
+) -> Result<bool, ServerFnError> {
+    if user.is_totp_locked() {
+        return Err(ServerFnError::new(
+            "Too many wrong codes, please try again later.",
+        ));
+    }
+    if check_code(user, code, conn).await? {
+        return Ok(true);
+    }
+    match RecoveryCode::redeem(&user.id, code, conn).await {
+        Ok(()) => {
+            user.reset_totp_failures(conn).await?;
+            Ok(true)
+        }
+        Err(DbError::Unauthorized) => {
+            user.record_totp_failure(conn).await?;
+            Ok(false)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn start_pending_login(user_id: Uuid) -> Result<(), ServerFnError> {
+    let pending = PendingLogin {
+        user_id,
+        started: Utc::now(),
+    };
+    session()?.insert(PENDING_LOGIN, pending)?;
+    Ok(())
+}
+
+// The user of the pending login, it has to be finished within five minutes
+pub fn pending_login_user() -> Result<Uuid, ServerFnError> {
+    let session = session()?;
+    let pending = session
+        .get::<PendingLogin>(PENDING_LOGIN)?
+        .ok_or(ServerFnError::new("Please log in again."))?;
+    if Utc::now() - pending.started > Duration::minutes(5) {
+        session.remove(PENDING_LOGIN);
+        return Err(ServerFnError::new("Please log in again."));
+    }
+    Ok(pending.user_id)
+}
+
+pub fn finish_pending_login() -> Result<(), ServerFnError> {
+    session()?.remove(PENDING_LOGIN);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn code_at(secret: &str, time: DateTime<Utc>) -> String {
+        totp(secret, "player")
+            .unwrap()
+            .generate(time.timestamp() as u64)
+    }
+
+    #[test]
+    fn tests_matching_step_within_skew() {
+        let secret = new_secret();
+        let issued = Utc.timestamp_opt(1_700_000_010, 0).unwrap();
+        let code = code_at(&secret, issued);
+        let step = issued.timestamp() / 30;
+        for offset in [-30, 0, 30] {
+            let now = issued + Duration::seconds(offset);
+            assert_eq!(
+                matching_step(&secret, "player", &code, now).unwrap(),
+                Some(step)
+            );
+        }
+        let spaced = format!("{} {}", &code[..3], &code[3..]);
+        assert_eq!(
+            matching_step(&secret, "player", &spaced, issued).unwrap(),
+            Some(step)
+        );
+    }
+
+    #[test]
+    fn tests_matching_step_outside_skew() {
+        let secret = new_secret();
+        let issued = Utc.timestamp_opt(1_700_000_010, 0).unwrap();
+        let code = code_at(&secret, issued);
+        for offset in [-60, 60, 300] {
+            let now = issued + Duration::seconds(offset);
+            assert_eq!(matching_step(&secret, "player", &code, now).unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn tests_fresh_step() {
+        assert!(fresh_step(None, 10));
+        assert!(fresh_step(Some(9), 10));
+        assert!(!fresh_step(Some(10), 10));
+        assert!(!fresh_step(Some(11), 10));
+    }
+}
@@ -0,0 +1,50 @@
+use leptos::*;
+use shared_types::GameId;
+
+#[server]
+pub async fn get_conditional_moves(game_id: GameId) -> Result<Vec<String>, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::{ConditionalMove, Game};
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let uuid = ensure_active(&mut conn).await?.id;
+    let game = Game::find_by_game_id(&game_id, &mut conn).await?;
+    let lines = ConditionalMove::for_game_and_user(&game.id, &uuid, &mut conn).await?;
+    Ok(lines.into_iter().map(|line| line.moves).collect())
+}
+
+#[server]
+pub async fn add_conditional_moves(
+    game_id: GameId,
+    moves: String,
+) -> Result<Vec<String>, ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::{ConditionalMove, Game, NewConditionalMove};
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let uuid = ensure_active(&mut conn).await?.id;
+    let game = Game::find_by_game_id(&game_id, &mut conn).await?;
+    NewConditionalMove::new(&game, uuid, &moves)?
+        .insert(&mut conn)
+        .await?;
+    let lines = ConditionalMove::for_game_and_user(&game.id, &uuid, &mut conn).await?;
+    Ok(lines.into_iter().map(|line| line.moves).collect())
+}
+
+#[server]
+pub async fn clear_conditional_moves(game_id: GameId) -> Result<(), ServerFnError> {
+    use crate::functions::auth::identity::ensure_active;
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::{ConditionalMove, Game};
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let uuid = ensure_active(&mut conn).await?.id;
+    let game = Game::find_by_game_id(&game_id, &mut conn).await?;
+    ConditionalMove::delete_for_game_and_user(&game.id, &uuid, &mut conn).await?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::responses::GameResponse;
 use leptos::*;
 use shared_types::GameId;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[server]
@@ -24,3 +25,19 @@ pub async fn get_game_from_nanoid(game_id: GameId) -> Result<GameResponse, Serve
         .await
         .map_err(ServerFnError::new)
 }
+
+// How long a realtime opponent has to be gone before the win can be claimed, the server's
+// configuration decides
+#[server]
+pub async fn get_claim_victory_after() -> Result<Duration, ServerFnError> {
+    use actix_web::web::Data;
+    use db_lib::config::DbConfig;
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    Ok(req
+        .app_data::<Data<DbConfig>>()
+        .ok_or("Failed to get config")
+        .map_err(ServerFnError::new)?
+        .claim_victory_after)
+}
@@ -1,7 +1,8 @@
-use crate::responses::{GameResponse, UserResponse};
+use crate::responses::{GameResponse, RatingHistoryResponse, UserResponse};
 use chrono::{DateTime, Utc};
+use hive_lib::GameType;
 use leptos::*;
-use shared_types::GameSpeed;
+use shared_types::{GameSpeed, LeaderboardKind, Statistics};
 use uuid::Uuid;
 
 #[server]
@@ -26,6 +27,21 @@ pub async fn get_user_by_username(username: String) -> Result<UserResponse, Serv
         .map_err(ServerFnError::new)
 }
 
+#[server]
+pub async fn get_rating_history(
+    username: String,
+    game_type: GameType,
+    game_speed: GameSpeed,
+) -> Result<Vec<RatingHistoryResponse>, ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    RatingHistoryResponse::from_username(&username, &game_type, &game_speed, &mut conn)
+        .await
+        .map_err(ServerFnError::new)
+}
+
 #[server]
 pub async fn username_taken(username: String) -> Result<bool, ServerFnError> {
     use crate::functions::db::pool;
@@ -85,6 +101,7 @@ pub async fn get_finished_games_in_batches(
 
 #[server]
 pub async fn get_top_users(
+    game_type: GameType,
     game_speed: GameSpeed,
     limit: i64,
 ) -> Result<Vec<UserResponse>, ServerFnError> {
@@ -93,7 +110,8 @@ pub async fn get_top_users(
     use db_lib::models::{Rating, User};
     let pool = pool()?;
     let mut conn = get_conn(&pool).await?;
-    let top_users: Vec<(User, Rating)> = User::get_top_users(&game_speed, limit, &mut conn).await?;
+    let top_users: Vec<(User, Rating)> =
+        User::get_top_users(&game_type, &game_speed, limit, &mut conn).await?;
     let mut results: Vec<UserResponse> = Vec::new();
     for (user, _rating) in top_users.iter() {
         results.push(
@@ -104,3 +122,61 @@ pub async fn get_top_users(
     }
     Ok(results)
 }
+
+// The monthly boards, with the games played or the rating gained next to every player
+#[server]
+pub async fn get_monthly_leaderboard(
+    kind: LeaderboardKind,
+    game_type: GameType,
+    game_speed: GameSpeed,
+    limit: i64,
+) -> Result<Vec<(UserResponse, f64)>, ServerFnError> {
+    use crate::functions::db::pool;
+    use chrono::{Datelike, TimeZone};
+    use db_lib::get_conn;
+    use db_lib::models::RatingHistory;
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let now = Utc::now();
+    let since = Utc
+        .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now);
+    let board = match kind {
+        LeaderboardKind::TopRated => {
+            return Err(ServerFnError::new("Top rated is not a monthly board"));
+        }
+        LeaderboardKind::MostGames => {
+            RatingHistory::most_games_since(&game_type, &game_speed, since, limit, &mut conn)
+                .await?
+                .into_iter()
+                .map(|(user, games)| (user, games as f64))
+                .collect::<Vec<_>>()
+        }
+        LeaderboardKind::RatingGain => {
+            RatingHistory::biggest_gains_since(&game_type, &game_speed, since, limit, &mut conn)
+                .await?
+        }
+    };
+    let mut results = Vec::new();
+    for (user, value) in board.iter() {
+        results.push((
+            UserResponse::from_model(user, &mut conn)
+                .await
+                .map_err(ServerFnError::new)?,
+            *value,
+        ));
+    }
+    Ok(results)
+}
+
+#[server]
+pub async fn get_statistics(username: String) -> Result<Statistics, ServerFnError> {
+    use crate::functions::db::pool;
+    use db_lib::get_conn;
+    use db_lib::models::{User, UserStatistics};
+    let pool = pool()?;
+    let mut conn = get_conn(&pool).await?;
+    let user = User::find_by_username(&username, &mut conn).await?;
+    Ok(UserStatistics::for_user(&user.id, &mut conn).await?)
+}
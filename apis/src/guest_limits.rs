@@ -0,0 +1,62 @@
+use chrono::{DateTime, Duration, Utc};
+use std::{collections::HashMap, sync::RwLock};
+
+// How many guests one address can start within the window
+const GUESTS_PER_WINDOW: usize = 5;
+const WINDOW: Duration = Duration::hours(1);
+
+// When the guests of each client address were started, so a script can't fill the users table
+#[derive(Debug, Default)]
+pub struct GuestLimits {
+    started: RwLock<HashMap<String, Vec<DateTime<Utc>>>>,
+}
+
+impl GuestLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Counts a new guest for the address, unless it already started its share within the window
+    pub fn try_start(&self, address: &str, now: DateTime<Utc>) -> bool {
+        let Ok(mut started) = self.started.write() else {
+            return false;
+        };
+        started.retain(|_, times| {
+            times.retain(|time| now - *time < WINDOW);
+            !times.is_empty()
+        });
+        let times = started.entry(address.to_owned()).or_default();
+        if times.len() >= GUESTS_PER_WINDOW {
+            return false;
+        }
+        times.push(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_limits_guests_per_address() {
+        let limits = GuestLimits::new();
+        let now = Utc::now();
+        for _ in 0..GUESTS_PER_WINDOW {
+            assert!(limits.try_start("1.2.3.4", now));
+        }
+        assert!(!limits.try_start("1.2.3.4", now));
+        assert!(limits.try_start("5.6.7.8", now));
+    }
+
+    #[test]
+    fn tests_window_passes() {
+        let limits = GuestLimits::new();
+        let now = Utc::now();
+        for _ in 0..GUESTS_PER_WINDOW {
+            assert!(limits.try_start("1.2.3.4", now));
+        }
+        assert!(!limits.try_start("1.2.3.4", now + WINDOW - Duration::seconds(1)));
+        assert!(limits.try_start("1.2.3.4", now + WINDOW));
+    }
+}
@@ -0,0 +1,51 @@
+use std::f32::consts::PI;
+use uuid::Uuid;
+
+// Size of one hex and of the image around the seven of them
+const HEX_SIZE: f32 = 20.0;
+const VIEW_SIZE: f32 = 120.0;
+const BACKGROUND: &str = "#e5e7eb";
+
+fn hex_points(x: f32, y: f32) -> String {
+    (0..6)
+        .map(|corner| {
+            let angle = PI / 3.0 * corner as f32;
+            format!(
+                "{:.2},{:.2}",
+                x + HEX_SIZE * angle.cos(),
+                y + HEX_SIZE * angle.sin()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The avatar of users who did not upload one: a flower of hexes in a colour and pattern taken
+// from their id, so it never changes and looks different for everyone
+pub fn avatar_svg(id: &Uuid) -> String {
+    let bytes = id.as_bytes();
+    let hue = u16::from_be_bytes([bytes[0], bytes[1]]) % 360;
+    let distance = HEX_SIZE * 3.0_f32.sqrt();
+    let mut hexes = vec![(0.0, 0.0, bytes[2])];
+    for (neighbor, byte) in bytes[3..9].iter().enumerate() {
+        let angle = PI / 6.0 + PI / 3.0 * neighbor as f32;
+        hexes.push((distance * angle.cos(), distance * angle.sin(), *byte));
+    }
+    let tiles = hexes
+        .iter()
+        .enumerate()
+        // The middle one is always there, about half of the others are left out
+        .filter(|(index, (_, _, byte))| *index == 0 || byte % 2 == 0)
+        .map(|(_, (x, y, byte))| {
+            format!(
+                r#"<polygon points="{points}" fill="hsl({hue}, 60%, {lightness}%)" stroke="{BACKGROUND}" stroke-width="2"/>"#,
+                points = hex_points(*x, *y),
+                lightness = 35 + byte % 30,
+            )
+        })
+        .collect::<String>();
+    let origin = -VIEW_SIZE / 2.0;
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{VIEW_SIZE}" height="{VIEW_SIZE}" viewBox="{origin} {origin} {VIEW_SIZE} {VIEW_SIZE}"><rect x="{origin}" y="{origin}" width="{VIEW_SIZE}" height="{VIEW_SIZE}" fill="{BACKGROUND}"/>{tiles}</svg>"#
+    )
+}
@@ -0,0 +1,97 @@
+use super::{
+    avatar::avatar_svg,
+    position::{position_svg, HEIGHT, WIDTH},
+};
+use actix_web::{
+    error::{ErrorInternalServerError, ErrorNotFound},
+    get,
+    http::header::{CacheControl, CacheDirective},
+    web::{Data, Path, Query},
+    Error, HttpResponse,
+};
+use db_lib::{
+    get_conn,
+    models::{Avatar, Game, User},
+    DbPool,
+};
+use hive_lib::State;
+use resvg::{tiny_skia, usvg};
+use serde::Deserialize;
+use shared_types::GameId;
+
+#[derive(Deserialize)]
+pub struct ImageQuery {
+    // Show the position after this many moves instead of the current one
+    turn: Option<usize>,
+}
+
+async fn game_svg_string(
+    nanoid: String,
+    turn: Option<usize>,
+    pool: &DbPool,
+) -> Result<String, Error> {
+    let mut conn = get_conn(pool).await.map_err(ErrorInternalServerError)?;
+    let game = Game::find_by_game_id(&GameId(nanoid), &mut conn)
+        .await
+        .map_err(ErrorNotFound)?;
+    let moves = game
+        .history
+        .split_terminator(';')
+        .take(turn.unwrap_or(usize::MAX))
+        .map(|mov| format!("{mov};"))
+        .collect::<String>();
+    let state = State::new_from_str_with(&moves, &game.game_type, game.automatic_repetition())
+        .map_err(ErrorInternalServerError)?;
+    Ok(position_svg(&state))
+}
+
+fn image_response(content_type: &str, body: impl Into<actix_web::body::BoxBody>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(60),
+        ]))
+        .body(body)
+}
+
+#[get("/game/{nanoid}/image.svg")]
+pub async fn game_svg(
+    nanoid: Path<String>,
+    query: Query<ImageQuery>,
+    pool: Data<DbPool>,
+) -> Result<HttpResponse, Error> {
+    let svg = game_svg_string(nanoid.into_inner(), query.turn, &pool).await?;
+    Ok(image_response("image/svg+xml", svg))
+}
+
+#[get("/game/{nanoid}/image.png")]
+pub async fn game_png(
+    nanoid: Path<String>,
+    query: Query<ImageQuery>,
+    pool: Data<DbPool>,
+) -> Result<HttpResponse, Error> {
+    let svg = game_svg_string(nanoid.into_inner(), query.turn, &pool).await?;
+    let tree =
+        usvg::Tree::from_str(&svg, &usvg::Options::default()).map_err(ErrorInternalServerError)?;
+    let mut pixmap = tiny_skia::Pixmap::new(WIDTH as u32, HEIGHT as u32)
+        .ok_or_else(|| ErrorInternalServerError("Could not allocate the image"))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    let png = pixmap.encode_png().map_err(ErrorInternalServerError)?;
+    Ok(image_response("image/png", png))
+}
+
+#[get("/@/{username}/avatar")]
+pub async fn avatar(username: Path<String>, pool: Data<DbPool>) -> Result<HttpResponse, Error> {
+    let mut conn = get_conn(&pool).await.map_err(ErrorInternalServerError)?;
+    let user = User::find_by_username(&username, &mut conn)
+        .await
+        .map_err(ErrorNotFound)?;
+    let uploaded = Avatar::find_by_user(&user.id, &mut conn)
+        .await
+        .map_err(ErrorInternalServerError)?;
+    Ok(match uploaded {
+        Some(avatar) => image_response(&avatar.content_type, avatar.image),
+        None => image_response("image/svg+xml", avatar_svg(&user.id)),
+    })
+}
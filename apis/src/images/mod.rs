@@ -0,0 +1,3 @@
+pub mod avatar;
+pub mod handler;
+pub mod position;
@@ -0,0 +1,78 @@
+use crate::common::{HexStack, HexType, SvgPos};
+use apis::components::atoms::svgs::Svgs;
+use hive_lib::{Bug, Position, State};
+use leptos::*;
+use std::sync::OnceLock;
+
+// Size of the rendered image, the usual size of link previews
+pub const WIDTH: f32 = 1200.0;
+pub const HEIGHT: f32 = 630.0;
+// Space around the pieces and the smallest area shown, in board units
+const PADDING: f32 = 60.0;
+const MIN_WIDTH: f32 = 480.0;
+const BACKGROUND: &str = "#e5e7eb";
+
+// The piece art of the board, rendered once from the same component the client uses
+fn defs() -> &'static str {
+    static DEFS: OnceLock<String> = OnceLock::new();
+    DEFS.get_or_init(|| leptos::ssr::render_to_string(|| view! { <Svgs/> }).to_string())
+}
+
+fn dot_color(bug: Bug) -> &'static str {
+    match bug {
+        Bug::Ant => "#289ee0",
+        Bug::Beetle => "#9a7fc7",
+        Bug::Grasshopper => "#42b23c",
+        Bug::Spider => "#a4572a",
+        _ => "#FF0000",
+    }
+}
+
+// The board of the state as a standalone SVG, drawn with the official tile design
+pub fn position_svg(state: &State) -> String {
+    let mut pieces = String::new();
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    for r in 0..32 {
+        for q in 0..32 {
+            let position = Position::new(q, r);
+            let bug_stack = state.board.board.get(position);
+            if bug_stack.is_empty() {
+                continue;
+            }
+            for hex in HexStack::new_history(bug_stack, position).hexes {
+                let HexType::Tile(piece, _) = hex.kind else {
+                    continue;
+                };
+                let (x, y) = SvgPos::center_for_level(hex.position, hex.level);
+                (min_x, min_y) = (min_x.min(x), min_y.min(y));
+                (max_x, max_y) = (max_x.max(x), max_y.max(y));
+                pieces.push_str(&format!(
+                    r##"<g style="color: {color}"><g transform="translate({x},{y})"><use href="#ds" transform="scale(0.56, 0.56) translate(-67, -64.5)"></use></g><g transform="translate({x},{y})"><use href="#{tile}" transform="scale(0.56, 0.56) translate(-45, -50)"></use><use href="#{bug}" transform="scale(0.56, 0.56) translate(-45, -50)"></use><use href="#{order}" transform="scale(0.56, 0.56) translate(-45, -50)"></use></g></g>"##,
+                    color = dot_color(piece.bug()),
+                    tile = piece.color(),
+                    bug = piece.bug(),
+                    order = piece.order(),
+                ));
+            }
+        }
+    }
+    if pieces.is_empty() {
+        (min_x, min_y, max_x, max_y) = (0.0, 0.0, 0.0, 0.0);
+    }
+    // Fits the pieces into the image without distorting them
+    let mut width = (max_x - min_x + 2.0 * PADDING).max(MIN_WIDTH);
+    let mut height = max_y - min_y + 2.0 * PADDING;
+    if width / height < WIDTH / HEIGHT {
+        width = height * WIDTH / HEIGHT;
+    } else {
+        height = width * HEIGHT / WIDTH;
+    }
+    let (x, y) = (
+        (min_x + max_x - width) / 2.0,
+        (min_y + max_y - height) / 2.0,
+    );
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="{x} {y} {width} {height}"><rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{BACKGROUND}"/>{defs}{pieces}</svg>"#,
+        defs = defs(),
+    )
+}
@@ -0,0 +1,50 @@
+use actix_web::web::Data;
+use apis::mailer::{send_digest, Mailer};
+use chrono::Utc;
+use db_lib::{get_conn, models::NotificationSettings, DbPool};
+use std::time::Duration;
+
+pub fn run(pool: DbPool, mailer: Data<dyn Mailer>) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            let Ok(mut conn) = get_conn(&pool).await else {
+                continue;
+            };
+            let subscribed = match NotificationSettings::subscribed(&mut conn).await {
+                Ok(subscribed) => subscribed,
+                Err(e) => {
+                    println!("Failed to get the e-mail digests: {e}");
+                    continue;
+                }
+            };
+            for (user, settings) in subscribed {
+                let now = Utc::now();
+                let digest = match settings.digest(now, &mut conn).await {
+                    Ok(digest) => digest,
+                    Err(e) => {
+                        println!(
+                            "Failed to put together the digest of {}: {e}",
+                            user.username
+                        );
+                        continue;
+                    }
+                };
+                if !digest.is_empty() {
+                    if let Err(e) = send_digest(&user, &digest, &**mailer).await {
+                        // Nothing is marked as sent, so the next run tries again
+                        println!("Failed to send the digest to {}: {e}", user.username);
+                        continue;
+                    }
+                }
+                if let Err(e) = settings.digest_sent(now, &mut conn).await {
+                    println!(
+                        "Failed to mark the digest of {} as sent: {e}",
+                        user.username
+                    );
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,46 @@
+use actix_web::web;
+use chrono::{Duration as ChronoDuration, Utc};
+use db_lib::{get_conn, models::FairPlayReport, DbPool};
+use std::time::Duration;
+
+// Once a day everyone who finished a game since the last run gets their report refreshed
+pub fn run(pool: DbPool) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(60 * 60 * 24));
+        loop {
+            interval.tick().await;
+            let Ok(mut conn) = get_conn(&pool).await else {
+                continue;
+            };
+            let since = Utc::now() - ChronoDuration::days(1);
+            let players = match FairPlayReport::players_since(since, &mut conn).await {
+                Ok(players) => players,
+                Err(e) => {
+                    println!("Failed to get the players for fair-play analysis: {e}");
+                    continue;
+                }
+            };
+            for user_id in players {
+                let games = match FairPlayReport::games_to_analyse(&user_id, &mut conn).await {
+                    Ok(games) => games,
+                    Err(e) => {
+                        println!("Failed to get the games of {user_id} to analyse: {e}");
+                        continue;
+                    }
+                };
+                // Replaying games with the engine is slow, so it runs on the blocking pool
+                let report =
+                    match web::block(move || FairPlayReport::analyse(user_id, &games)).await {
+                        Ok(report) => report,
+                        Err(e) => {
+                            println!("Failed to analyse the games of {user_id}: {e}");
+                            continue;
+                        }
+                    };
+                if let Err(e) = report.save(&mut conn).await {
+                    println!("Failed to save the fair-play report of {user_id}: {e}");
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,90 @@
+use crate::common::{GameActionResponse, GameReaction, GameUpdate, ServerMessage, ServerResult};
+use crate::responses::GameResponse;
+use crate::websockets::game_states::GameStates;
+use crate::websockets::internal_server_message::MessageDestination;
+use crate::websockets::lobby::Lobby;
+use crate::websockets::messages::ClientActorMessage;
+use crate::websockets::premoves::Premoves;
+use crate::websockets::timers::Timers;
+use actix::Addr;
+use actix_web::web::Data;
+use anyhow::Result;
+use chrono::Utc;
+use db_lib::{get_conn, models::Game, DbPool};
+use hive_lib::{Color, GameControl};
+use std::time::Duration;
+
+// Aborts games where a player never made their first move
+pub fn run(
+    pool: DbPool,
+    lobby: Data<Addr<Lobby>>,
+    timers: Data<Timers>,
+    premoves: Data<Premoves>,
+    game_states: Data<GameStates>,
+    window: Duration,
+) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if let Err(e) =
+                abort_unstarted(&pool, &lobby, &timers, &premoves, &game_states, window).await
+            {
+                println!("Failed to abort unstarted games: {e}");
+            }
+        }
+    });
+}
+
+async fn abort_unstarted(
+    pool: &DbPool,
+    lobby: &Addr<Lobby>,
+    timers: &Timers,
+    premoves: &Premoves,
+    game_states: &GameStates,
+    window: Duration,
+) -> Result<()> {
+    let mut conn = get_conn(pool).await?;
+    let cutoff = Utc::now() - chrono::Duration::from_std(window)?;
+    for game in Game::get_unstarted_before(cutoff, &mut conn).await? {
+        let game_response = GameResponse::from_model(&game, &mut conn).await?;
+        game.delete(&mut conn).await?;
+        timers.remove(&game_response.game_id);
+        premoves.remove(&game_response.game_id);
+        game_states.invalidate(&game_response.game_id);
+        // The abort goes out in the name of the player who didn't move
+        let (color, player) = if game.turn % 2 == 0 {
+            (Color::White, &game_response.white_player)
+        } else {
+            (Color::Black, &game_response.black_player)
+        };
+        let message = ServerResult::Ok(Box::new(ServerMessage::Game(Box::new(
+            GameUpdate::Reaction(GameActionResponse {
+                game_action: GameReaction::Control(GameControl::Abort(color)),
+                game_id: game_response.game_id.clone(),
+                user_id: player.uid,
+                username: player.username.clone(),
+                game: game_response.clone(),
+            }),
+        ))));
+        let serialized =
+            serde_json::to_string(&message).expect("Failed to serialize a server message");
+        // The players hear about it wherever they are, spectators on the game
+        for destination in [
+            MessageDestination::User(game.white_id),
+            MessageDestination::User(game.black_id),
+            MessageDestination::GameSpectators(
+                game_response.game_id.clone(),
+                game.white_id,
+                game.black_id,
+            ),
+        ] {
+            lobby.do_send(ClientActorMessage {
+                destination,
+                serialized: serialized.clone(),
+                from: None,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use db_lib::{get_conn, models::User, DbPool};
+use std::time::Duration;
+
+pub fn run(pool: DbPool) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Ok(mut conn) = get_conn(&pool).await {
+                if let Err(e) = User::delete_stale_guests(&mut conn).await {
+                    println!("Failed to delete stale guests: {e}");
+                }
+                if let Err(e) = User::retire_idle_guests(&mut conn).await {
+                    println!("Failed to retire idle guests: {e}");
+                }
+            }
+        }
+    });
+}
@@ -1,2 +1,9 @@
 pub mod tournament_start;
 pub mod heartbeat;
+pub mod game_abort;
+pub mod rating_decay;
+pub mod timeouts;
+pub mod email_digest;
+pub mod guest_cleanup;
+pub mod fair_play;
+pub mod presence_prune;
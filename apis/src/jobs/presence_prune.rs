@@ -0,0 +1,29 @@
+use crate::websockets::presence::Presence;
+use actix_web::web::Data;
+use db_lib::{get_conn, models::Game, DbPool};
+use shared_types::TimeMode;
+use std::collections::HashSet;
+use std::time::Duration;
+
+// Leaving only matters to the opponents of running realtime games
+pub fn run(pool: DbPool, presence: Data<Presence>) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(10 * 60));
+        loop {
+            interval.tick().await;
+            if let Ok(mut conn) = get_conn(&pool).await {
+                match Game::get_running_timed(&mut conn).await {
+                    Ok(games) => {
+                        let players = games
+                            .iter()
+                            .filter(|game| game.time_mode == TimeMode::RealTime.to_string())
+                            .flat_map(|game| [game.white_id, game.black_id])
+                            .collect::<HashSet<_>>();
+                        presence.prune(&players);
+                    }
+                    Err(e) => println!("Failed to prune presence: {e}"),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,16 @@
+use db_lib::{get_conn, models::Rating, DbPool};
+use std::time::Duration;
+
+pub fn run(pool: DbPool) {
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if let Ok(mut conn) = get_conn(&pool).await {
+                if let Err(e) = Rating::decay_inactive(&mut conn).await {
+                    println!("Failed to decay ratings: {e}");
+                }
+            }
+        }
+    });
+}
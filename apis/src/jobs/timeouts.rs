@@ -0,0 +1,125 @@
+use crate::common::{GameActionResponse, GameReaction, GameUpdate, ServerMessage, ServerResult};
+use crate::responses::GameResponse;
+use crate::websockets::api::tournaments::armageddon::armageddon_messages;
+use crate::websockets::game_states::GameStates;
+use crate::websockets::internal_server_message::MessageDestination;
+use crate::websockets::lobby::Lobby;
+use crate::websockets::messages::ClientActorMessage;
+use crate::websockets::timers::Timers;
+use actix::Addr;
+use actix_web::web::Data;
+use anyhow::Result;
+use chrono::Utc;
+use db_lib::{get_conn, models::Game, DbPool};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+use shared_types::{Conclusion, GameId};
+use std::str::FromStr;
+use std::time::Duration;
+
+// Games that change outside of the websocket handlers get picked up by the periodic sync
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+pub fn run(
+    pool: DbPool,
+    lobby: Data<Addr<Lobby>>,
+    timers: Data<Timers>,
+    game_states: Data<GameStates>,
+) {
+    let sync_pool = pool.clone();
+    let sync_timers = timers.clone();
+    actix_rt::spawn(async move {
+        let mut interval = actix_rt::time::interval(SYNC_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Ok(mut conn) = get_conn(&sync_pool).await {
+                if let Ok(games) = Game::get_running_timed(&mut conn).await {
+                    sync_timers.sync(&games);
+                }
+            }
+        }
+    });
+    actix_rt::spawn(async move {
+        loop {
+            let sleep = timers
+                .next_deadline()
+                .map(|deadline| {
+                    deadline
+                        .signed_duration_since(Utc::now())
+                        .to_std()
+                        .unwrap_or_default()
+                })
+                .unwrap_or(MAX_SLEEP)
+                .min(MAX_SLEEP);
+            // Wakes up early when a clock changed, the next deadline might be sooner
+            let _ = actix_rt::time::timeout(sleep, timers.changed()).await;
+            for game_id in timers.take_expired() {
+                if let Err(e) = flag(&game_id, &pool, &lobby, &timers, &game_states).await {
+                    println!("Failed to time out {game_id}: {e}");
+                }
+            }
+        }
+    });
+}
+
+async fn flag(
+    game_id: &GameId,
+    pool: &DbPool,
+    lobby: &Addr<Lobby>,
+    timers: &Timers,
+    game_states: &GameStates,
+) -> Result<()> {
+    let mut conn = get_conn(pool).await?;
+    let game_id = game_id.clone();
+    let (game, game_response) = conn
+        .transaction::<_, anyhow::Error, _>(move |tc| {
+            async move {
+                // find_by_game_id times the game out
+                let game = Game::find_by_game_id(&game_id, tc).await?;
+                let game_response = GameResponse::from_model(&game, tc).await?;
+                Ok((game, game_response))
+            }
+            .scope_boxed()
+        })
+        .await?;
+    if !game.finished {
+        // A move landed just in time
+        timers.update_from_model(&game);
+        return Ok(());
+    }
+    game_states.invalidate(&game_response.game_id);
+    if Conclusion::from_str(&game.conclusion)? != Conclusion::Timeout {
+        return Ok(());
+    }
+    let flagged = if game.turn % 2 == 0 {
+        &game_response.white_player
+    } else {
+        &game_response.black_player
+    };
+    let message = ServerResult::Ok(Box::new(ServerMessage::Game(Box::new(
+        GameUpdate::Reaction(GameActionResponse {
+            game_action: GameReaction::TimedOut,
+            game_id: game_response.game_id.clone(),
+            user_id: flagged.uid,
+            username: flagged.username.clone(),
+            game: game_response.clone(),
+        }),
+    ))));
+    let serialized = serde_json::to_string(&message).expect("Failed to serialize a server message");
+    lobby.do_send(ClientActorMessage {
+        destination: MessageDestination::Global,
+        serialized,
+        from: None,
+    });
+    for message in armageddon_messages(&game, &mut conn).await? {
+        let serialized = serde_json::to_string(&ServerResult::Ok(Box::new(message.message)))
+            .expect("Failed to serialize a server message");
+        lobby.do_send(ClientActorMessage {
+            destination: message.destination,
+            serialized,
+            from: None,
+        });
+    }
+    Ok(())
+}
@@ -2,9 +2,17 @@ pub mod app;
 pub mod common;
 pub mod components;
 pub mod functions;
+#[cfg(feature = "ssr")]
+pub mod guest_limits;
+#[cfg(feature = "ssr")]
+pub mod mailer;
 pub mod pages;
 pub mod providers;
+#[cfg(feature = "ssr")]
+pub mod resignations;
 pub mod responses;
+#[cfg(feature = "ssr")]
+pub mod sessions;
 
 use cfg_if::cfg_if;
 
@@ -0,0 +1,43 @@
+use super::{Mail, MailError, MailFuture, Mailer};
+use chrono::Utc;
+use std::path::PathBuf;
+
+// Stand-in for local development and tests: mails end up in a directory, or in the log
+pub struct FileMailer {
+    dir: Option<PathBuf>,
+}
+
+impl FileMailer {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+}
+
+impl Mailer for FileMailer {
+    fn send(&self, mail: Mail) -> MailFuture<'_> {
+        Box::pin(async move {
+            let text = format!(
+                "To: {}\nSubject: {}\n\n{}",
+                mail.to, mail.subject, mail.body
+            );
+            match &self.dir {
+                Some(dir) => {
+                    let path = dir.join(format!(
+                        "{}.eml",
+                        Utc::now().timestamp_nanos_opt().unwrap_or_default()
+                    ));
+                    tokio::fs::create_dir_all(dir)
+                        .await
+                        .map_err(|err| MailError::Transport(err.to_string()))?;
+                    tokio::fs::write(path, text)
+                        .await
+                        .map_err(|err| MailError::Transport(err.to_string()))
+                }
+                None => {
+                    log::warn!("{text}");
+                    Ok(())
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,196 @@
+mod file;
+mod smtp;
+pub use file::FileMailer;
+pub use smtp::SmtpMailer;
+
+use actix_web::web::Data;
+use db_lib::{
+    models::{Digest, EmailToken, TokenPurpose, Tournament, User},
+    DbConn,
+};
+use leptos::*;
+use std::{env, future::Future, path::PathBuf, pin::Pin, sync::Arc};
+use thiserror::Error;
+
+const DEFAULT_SITE_URL: &str = "https://hivegame.com";
+const DEFAULT_FROM: &str = "HiveGame <noreply@hivegame.com>";
+
+#[derive(Error, Debug)]
+pub enum MailError {
+    #[error("Invalid e-mail address: {0}")]
+    Address(String),
+    #[error("Could not send e-mail: {0}")]
+    Transport(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+pub type MailFuture<'a> = Pin<Box<dyn Future<Output = Result<(), MailError>> + Send + 'a>>;
+
+// Anything that can deliver mails, so that development and tests don't need an SMTP server
+pub trait Mailer: Send + Sync {
+    fn send(&self, mail: Mail) -> MailFuture<'_>;
+}
+
+// Uses SMTP when SMTP_HOST is set, otherwise mails get written to MAIL_DIR or the log
+pub fn from_env() -> Arc<dyn Mailer> {
+    let from = env::var("MAIL_FROM").unwrap_or(String::from(DEFAULT_FROM));
+    match env::var("SMTP_HOST") {
+        Ok(host) => {
+            let username = env::var("SMTP_USERNAME").unwrap_or_default();
+            let password = env::var("SMTP_PASSWORD").unwrap_or_default();
+            match SmtpMailer::new(&host, username, password, &from) {
+                Ok(mailer) => return Arc::new(mailer),
+                Err(err) => log::error!("Falling back to the file mailer: {err}"),
+            }
+        }
+        Err(_) => log::warn!("SMTP_HOST is not set, mails will not be sent"),
+    }
+    Arc::new(FileMailer::new(
+        env::var("MAIL_DIR").ok().map(PathBuf::from),
+    ))
+}
+
+pub fn mailer() -> Result<Data<dyn Mailer>, ServerFnError> {
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    let mailer = req
+        .app_data::<Data<dyn Mailer>>()
+        .ok_or("Failed to get mailer")
+        .map_err(ServerFnError::new)?
+        .clone();
+    Ok(mailer)
+}
+
+fn site_url() -> String {
+    let site_url = env::var("SITE_URL").unwrap_or(String::from(DEFAULT_SITE_URL));
+    site_url.trim_end_matches('/').to_owned()
+}
+
+// Guests have no address to write to
+fn address(user: &User) -> Result<String, MailError> {
+    user.email
+        .clone()
+        .ok_or_else(|| MailError::Address(format!("{} has none", user.username)))
+}
+
+fn link(path: &str, token: &str) -> String {
+    format!("{}/{path}?token={token}", site_url())
+}
+
+pub async fn send_verification(
+    user: &User,
+    mailer: &dyn Mailer,
+    conn: &mut DbConn<'_>,
+) -> Result<(), ServerFnError> {
+    let to = address(user).map_err(ServerFnError::new)?;
+    let token = EmailToken::issue(&user.id, TokenPurpose::VerifyEmail, conn).await?;
+    let mail = Mail {
+        to,
+        subject: String::from("Verify your e-mail address"),
+        body: format!(
+            "Hi {},\n\nplease confirm your e-mail address by opening this link:\n\n{}\n\nThe link is valid for {} hours.\n",
+            user.username,
+            link("verify_email", &token),
+            TokenPurpose::VerifyEmail.lifetime().num_hours(),
+        ),
+    };
+    mailer.send(mail).await.map_err(ServerFnError::new)
+}
+
+pub async fn send_password_reset(
+    user: &User,
+    mailer: &dyn Mailer,
+    conn: &mut DbConn<'_>,
+) -> Result<(), ServerFnError> {
+    let to = address(user).map_err(ServerFnError::new)?;
+    let token = EmailToken::issue(&user.id, TokenPurpose::ResetPassword, conn).await?;
+    let mail = Mail {
+        to,
+        subject: String::from("Reset your password"),
+        body: format!(
+            "Hi {},\n\nsomeone asked to reset the password of your account. To choose a new one open this link:\n\n{}\n\nThe link is valid for {} minutes. If you didn't ask for this, you can ignore this e-mail.\n",
+            user.username,
+            link("reset_password", &token),
+            TokenPurpose::ResetPassword.lifetime().num_minutes(),
+        ),
+    };
+    mailer.send(mail).await.map_err(ServerFnError::new)
+}
+
+fn digest_section(body: &mut String, title: &str, links: Vec<String>) {
+    if links.is_empty() {
+        return;
+    }
+    body.push_str(&format!("{title}\n"));
+    for link in links {
+        body.push_str(&format!("  {link}\n"));
+    }
+    body.push('\n');
+}
+
+pub async fn send_digest(
+    user: &User,
+    digest: &Digest,
+    mailer: &dyn Mailer,
+) -> Result<(), MailError> {
+    let site_url = site_url();
+    let game_link = |nanoid: &str| format!("{site_url}/game/{nanoid}");
+    let tournament_line = |tournament: &Tournament| {
+        format!(
+            "{} {site_url}/tournament/{}",
+            tournament.name, tournament.nanoid
+        )
+    };
+    let mut body = format!(
+        "Hi {},\n\nhere is what happened on HiveGame:\n\n",
+        user.username
+    );
+    digest_section(
+        &mut body,
+        "Your time is running low in:",
+        digest
+            .time_running_low
+            .iter()
+            .map(|game| game_link(&game.nanoid))
+            .collect(),
+    );
+    digest_section(
+        &mut body,
+        "It's your turn in:",
+        digest
+            .your_turn
+            .iter()
+            .map(|game| game_link(&game.nanoid))
+            .collect(),
+    );
+    digest_section(
+        &mut body,
+        "These tournaments are starting:",
+        digest
+            .tournaments_starting
+            .iter()
+            .map(tournament_line)
+            .collect(),
+    );
+    digest_section(
+        &mut body,
+        "You have been invited to:",
+        digest.invitations.iter().map(tournament_line).collect(),
+    );
+    body.push_str(&format!(
+        "You can turn these e-mails off at {site_url}/account\n"
+    ));
+    let mail = Mail {
+        to: address(user)?,
+        subject: String::from("News from your games"),
+        body,
+    };
+    mailer.send(mail).await
+}
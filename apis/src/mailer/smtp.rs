@@ -0,0 +1,47 @@
+use super::{Mail, MailError, MailFuture, Mailer};
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: &str,
+        username: String,
+        password: String,
+        from: &str,
+    ) -> Result<Self, MailError> {
+        let from = from
+            .parse()
+            .map_err(|_| MailError::Address(from.to_owned()))?;
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+            .map_err(|err| MailError::Transport(err.to_string()))?
+            .credentials(Credentials::new(username, password))
+            .build();
+        Ok(Self { transport, from })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, mail: Mail) -> MailFuture<'_> {
+        Box::pin(async move {
+            let to: Mailbox = mail.to.parse().map_err(|_| MailError::Address(mail.to))?;
+            let message = Message::builder()
+                .from(self.from.clone())
+                .to(to)
+                .subject(mail.subject)
+                .body(mail.body)
+                .map_err(|err| MailError::Transport(err.to_string()))?;
+            self.transport
+                .send(message)
+                .await
+                .map_err(|err| MailError::Transport(err.to_string()))?;
+            Ok(())
+        })
+    }
+}
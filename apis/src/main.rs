@@ -1,11 +1,20 @@
 pub mod common;
+pub mod export;
 pub mod functions;
+pub mod images;
 pub mod jobs;
 pub mod responses;
 pub mod websockets;
+// Shared with the library, so what is handed to the app is what server functions look up
+pub use apis::{guest_limits, mailer, resignations, sessions};
 use actix_session::config::PersistentSession;
 use actix_web::cookie::time::Duration;
 use actix_web::middleware::Compress;
+use websockets::game_states::GameStates;
+use websockets::premoves::Premoves;
+use websockets::presence::Presence;
+use websockets::rematch_offers::RematchOffers;
+use websockets::timers::Timers;
 use websockets::tournament_game_start::TournamentGameStart;
 
 cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
@@ -13,15 +22,20 @@ cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     use crate::websockets::{chat::Chats, lobby::Lobby,start_connection};
-    use actix::Actor;
+    use actix::{Actor, Recipient};
     use actix_files::Files;
     use actix_identity::IdentityMiddleware;
     use actix_session::{storage::CookieSessionStore, SessionMiddleware};
     use actix_web::{cookie::Key, App, HttpServer, web::Data,};
     use apis::app::App;
-    use db_lib::{config::DbConfig, get_pool};
+    use apis::guest_limits::GuestLimits;
+    use apis::mailer::Mailer;
+    use apis::resignations::GamesResigned;
+    use apis::sessions::EndSessions;
+    use db_lib::{config::DbConfig, db_error::DbError, get_conn, get_pool, models::Rating};
     use diesel::pg::PgConnection;
     use diesel::Connection;
+    use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
     use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
     use leptos::*;
     use leptos_actix::{generate_route_list, LeptosRoutes};
@@ -48,12 +62,64 @@ async fn main() -> std::io::Result<()> {
     let pool = get_pool(&config.database_url)
         .await
         .expect("Failed to get pool");
+    let mut rebuild_conn = get_conn(&pool).await.expect("Failed to get connection");
+    let replayed = rebuild_conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move { Rating::recompute_if_pending(tc).await }.scope_boxed()
+        })
+        .await
+        .expect("Failed to recompute ratings");
+    if let Some(replayed) = replayed {
+        println!("Recomputed ratings from {replayed} games");
+    }
+    drop(rebuild_conn);
     let chat_history = Data::new(Chats::new());
-    let websocket_server = Data::new(Lobby::new(pool.clone()).start());
+    let timers = Data::new(Timers::new());
+    let presence = Data::new(Presence::new(config.claim_victory_after));
+    let rematch_offers = Data::new(RematchOffers::new());
+    let premoves = Data::new(Premoves::new());
+    let game_states = Data::new(GameStates::new());
+    let websocket_server = Data::new(
+        Lobby::new(
+            pool.clone(),
+            Data::clone(&timers),
+            Data::clone(&presence),
+            Data::clone(&rematch_offers),
+            Data::clone(&premoves),
+            Data::clone(&game_states),
+        )
+        .start(),
+    );
+    let end_sessions: Data<Recipient<EndSessions>> =
+        Data::new(websocket_server.get_ref().clone().recipient());
+    let games_resigned: Data<Recipient<GamesResigned>> =
+        Data::new(websocket_server.get_ref().clone().recipient());
     let tournament_game_start = Data::new(TournamentGameStart::new());
+    let mailer: Data<dyn Mailer> = Data::from(apis::mailer::from_env());
+    let db_config = Data::new(config.clone());
+    let guest_limits = Data::new(GuestLimits::new());
 
     jobs::tournament_start::run(pool.clone(), Data::clone(&websocket_server));
+    jobs::rating_decay::run(pool.clone());
+    jobs::guest_cleanup::run(pool.clone());
+    jobs::fair_play::run(pool.clone());
+    jobs::game_abort::run(
+        pool.clone(),
+        Data::clone(&websocket_server),
+        Data::clone(&timers),
+        Data::clone(&premoves),
+        Data::clone(&game_states),
+        config.abort_unstarted_after,
+    );
     jobs::heartbeat::run(Data::clone(&websocket_server));
+    jobs::email_digest::run(pool.clone(), Data::clone(&mailer));
+    jobs::timeouts::run(
+        pool.clone(),
+        Data::clone(&websocket_server),
+        Data::clone(&timers),
+        Data::clone(&game_states),
+    );
+    jobs::presence_prune::run(pool.clone(), Data::clone(&presence));
 
     println!("listening on http://{}", &addr);
 
@@ -65,7 +131,17 @@ async fn main() -> std::io::Result<()> {
             .app_data(Data::new(pool.clone()))
             .app_data(Data::clone(&chat_history))
             .app_data(Data::clone(&websocket_server))
+            .app_data(Data::clone(&end_sessions))
+            .app_data(Data::clone(&games_resigned))
             .app_data(Data::clone(&tournament_game_start))
+            .app_data(Data::clone(&rematch_offers))
+            .app_data(Data::clone(&premoves))
+            .app_data(Data::clone(&game_states))
+            .app_data(Data::clone(&timers))
+            .app_data(Data::clone(&presence))
+            .app_data(Data::clone(&mailer))
+            .app_data(Data::clone(&db_config))
+            .app_data(Data::clone(&guest_limits))
             // serve JS/WASM/CSS from `pkg`
             .service(Files::new("/pkg", format!("{site_root}/pkg")))
             // serve other assets from the `assets` directory
@@ -73,6 +149,10 @@ async fn main() -> std::io::Result<()> {
             // serve the favicon from /favicon.ico
             .service(favicon)
             .service(start_connection::start_connection)
+            .service(images::handler::game_svg)
+            .service(images::handler::game_png)
+            .service(images::handler::avatar)
+            .service(export::handler::account_export)
             // .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
             .leptos_routes(
                 leptos_options.to_owned(),
@@ -1,10 +1,21 @@
-use crate::{components::organisms::header::Redirect, functions::accounts::edit::EditAccount};
+use crate::{
+    components::organisms::{
+        account_data::AccountData, edit_profile::EditProfile,
+        email_notifications::EmailNotifications, header::Redirect, two_factor::TwoFactor,
+    },
+    functions::accounts::{edit::EditAccount, verify_email::ResendVerification},
+    providers::AuthContext,
+};
 use leptos::*;
 use leptos_router::ActionForm;
 
 #[component]
 pub fn Account(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoView {
     let account_action = create_server_action::<EditAccount>();
+    let resend_action = create_server_action::<ResendVerification>();
+    let auth_context = expect_context::<AuthContext>();
+    let unverified =
+        move || matches!((auth_context.user)(), Some(Ok(Some(account))) if !account.email_verified);
     let pathname =
         move || use_context::<Redirect>().unwrap_or(Redirect(RwSignal::new(String::from("/"))));
     let my_input = NodeRef::<html::Input>::new();
@@ -70,6 +81,33 @@ pub fn Account(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoVi
                     value="Save"
                 />
             </ActionForm>
+            <Show when=unverified>
+                <ActionForm
+                    action=resend_action
+                    class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-slate-800"
+                >
+                    <p class="mb-3">"Your e-mail address is not verified yet."</p>
+                    <Show
+                        when=move || resend_action.value().get().is_some_and(|v| v.is_ok())
+                        fallback=|| {
+                            view! {
+                                <input
+                                    type="submit"
+                                    class="bg-button-dawn dark:bg-button-twilight transform transition-transform duration-300 active:scale-95 hover:bg-pillbug-teal text-white font-bold py-2 px-4 rounded focus:outline-none cursor-pointer"
+                                    value="Send verification e-mail"
+                                />
+                            }
+                        }
+                    >
+
+                        <small>"We sent you a link, please check your inbox."</small>
+                    </Show>
+                </ActionForm>
+            </Show>
+            <EditProfile/>
+            <EmailNotifications/>
+            <TwoFactor/>
+            <AccountData/>
         </div>
     }
 }
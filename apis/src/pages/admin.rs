@@ -1,4 +1,7 @@
-use crate::{components::organisms::chat::ChatWindow, providers::AuthContext};
+use crate::{
+    components::organisms::{chat::ChatWindow, fair_play::FairPlayReports, moderation::Moderation},
+    providers::AuthContext,
+};
 use leptos::*;
 use shared_types::SimpleDestination;
 
@@ -14,7 +17,17 @@ pub fn Admin() -> impl IntoView {
                     false
                 }
             }>
-                <ChatWindow destination=SimpleDestination::Global/>
+                <div class="flex flex-col gap-2 p-2 md:flex-row md:items-start">
+                    <div class="md:w-1/2">
+                        <Moderation/>
+                    </div>
+                    <div class="md:w-1/2">
+                        <ChatWindow destination=SimpleDestination::Global/>
+                    </div>
+                </div>
+                <div class="p-2">
+                    <FairPlayReports/>
+                </div>
             </Show>
         </div>
     }
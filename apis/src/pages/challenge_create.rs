@@ -14,7 +14,8 @@ use hive_lib::{ColorChoice, GameType};
 use leptos::ev::Event;
 use leptos::*;
 use shared_types::{
-    ChallengeDetails, ChallengeVisibility, CorrespondenceMode, GameSpeed, TimeMode,
+    ChallengeDetails, ChallengeVisibility, ClockType, CorrespondenceMode, GameSpeed,
+    RepetitionRule, TimeMode,
 };
 use std::str::FromStr;
 
@@ -28,6 +29,8 @@ pub struct ChallengeParams {
     pub time_mode: RwSignal<TimeMode>,
     pub time_base: StoredValue<Option<i32>>,
     pub time_increment: StoredValue<Option<i32>>,
+    pub clock_type: StoredValue<ClockType>,
+    pub repetition_rule: RwSignal<RepetitionRule>,
     pub band_upper: RwSignal<Option<i32>>,
     pub band_lower: RwSignal<Option<i32>>,
 }
@@ -47,6 +50,8 @@ pub fn ChallengeCreate(
         time_mode: RwSignal::new(TimeMode::RealTime),
         time_base: store_value(None),
         time_increment: store_value(None),
+        clock_type: store_value(ClockType::Fischer),
+        repetition_rule: RwSignal::new(RepetitionRule::Automatic),
         band_upper: RwSignal::new(None),
         band_lower: RwSignal::new(None),
     };
@@ -83,16 +88,23 @@ pub fn ChallengeCreate(
             TimeMode::Untimed => {
                 params.time_base.update_value(|v| *v = None);
                 params.time_increment.update_value(|v| *v = None);
+                params.clock_type.update_value(|v| *v = ClockType::Fischer);
             }
             TimeMode::RealTime => {
+                let clock_type = time_signals.clock_type.get_untracked();
+                let increment = if clock_type.uses_increment() {
+                    time_signals.sec_per_move.get_untracked()
+                } else {
+                    0
+                };
                 params
                     .time_base
                     .update_value(|v| *v = Some(time_signals.total_seconds.get_untracked()));
-                params
-                    .time_increment
-                    .update_value(|v| *v = Some(time_signals.sec_per_move.get_untracked()));
+                params.time_increment.update_value(|v| *v = Some(increment));
+                params.clock_type.update_value(|v| *v = clock_type);
             }
             TimeMode::Correspondence => {
+                params.clock_type.update_value(|v| *v = ClockType::Fischer);
                 match time_signals.corr_mode.get_untracked() {
                     CorrespondenceMode::DaysPerMove => {
                         params.time_increment.update_value(|v| {
@@ -122,12 +134,14 @@ pub fn ChallengeCreate(
                     return None;
                 };
                 // TODO: Make rating update in realtime, currently it becomes stale
-                let rating = account
-                    .user
-                    .rating_for_speed(&GameSpeed::from_base_increment(
+                let rating = account.user.rating_for_speed(
+                    &params.game_type.get_untracked(),
+                    &GameSpeed::from_base_increment(
                         (params.time_base)(),
                         (params.time_increment)(),
-                    ));
+                        (params.clock_type)(),
+                    ),
+                );
                 Some((rating as i32).saturating_add(band_upper))
             } else {
                 None
@@ -140,19 +154,22 @@ pub fn ChallengeCreate(
                 if band_lower < -500 || opponent().is_some() {
                     return None;
                 };
-                let rating = account
-                    .user
-                    .rating_for_speed(&GameSpeed::from_base_increment(
+                let rating = account.user.rating_for_speed(
+                    &params.game_type.get_untracked(),
+                    &GameSpeed::from_base_increment(
                         (params.time_base)(),
                         (params.time_increment)(),
-                    ));
+                        (params.clock_type)(),
+                    ),
+                );
                 Some((rating as i32).saturating_add(band_lower))
             } else {
                 None
             }
         };
         let details = ChallengeDetails {
-            rated: params.rated.get_untracked(),
+            rated: params.rated.get_untracked()
+                && !account().is_some_and(|account| account.user.guest),
             game_type: params.game_type.get_untracked(),
             visibility: if opponent().is_none() {
                 params.visibility.get_untracked()
@@ -164,6 +181,8 @@ pub fn ChallengeCreate(
             time_mode: params.time_mode.get_untracked(),
             time_base: (params.time_base)(),
             time_increment: (params.time_increment)(),
+            clock_type: (params.clock_type)(),
+            repetition_rule: params.repetition_rule.get_untracked(),
             band_upper: upper_rating(),
             band_lower: lower_rating(),
         };
@@ -174,9 +193,14 @@ pub fn ChallengeCreate(
 
     let buttons_style =
         "my-1 p-1 transform transition-transform duration-300 active:scale-95 hover:shadow-xl dark:hover:shadow dark:hover:shadow-gray-500 drop-shadow-lg dark:shadow-gray-600 rounded";
+    let auth_context = expect_context::<AuthContext>();
+    // Guests can only create casual challenges
+    let guest =
+        move || matches!((auth_context.user)(), Some(Ok(Some(account))) if account.user.guest);
     let disable_rated = move || {
         if (params.game_type)() == GameType::Base
             || time_signals.time_control.get() == TimeMode::Untimed
+            || guest()
         {
             return true;
         }
@@ -279,6 +303,32 @@ pub fn ChallengeCreate(
                     Base
                 </button>
             </div>
+            <div class="flex justify-center">
+                <button
+                    class=move || {
+                        format!(
+                            "{buttons_style} {}",
+                            active_color((params.repetition_rule)() == RepetitionRule::Automatic),
+                        )
+                    }
+
+                    on:click=move |_| params.repetition_rule.set(RepetitionRule::Automatic)
+                >
+                    Auto draw
+                </button>
+                <button
+                    class=move || {
+                        format!(
+                            "{buttons_style} {}",
+                            active_color((params.repetition_rule)() == RepetitionRule::Claimed),
+                        )
+                    }
+
+                    on:click=move |_| params.repetition_rule.set(RepetitionRule::Claimed)
+                >
+                    Claim draw
+                </button>
+            </div>
             <div class=move || {
                 format!("{} justify-center", opponent().map_or("flex", |_| "hidden"))
             }>
@@ -11,6 +11,7 @@ pub fn DisplayGames(tab_view: ProfileGamesView) -> impl IntoView {
         ProfileGamesView::Finished => all_games.finished,
         ProfileGamesView::Playing => all_games.playing,
         ProfileGamesView::Unstarted => all_games.unstarted,
+        ProfileGamesView::Statistics => RwSignal::new(Vec::new()),
     };
     let is_active = expect_context::<RwSignal<ProfileGamesView>>();
     let elem = create_node_ref::<html::Div>();
@@ -0,0 +1,50 @@
+use crate::functions::auth::password_reset::RequestPasswordReset;
+use leptos::*;
+use leptos_router::ActionForm;
+
+#[component]
+pub fn ForgotPassword() -> impl IntoView {
+    let request_reset = create_server_action::<RequestPasswordReset>();
+    let my_input = NodeRef::<html::Input>::new();
+    create_effect(move |_| {
+        let _ = my_input.get_untracked().map(|el| el.focus());
+    });
+
+    view! {
+        <div class="w-full max-w-xs mx-auto pt-20">
+            <ActionForm
+                action=request_reset
+                class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-reserve-twilight"
+            >
+                <label class="block mb-2 font-bold" for="email">
+                    E-Mail
+                    <input
+                        ref=my_input
+                        class="px-3 py-2 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                        name="email"
+                        id="email"
+                        type="email"
+                        inputmode="email"
+                        autocomplete="email"
+                        placeholder="E-mail"
+                    />
+                </label>
+                <p class="mb-3">
+                    <Show when=move || request_reset.value().get().is_some_and(|v| v.is_ok())>
+                        <small>
+                            "If an account uses this address, we sent it a link to reset the password."
+                        </small>
+                    </Show>
+                    <Show when=move || request_reset.value().get().is_some_and(|v| v.is_err())>
+                        <small class="text-ladybug-red">"Something went wrong, please try again"</small>
+                    </Show>
+                </p>
+                <input
+                    class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
+                    type="submit"
+                    value="Send reset link"
+                />
+            </ActionForm>
+        </div>
+    }
+}
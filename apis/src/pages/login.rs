@@ -1,4 +1,6 @@
-use crate::{components::organisms::header::Redirect, providers::AuthContext};
+use crate::{
+    components::organisms::header::Redirect, providers::AuthContext, responses::LoginResponse,
+};
 use leptos::*;
 use leptos_router::ActionForm;
 
@@ -11,51 +13,106 @@ pub fn Login(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoView
     create_effect(move |_| {
         let _ = my_input.get_untracked().map(|el| el.focus());
     });
+    let needs_second_factor = move || {
+        matches!(
+            auth_context.login.value().get(),
+            Some(Ok(LoginResponse::SecondFactor))
+        )
+    };
+    let second_factor_error = move || match auth_context.second_factor.value().get() {
+        Some(Err(ServerFnError::ServerError(error))) => error,
+        Some(Err(_)) => String::from("Something went wrong, please try again"),
+        _ => String::new(),
+    };
 
     view! {
         <div class=format!("w-full max-w-xs mx-auto pt-20 {extend_tw_classes}")>
-            <ActionForm
-                action=auth_context.login
-                class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-reserve-twilight"
+            <Show
+                when=needs_second_factor
+                fallback=move || {
+                    view! {
+                        <ActionForm
+                            action=auth_context.login
+                            class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-reserve-twilight"
+                        >
+                            <label class="block mb-2 font-bold" for="email">
+                                E-Mail
+                                <input
+                                    ref=my_input
+                                    class="px-3 py-2 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                                    name="email"
+                                    id="email"
+                                    type="email"
+                                    inputmode="email"
+                                    autocomplete="email"
+                                    placeholder="E-mail"
+                                />
+                            </label>
+                            <label class="block font-bold" for="password">
+                                Password
+                                <input
+                                    class="px-3 py-2 mb-3 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                                    name="password"
+                                    id="password"
+                                    type="password"
+                                    autocomplete="current-password"
+                                    placeholder="********"
+                                />
+                            </label>
+                            <input type="hidden" name="pathname" value=pathname().0/>
+                            <p class="h-5">
+                                <Show when=move || {
+                                    auth_context.login.value().get().is_some_and(|v| v.is_err())
+                                }>
+                                    <small class="text-ladybug-red">"Invalid email or password"</small>
+                                </Show>
+                            </p>
+                            <input
+                                class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
+                                type="submit"
+                                value="Sign In"
+                            />
+                        </ActionForm>
+                    }
+                }
             >
-                <label class="block mb-2 font-bold" for="email">
-                    E-Mail
-                    <input
-                        ref=my_input
-                        class="px-3 py-2 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
-                        name="email"
-                        id="email"
-                        type="email"
-                        inputmode="email"
-                        autocomplete="email"
-                        placeholder="E-mail"
-                    />
-                </label>
-                <label class="block font-bold" for="password">
-                    Password
+
+                <ActionForm
+                    action=auth_context.second_factor
+                    class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-reserve-twilight"
+                >
+                    <label class="block mb-2 font-bold" for="code">
+                        Authentication code
+                        <input
+                            class="px-3 py-2 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                            name="code"
+                            id="code"
+                            type="text"
+                            inputmode="numeric"
+                            autocomplete="one-time-code"
+                            placeholder="123456"
+                        />
+                    </label>
+                    <small>"Lost your device? Enter one of your recovery codes instead."</small>
+                    <input type="hidden" name="pathname" value=pathname().0/>
+                    <p class="h-5">
+                        <small class="text-ladybug-red">{second_factor_error}</small>
+                    </p>
                     <input
-                        class="px-3 py-2 mb-3 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
-                        name="password"
-                        id="password"
-                        type="password"
-                        autocomplete="current-password"
-                        placeholder="********"
+                        class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
+                        type="submit"
+                        value="Verify"
                     />
-                </label>
-                <input type="hidden" name="pathname" value=pathname().0/>
-                <p class="h-5">
-                    <Show when=move || {
-                        auth_context.login.value().get().is_some_and(|v| v.is_err())
-                    }>
-                        <small class="text-ladybug-red">"Invalid email or password"</small>
-                    </Show>
-                </p>
-                <input
-                    class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
-                    type="submit"
-                    value="Sign In"
-                />
-            </ActionForm>
+                </ActionForm>
+            </Show>
+            <p class="text-xs text-center text-gray-500">
+                <a
+                    class="text-blue-500 transition-transform duration-300 transform hover:underline"
+                    href="/forgot_password"
+                >
+                    "Forgot your password?"
+                </a>
+            </p>
             <p class="text-xs text-center text-gray-500">
                 "Don't have an account?"
                 <a
@@ -65,6 +122,17 @@ pub fn Login(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoView
                     Sign Up
                 </a>
             </p>
+            <ActionForm action=auth_context.guest class="mt-4 text-center">
+                <input type="hidden" name="pathname" value=pathname().0/>
+                <input
+                    class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
+                    type="submit"
+                    value="Play as guest"
+                />
+                <p class="text-xs text-gray-500">
+                    "Guests play casual games only. Sign up later to keep them."
+                </p>
+            </ActionForm>
 
         </div>
     }
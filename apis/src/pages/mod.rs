@@ -7,8 +7,10 @@ pub mod config;
 pub mod display_games;
 pub mod donate;
 pub mod faq;
+pub mod forgot_password;
 pub mod home;
 pub mod login;
+pub mod password_reset;
 pub mod play;
 pub mod profile_view;
 pub mod puzzles;
@@ -21,3 +23,4 @@ pub mod tournament;
 pub mod tournament_create;
 pub mod tournaments;
 pub mod tutorial;
+pub mod verify_email;
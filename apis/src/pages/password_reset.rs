@@ -0,0 +1,57 @@
+use crate::functions::auth::password_reset::ResetPassword;
+use leptos::*;
+use leptos_router::*;
+
+#[component]
+pub fn PasswordReset() -> impl IntoView {
+    let reset_password = create_server_action::<ResetPassword>();
+    let query = use_query_map();
+    let token =
+        move || query.with_untracked(|query| query.get("token").cloned().unwrap_or_default());
+    let error = move || match reset_password.value().get() {
+        Some(Err(ServerFnError::ServerError(error))) => error,
+        Some(Err(_)) => String::from("Something went wrong, please try again"),
+        _ => String::new(),
+    };
+
+    view! {
+        <div class="w-full max-w-xs mx-auto pt-20">
+            <ActionForm
+                action=reset_password
+                class="px-8 pt-6 pb-8 mb-4 rounded shadow-md bg-stone-300 dark:bg-reserve-twilight"
+            >
+                <label class="block font-bold" for="password">
+                    New Password
+                    <input
+                        class="px-3 py-2 mb-3 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                        name="password"
+                        id="password"
+                        type="password"
+                        autocomplete="new-password"
+                        placeholder="New password"
+                    />
+                </label>
+                <label class="block font-bold" for="password_confirmation">
+                    Confirm Password
+                    <input
+                        class="px-3 py-2 mb-3 w-full leading-tight rounded border shadow appearance-none focus:outline-none"
+                        name="password_confirmation"
+                        id="password_confirmation"
+                        type="password"
+                        autocomplete="new-password"
+                        placeholder="New password (again)"
+                    />
+                </label>
+                <input type="hidden" name="token" value=token/>
+                <p class="h-5">
+                    <small class="text-ladybug-red">{error}</small>
+                </p>
+                <input
+                    class="px-4 py-2 font-bold text-white rounded transition-transform duration-300 transform cursor-pointer bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 focus:outline-none"
+                    type="submit"
+                    value="Change password"
+                />
+            </ActionForm>
+        </div>
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    components::organisms::display_profile::DisplayProfile,
+    components::organisms::{display_profile::DisplayProfile, rating_chart::RatingChart},
     functions::users::get::{
         get_finished_games_in_batches, get_ongoing_games, get_user_by_username,
     },
@@ -21,6 +21,7 @@ pub enum ProfileGamesView {
     Unstarted,
     Playing,
     Finished,
+    Statistics,
 }
 
 #[derive(Debug, Clone)]
@@ -124,8 +125,10 @@ pub fn ProfileView(children: ChildrenFn) -> impl IntoView {
                         .map(|data| match data {
                             Err(_) => view! { <pre>"Page not found"</pre> }.into_view(),
                             Ok(user) => {
+                                let user = store_value(user);
                                 view! {
-                                    <DisplayProfile user=store_value(user)/>
+                                    <DisplayProfile user/>
+                                    <RatingChart user/>
                                     <div class="flex gap-1 ml-3">
                                         <Show when=move || !unstarted().is_empty()>
                                             <A
@@ -154,6 +157,12 @@ pub fn ProfileView(children: ChildrenFn) -> impl IntoView {
                                                 "Finished Games "
                                             </A>
                                         </Show>
+                                        <A
+                                            href="statistics"
+                                            class=move || active(ProfileGamesView::Statistics)
+                                        >
+                                            "Statistics"
+                                        </A>
                                     </div>
                                     {stored_children()()}
                                     <Show when=finished_games.loading()>
@@ -14,6 +14,8 @@ use web_sys::Event;
 
 const BANNED_USERNAMES: [&str; 3] = ["black", "white", "admin"];
 const VALID_USERNAME_CHARS: &str = "-_";
+// Generated names of guests and deleted accounts
+const RESERVED_PREFIXES: [&str; 2] = ["guest-", "deleted-"];
 
 lazy_static! {
     static ref EMAIL_RE: Regex =
@@ -69,6 +71,9 @@ pub fn Register(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoV
     let username_exists = move || {
         let username = username();
         BANNED_USERNAMES.contains(&username.to_lowercase().as_str())
+            || RESERVED_PREFIXES
+                .iter()
+                .any(|prefix| username.to_lowercase().starts_with(prefix))
             || username_taken.value().get().is_some_and(|v| {
                 if let Ok(value) = v {
                     username.len() > 1 && value
@@ -144,7 +149,7 @@ pub fn Register(#[prop(optional)] extend_tw_classes: &'static str) -> impl IntoV
                         <small class="text-ladybug-red">"Invalid email"</small>
                     </Show>
                     <br/>
-                    <small>Email notifications and password reset once we implement them</small>
+                    <small>"Used to verify your account and to reset your password"</small>
                 </label>
                 <label>
                     <p class="font-bold">Password</p>
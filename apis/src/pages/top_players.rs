@@ -1,19 +1,62 @@
+use hive_lib::GameType;
 use leptos::*;
-use shared_types::GameSpeed;
+use shared_types::{rated_game_types, GameSpeed, LeaderboardKind, PrettyString};
 
 use crate::components::{molecules::banner::Banner, organisms::leaderboard::Leaderboard};
 
+const BUTTON_STYLE: &str = "hover:bg-pillbug-teal transform transition-transform duration-300 active:scale-95 text-white font-bold py-2 px-4 m-1 rounded";
+
 #[component]
 pub fn TopPlayers() -> impl IntoView {
-    let leaderboards = GameSpeed::all_rated_games()
+    let kind = RwSignal::new(LeaderboardKind::TopRated);
+    let game_type = RwSignal::new(GameType::MLP);
+    let button_class = move |active: bool| {
+        if active {
+            format!("{BUTTON_STYLE} bg-pillbug-teal")
+        } else {
+            format!("{BUTTON_STYLE} bg-button-dawn dark:bg-button-twilight")
+        }
+    };
+    let kind_buttons = LeaderboardKind::all()
+        .into_iter()
+        .map(|board| {
+            view! {
+                <button
+                    class=move || button_class(kind() == board)
+                    on:click=move |_| kind.set(board)
+                >
+                    {board.pretty_string()}
+                </button>
+            }
+        })
+        .collect_view();
+    let game_type_buttons = rated_game_types()
         .into_iter()
-        .map(|speed| {
-            view! { <Leaderboard speed=speed/> }
+        .map(|rated| {
+            view! {
+                <button
+                    class=move || button_class(game_type() == rated)
+                    on:click=move |_| game_type.set(rated)
+                >
+                    {rated.to_string()}
+                </button>
+            }
         })
         .collect_view();
+    let leaderboards = move || {
+        let (kind, game_type) = (kind(), game_type());
+        GameSpeed::all_rated_games()
+            .into_iter()
+            .map(|speed| {
+                view! { <Leaderboard game_type=game_type speed=speed kind=kind/> }
+            })
+            .collect_view()
+    };
     view! {
         <div class="flex flex-col items-center pt-20">
-            <Banner title="Top Rated Players" extend_tw_classes="w-10/12"/>
+            <Banner title="Top Players" extend_tw_classes="w-10/12"/>
+            <div class="flex flex-wrap justify-center">{kind_buttons}</div>
+            <div class="flex flex-wrap justify-center">{game_type_buttons}</div>
             <div class="flex flex-col flex-wrap gap-1 justify-center items-center w-full md:flex-row">
                 {leaderboards}
             </div>
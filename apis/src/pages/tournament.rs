@@ -1,5 +1,5 @@
 use crate::common::{TournamentAction, UserAction};
-use crate::components::molecules::score_row::ScoreRow;
+use crate::components::molecules::{match_row::MatchRow, score_row::ScoreRow};
 use crate::components::{
     molecules::{
         game_previews::GamePreviews, invite_user::InviteUser, time_row::TimeRow, user_row::UserRow,
@@ -11,10 +11,11 @@ use crate::providers::{
     ApiRequests, AuthContext,
 };
 use chrono::Local;
+use hive_lib::GameType;
 use leptos::*;
 use leptos_router::use_navigate;
 use shared_types::PrettyString;
-use shared_types::{GameSpeed, TimeInfo, TournamentStatus};
+use shared_types::{GameSpeed, ScoringMode, TimeInfo, TournamentStatus};
 use uuid::Uuid;
 
 const BUTTON_STYLE: &str = "flex gap-1 justify-center items-center px-4 py-2 font-bold text-white rounded bg-button-dawn dark:bg-button-twilight hover:bg-pillbug-teal active:scale-95 disabled:opacity-25 disabled:cursor-not-allowed disabled:hover:bg-transparent";
@@ -92,7 +93,7 @@ pub fn Tournament() -> impl IntoView {
 
     let display_tournament = move || {
         current_tournament().and_then(|tournament| {
-            let time_info = TimeInfo{mode:tournament.time_mode.clone() ,base: tournament.time_base, increment: tournament.time_increment};
+            let time_info = TimeInfo{mode:tournament.time_mode.clone() ,base: tournament.time_base, increment: tournament.time_increment, clock: tournament.clock_type};
             let tournament = store_value(tournament);
             let start_disabled = move || {let tournament =tournament(); tournament.min_seats > tournament.players.len() as i32} ;
             let join_disabled = move || {
@@ -108,8 +109,8 @@ pub fn Tournament() -> impl IntoView {
                         return true;
                     }
                     let game_speed =
-                    GameSpeed::from_base_increment(tournament.time_base, tournament.time_increment);
-                    let rating = user.rating_for_speed(&game_speed) as i32;
+                    GameSpeed::from_base_increment(tournament.time_base, tournament.time_increment, tournament.clock_type);
+                    let rating = user.rating_for_speed(&GameType::MLP, &game_speed) as i32;
                     match (tournament.band_lower, tournament.band_upper) {
                         (None, None) => false,
                         (None, Some(upper)) => rating >= upper,
@@ -216,7 +217,7 @@ pub fn Tournament() -> impl IntoView {
                                 let:user
                             >
                                 <div>
-                                    <UserRow actions=vec![] user=store_value(user)/>
+                                    <UserRow actions=vec![] user=store_value(user) with_avatar=true/>
                                 </div>
                             </For>
                         </div>
@@ -235,7 +236,11 @@ pub fn Tournament() -> impl IntoView {
                                             key=|(id, _)| (*id)
                                             let:user
                                         >
-                                            <UserRow actions=user_kick() user=store_value(user.1)/>
+                                            <UserRow
+                                                actions=user_kick()
+                                                user=store_value(user.1)
+                                                with_avatar=true
+                                            />
                                         </For>
                                     </Show>
                                 </div>
@@ -247,7 +252,11 @@ pub fn Tournament() -> impl IntoView {
                                             key=|users| (users.uid)
                                             let:user
                                         >
-                                            <UserRow actions=user_uninvite() user=store_value(user)/>
+                                            <UserRow
+                                                actions=user_uninvite()
+                                                user=store_value(user)
+                                                with_avatar=true
+                                            />
                                         </For>
                                     </Show>
                                     <Show when=user_is_organizer>
@@ -327,6 +336,31 @@ pub fn Tournament() -> impl IntoView {
                                 }
 
                             </For>
+                            <Show when=move || tournament().scoring == ScoringMode::Match>
+                                <p class="font-bold">Matches</p>
+                                {move || {
+                                    let tournament = tournament();
+                                    tournament
+                                        .standings
+                                        .matches()
+                                        .into_iter()
+                                        .filter_map(|score| {
+                                            let one = tournament.players.get(&score.one)?.clone();
+                                            let two = tournament.players.get(&score.two)?.clone();
+                                            Some(
+                                                view! {
+                                                    <MatchRow
+                                                        one=store_value(one)
+                                                        two=store_value(two)
+                                                        score=score
+                                                    />
+                                                },
+                                            )
+                                        })
+                                        .collect_view()
+                                }}
+
+                            </Show>
                             Tournament Games:
                             <div class="flex flex-wrap justify-center items-center">
                                 <GamePreviews games=Callback::new(move |_| (tournament().games))/>
@@ -11,8 +11,8 @@ use leptos::*;
 use leptos_router::use_navigate;
 use shared_types::PrettyString;
 use shared_types::{
-    CorrespondenceMode, ScoringMode, StartMode, Tiebreaker, TimeMode, TournamentDetails,
-    TournamentMode,
+    ClockType, CorrespondenceMode, RepetitionRule, ScoringMode, StartMode, Tiebreaker, TimeMode,
+    TournamentDetails, TournamentMode,
 };
 use uuid::Uuid;
 
@@ -23,6 +23,8 @@ pub struct TournamentSignals {
     pub name: RwSignal<String>,
     pub description: RwSignal<String>,
     pub scoring: RwSignal<ScoringMode>,
+    pub armageddon: RwSignal<bool>,
+    pub repetition_rule: RwSignal<RepetitionRule>,
     pub tiebreakers: RwSignal<Vec<Option<Tiebreaker>>>,
     pub seats: RwSignal<i32>,
     pub min_seats: RwSignal<i32>,
@@ -46,6 +48,8 @@ impl TournamentSignals {
             name: RwSignal::new(String::new()),
             description: RwSignal::new(String::new()),
             scoring: RwSignal::new(ScoringMode::Game),
+            armageddon: RwSignal::new(false),
+            repetition_rule: RwSignal::new(RepetitionRule::Automatic),
             tiebreakers: RwSignal::new(vec![
                 Some(Tiebreaker::RawPoints),
                 Some(Tiebreaker::HeadToHead),
@@ -116,12 +120,17 @@ pub fn TournamentCreate() -> impl IntoView {
                 tournament.time_increment.update_value(|v| *v = None);
             }
             TimeMode::RealTime => {
+                let increment = if time_signals.clock_type.get_untracked().uses_increment() {
+                    time_signals.sec_per_move.get_untracked()
+                } else {
+                    0
+                };
                 tournament
                     .time_base
                     .update_value(|v| *v = Some(time_signals.total_seconds.get_untracked()));
                 tournament
                     .time_increment
-                    .update_value(|v| *v = Some(time_signals.sec_per_move.get_untracked()));
+                    .update_value(|v| *v = Some(increment));
             }
             TimeMode::Correspondence => {
                 fixed_round_duration.set(false);
@@ -159,6 +168,8 @@ pub fn TournamentCreate() -> impl IntoView {
             name: tournament.name.get_untracked(),
             description: tournament.description.get_untracked(),
             scoring: tournament.scoring.get_untracked(),
+            armageddon: tournament.armageddon.get_untracked()
+                && tournament.scoring.get_untracked() == ScoringMode::Match,
             tiebreakers: tournament.tiebreakers.get_untracked(),
             invitees: vec![],
             seats: tournament.seats.get_untracked(),
@@ -169,6 +180,12 @@ pub fn TournamentCreate() -> impl IntoView {
             time_mode: tournament.time_mode.get_untracked(),
             time_base: tournament.time_base.get_value(),
             time_increment: tournament.time_increment.get_value(),
+            clock_type: if tournament.time_mode.get_untracked() == TimeMode::RealTime {
+                time_signals.clock_type.get_untracked()
+            } else {
+                ClockType::Fischer
+            },
+            repetition_rule: tournament.repetition_rule.get_untracked(),
             band_upper: tournament.band_upper.get_untracked(),
             band_lower: tournament.band_lower.get_untracked(),
             series: tournament.series.get_untracked(),
@@ -299,13 +316,51 @@ pub fn TournamentCreate() -> impl IntoView {
                             on:change=update_from_input_parsed(tournament.scoring)
                         >
                             <SelectOption
-                                value=tournament.mode
+                                value=tournament.scoring
                                 is="Game"
                                 text=ScoringMode::Game.pretty_string()
                             />
+                            <SelectOption
+                                value=tournament.scoring
+                                is="Match"
+                                text=ScoringMode::Match.pretty_string()
+                            />
 
                         </select>
                     </div>
+                    <div>
+                        Repetition:
+                        <select
+                            class="bg-odd-light dark:bg-gray-700"
+                            name="Repetition Rule"
+                            on:change=update_from_input_parsed(tournament.repetition_rule)
+                        >
+                            <SelectOption
+                                value=tournament.repetition_rule
+                                is="Automatic"
+                                text=RepetitionRule::Automatic.pretty_string()
+                            />
+                            <SelectOption
+                                value=tournament.repetition_rule
+                                is="Claimed"
+                                text=RepetitionRule::Claimed.pretty_string()
+                            />
+
+                        </select>
+                    </div>
+                    <Show when=move || tournament.scoring.get() == ScoringMode::Match>
+                        <div class="flex">
+                            <input
+                                on:change=move |_| tournament.armageddon.update(|b| *b = !*b)
+                                type="checkbox"
+                                class="w-4 h-4 text-blue-600 bg-gray-100 rounded border-gray-300 focus:ring-blue-500 dark:focus:ring-blue-600 dark:ring-offset-gray-800 focus:ring-2 dark:bg-gray-700 dark:border-gray-600"
+                                prop:checked=tournament.armageddon
+                            />
+                            <label class="ml-2 text-sm font-medium text-gray-900 dark:text-gray-300">
+                                Armageddon tiebreak for drawn matches
+                            </label>
+                        </div>
+                    </Show>
                     <div class="flex">
                         <input
                             on:change=move |_| tournament.invite_only.update(|b| *b = !*b)
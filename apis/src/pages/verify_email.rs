@@ -0,0 +1,32 @@
+use crate::functions::accounts::verify_email::verify_email;
+use leptos::*;
+use leptos_router::*;
+
+#[component]
+pub fn VerifyEmail() -> impl IntoView {
+    let query = use_query_map();
+    let token =
+        move || query.with_untracked(|query| query.get("token").cloned().unwrap_or_default());
+    let verification = Resource::once(move || verify_email(token()));
+
+    view! {
+        <div class="flex flex-col items-center pt-20 mx-auto">
+            <Transition>
+                {move || {
+                    verification()
+                        .map(|result| match result {
+                            Ok(()) => view! { <p>"Your e-mail address is verified, thank you!"</p> },
+                            Err(_) => {
+                                view! {
+                                    <p>
+                                        "This link is invalid or has expired, you can request a new one on your account page."
+                                    </p>
+                                }
+                            }
+                        })
+                }}
+
+            </Transition>
+        </div>
+    }
+}
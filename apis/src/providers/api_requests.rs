@@ -1,14 +1,16 @@
 use super::challenges::ChallengeStateSignal;
 use super::games::GamesSignal;
 use super::AuthContext;
-use crate::common::{ChallengeAction, TournamentAction};
-use crate::common::{ClientRequest, GameAction};
+use crate::common::{ChallengeAction, QuickPairAction, TournamentAction};
+use crate::common::{ClientRequest, GameAction, RematchAction};
 use crate::providers::websocket::WebsocketContext;
 use crate::responses::create_challenge_handler;
 use chrono::Utc;
 use hive_lib::{GameControl, Turn};
 use leptos::*;
-use shared_types::{ChallengeId, ChatMessageContainer, GameId, TournamentGameResult, TournamentId};
+use shared_types::{
+    ChallengeId, ChatMessageContainer, GameId, QuickPairDetails, TournamentGameResult, TournamentId,
+};
 #[derive(Clone)]
 pub struct ApiRequests {
     websocket: WebsocketContext,
@@ -53,6 +55,33 @@ impl ApiRequests {
             .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
     }
 
+    pub fn rematch(&self, game_id: GameId, action: RematchAction) {
+        let msg = ClientRequest::Game {
+            game_id,
+            action: GameAction::Rematch(action),
+        };
+        self.websocket
+            .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
+    }
+
+    pub fn premove(&self, game_id: GameId, turn: Turn) {
+        let msg = ClientRequest::Game {
+            game_id,
+            action: GameAction::Premove(turn),
+        };
+        self.websocket
+            .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
+    }
+
+    pub fn cancel_premove(&self, game_id: GameId) {
+        let msg = ClientRequest::Game {
+            game_id,
+            action: GameAction::CancelPremove,
+        };
+        self.websocket
+            .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
+    }
+
     pub fn tournament_game_start(&self, game_id: GameId) {
         let msg = ClientRequest::Game {
             game_id,
@@ -152,6 +181,18 @@ impl ApiRequests {
             .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
     }
 
+    pub fn quick_pair_join(&self, details: QuickPairDetails) {
+        let msg = ClientRequest::QuickPair(QuickPairAction::Join(details));
+        self.websocket
+            .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
+    }
+
+    pub fn quick_pair_leave(&self) {
+        let msg = ClientRequest::QuickPair(QuickPairAction::Leave);
+        self.websocket
+            .send(&serde_json::to_string(&msg).expect("Serde_json::to_string failed"));
+    }
+
     pub fn search_user(&self, pattern: String) {
         if !pattern.is_empty() {
             let msg = ClientRequest::UserSearch(pattern);
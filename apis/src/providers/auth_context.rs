@@ -1,29 +1,43 @@
 use crate::functions::accounts::get::get_account;
-use crate::functions::auth::{login::Login, logout::Logout, register::Register};
+use crate::functions::auth::{
+    guest::PlayAsGuest,
+    login::{Login, LoginSecondFactor},
+    logout::Logout,
+    register::Register,
+};
 use crate::providers::websocket::WebsocketContext;
-use crate::responses::AccountResponse;
+use crate::responses::{AccountResponse, LoginResponse};
 use leptos::*;
 
 #[derive(Clone)]
 pub struct AuthContext {
-    pub login: Action<Login, Result<AccountResponse, ServerFnError>>,
+    pub login: Action<Login, Result<LoginResponse, ServerFnError>>,
+    pub second_factor: Action<LoginSecondFactor, Result<AccountResponse, ServerFnError>>,
     pub logout: Action<Logout, Result<(), ServerFnError>>,
     pub register: Action<Register, Result<(), ServerFnError>>,
-    pub user: Resource<(usize, usize, usize), Result<Option<AccountResponse>, ServerFnError>>,
+    pub guest: Action<PlayAsGuest, Result<(), ServerFnError>>,
+    pub user: Resource<
+        (usize, usize, usize, usize, usize),
+        Result<Option<AccountResponse>, ServerFnError>,
+    >,
 }
 
 /// Get the current user and place it in Context
 pub fn provide_auth() {
     let login = create_server_action::<Login>();
+    let second_factor = create_server_action::<LoginSecondFactor>();
     let logout = create_server_action::<Logout>();
     let register = create_server_action::<Register>();
+    let guest = create_server_action::<PlayAsGuest>();
 
     let user = create_local_resource(
         move || {
             (
                 login.version().get(),
+                second_factor.version().get(),
                 logout.version().get(),
                 register.version().get(),
+                guest.version().get(),
             )
         },
         move |_| get_account(),
@@ -48,7 +62,9 @@ pub fn provide_auth() {
     provide_context(AuthContext {
         user,
         login,
+        second_factor,
         logout,
         register,
+        guest,
     })
 }
@@ -6,7 +6,7 @@ use crate::responses::GameResponse;
 use hive_lib::{Color, GameControl, GameStatus, GameType, Piece, Position, State, Turn};
 use leptos::logging::log;
 use leptos::*;
-use shared_types::{GameId, GameSpeed};
+use shared_types::{GameId, GameSpeed, TimeMode};
 use uuid::Uuid;
 
 use super::auth_context::AuthContext;
@@ -42,6 +42,8 @@ impl GameStateSignal {
             s.history_turn = None;
             s.view = View::Game;
             s.game_control_pending = None;
+            s.rematch_offer = None;
+            s.premove = None;
         })
     }
 
@@ -100,6 +102,25 @@ impl GameStateSignal {
         })
     }
 
+    pub fn set_rematch_offer(&self, offered_by: Option<Uuid>) {
+        self.signal.update(|s| {
+            s.rematch_offer = offered_by;
+        })
+    }
+
+    pub fn cancel_premove(&self) {
+        self.signal.update(|s| {
+            if let (Some(_), Some(game_id)) = (s.premove.take(), s.game_id.clone()) {
+                ApiRequests::new().cancel_premove(game_id);
+            }
+        })
+    }
+
+    // The server plays or drops the premove once the opponent's turn lands
+    pub fn clear_premove(&self) {
+        self.signal.update(|s| s.premove = None)
+    }
+
     pub fn send_game_control(&self, game_control: GameControl, user: Uuid) {
         self.signal
             .get_untracked()
@@ -146,6 +167,10 @@ impl GameStateSignal {
         self.signal.get_untracked().is_move_allowed()
     }
 
+    pub fn is_premove_allowed(&self) -> bool {
+        self.signal.get_untracked().is_premove_allowed()
+    }
+
     pub fn show_moves(&mut self, piece: Piece, position: Position) {
         self.signal.update(|s| s.show_moves(piece, position))
     }
@@ -252,6 +277,10 @@ pub struct GameState {
     pub view: View,
     // Unanswered game_control
     pub game_control_pending: Option<GameControl>,
+    // Who offered a rematch once the game is over
+    pub rematch_offer: Option<Uuid>,
+    // Move queued on the opponent's turn in bullet and blitz
+    pub premove: Option<Turn>,
     pub game_response: Option<GameResponse>,
 }
 
@@ -274,6 +303,8 @@ impl GameState {
             history_turn: None,
             view: View::Game,
             game_control_pending: None,
+            rematch_offer: None,
+            premove: None,
             game_response: None,
         }
     }
@@ -289,6 +320,8 @@ impl GameState {
             history_turn: None,
             view: View::Game,
             game_control_pending: None,
+            rematch_offer: None,
+            premove: None,
             game_response: None,
         }
     }
@@ -352,12 +385,48 @@ impl GameState {
         })
     }
 
+    pub fn is_premove_allowed(&self) -> bool {
+        let auth_context = expect_context::<AuthContext>();
+
+        let user = move || match (auth_context.user)() {
+            Some(Ok(Some(user))) => Some(user),
+            _ => None,
+        };
+        if matches!(self.state.game_status, GameStatus::Finished(_)) {
+            return false;
+        }
+        let fast_game = self.game_response.as_ref().is_some_and(|gr| {
+            gr.time_mode == TimeMode::RealTime && gr.speed.allows_premoves() && !gr.finished
+        });
+        fast_game
+            && user().is_some_and(|user| {
+                self.user_color(user.id)
+                    .is_some_and(|color| color != self.state.turn_color)
+            })
+    }
+
+    // Premoves get picked from the user's pieces while the opponent is to move
+    fn selecting_color(&self) -> Color {
+        if self.is_premove_allowed() {
+            self.state.turn_color.opposite_color()
+        } else {
+            self.state.turn_color
+        }
+    }
+
     pub fn move_active(&mut self) {
         //log!("Moved active!");
         if let (Some(active), Some(position)) =
             (self.move_info.active, self.move_info.target_position)
         {
-            if let Err(e) = self.state.play_turn_from_position(active, position) {
+            if self.is_premove_allowed() {
+                if let Some(ref game_id) = self.game_id {
+                    let turn = Turn::Move(active, position);
+                    ApiRequests::new().premove(game_id.to_owned(), turn.clone());
+                    self.premove = Some(turn);
+                }
+                self.move_info.reset();
+            } else if let Err(e) = self.state.play_turn_from_position(active, position) {
                 log!("Could not play turn: {} {} {}", active, position, e);
             } else if let Some(ref game_id) = self.game_id {
                 let turn = Turn::Move(active, position);
@@ -382,7 +451,7 @@ impl GameState {
         }
         self.move_info.reset();
         self.move_info.current_position = Some(position);
-        let moves = self.state.board.moves(self.state.turn_color);
+        let moves = self.state.board.moves(self.selecting_color());
         if let Some(positions) = moves.get(&(piece, position)) {
             positions.clone_into(&mut self.move_info.target_positions);
             self.move_info.active = Some(piece);
@@ -391,15 +460,13 @@ impl GameState {
 
     pub fn show_spawns(&mut self, piece: Piece, position: Position) {
         self.move_info.reset();
+        let color = self.selecting_color();
         self.move_info.target_positions = self
             .state
             .board
-            .spawnable_positions(self.state.turn_color)
+            .spawnable_positions(color)
             .collect::<Vec<Position>>();
-        let reserve = self
-            .state
-            .board
-            .reserve(self.state.turn_color, self.state.game_type);
+        let reserve = self.state.board.reserve(color, self.state.game_type);
         if let Some(pieces) = reserve.get(&piece.bug()) {
             if let Some(piece) = pieces.first() {
                 if let Ok(piece) = Piece::from_str(piece) {
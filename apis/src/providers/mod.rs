@@ -11,6 +11,7 @@ pub mod navigation_controller;
 mod notifications;
 pub mod online_users;
 mod ping;
+pub mod quick_pair;
 pub mod refocus;
 pub mod timer;
 pub mod tournament_ready;
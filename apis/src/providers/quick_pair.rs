@@ -0,0 +1,26 @@
+use leptos::*;
+use shared_types::QuickPairDetails;
+
+#[derive(Clone, Debug, Copy)]
+pub struct QuickPairSignal {
+    // What the user is currently searching for in the quick pairing pool
+    pub signal: RwSignal<Option<QuickPairDetails>>,
+}
+
+impl Default for QuickPairSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuickPairSignal {
+    pub fn new() -> Self {
+        Self {
+            signal: RwSignal::new(None),
+        }
+    }
+}
+
+pub fn provide_quick_pair() {
+    provide_context(QuickPairSignal::new())
+}
@@ -2,6 +2,7 @@ use crate::responses::{GameResponse, HeartbeatResponse};
 use chrono::DateTime;
 use chrono::Utc;
 use leptos::*;
+use shared_types::ClockType;
 use shared_types::GameId;
 use shared_types::TimeMode;
 use std::time::Duration;
@@ -44,6 +45,7 @@ impl TimerSignal {
                 .time_increment
                 .map(|inc| Duration::from_secs(inc as u64));
             timer.time_mode = game.time_mode.clone();
+            timer.clock_type = game.clock_type;
             timer.last_interaction = game.last_interaction;
         });
     }
@@ -58,6 +60,7 @@ pub struct Timer {
     pub black_time_left: Option<Duration>,
     pub time_increment: Option<Duration>,
     pub time_mode: TimeMode,
+    pub clock_type: ClockType,
     pub last_interaction: Option<DateTime<Utc>>,
 }
 
@@ -71,9 +74,18 @@ impl Timer {
             black_time_left: None,
             time_increment: None,
             time_mode: TimeMode::Untimed,
+            clock_type: ClockType::default(),
             last_interaction: None,
         }
     }
+
+    // How long a clock waits before it starts running after the turn changed
+    pub fn delay(&self) -> Duration {
+        match (&self.time_mode, self.clock_type) {
+            (TimeMode::RealTime, ClockType::Delay) => self.time_increment.unwrap_or_default(),
+            _ => Duration::ZERO,
+        }
+    }
 }
 
 impl Default for Timer {
@@ -3,7 +3,9 @@ use crate::{
     providers::{auth_context::AuthContext, challenges::ChallengeStateSignal, NotificationContext},
     responses::ChallengeResponse,
 };
+use hive_lib::GameType;
 use leptos::*;
+use std::str::FromStr;
 
 fn filter_challenges(challenges: &mut Vec<ChallengeResponse>) {
     let auth_context = expect_context::<AuthContext>();
@@ -16,13 +18,14 @@ fn filter_challenges(challenges: &mut Vec<ChallengeResponse>) {
             if challenge.challenger.uid == account.id {
                 return true;
             }
+            let game_type = GameType::from_str(&challenge.game_type).unwrap_or(GameType::MLP);
             if let Some(upper) = challenge.band_upper {
-                if account.user.rating_for_speed(&challenge.speed) > upper as u64 {
+                if account.user.rating_for_speed(&game_type, &challenge.speed) > upper as u64 {
                     return false;
                 }
             }
             if let Some(lower) = challenge.band_lower {
-                if account.user.rating_for_speed(&challenge.speed) < lower as u64 {
+                if account.user.rating_for_speed(&game_type, &challenge.speed) < lower as u64 {
                     return false;
                 }
             }
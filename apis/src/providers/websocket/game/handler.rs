@@ -1,7 +1,8 @@
 use crate::{
     common::GameUpdate,
     providers::websocket::game::{
-        reaction::handler::handle_reaction, tv::handler::handle_tv, urgent::handler::handle_urgent,
+        premove::handler::handle_premove_cleared, reaction::handler::handle_reaction,
+        rematch::handler::handle_rematch, tv::handler::handle_tv, urgent::handler::handle_urgent,
     },
 };
 
@@ -13,5 +14,7 @@ pub fn handle_game(game_update: GameUpdate) {
         GameUpdate::Tv(game) => handle_tv(game),
         GameUpdate::Urgent(games) => handle_urgent(games),
         GameUpdate::Heartbeat(heartbeat) => handle_heartbeat(heartbeat),
+        GameUpdate::Rematch(update) => handle_rematch(update),
+        GameUpdate::PremoveCleared(game_id) => handle_premove_cleared(game_id),
     }
 }
@@ -1,5 +1,7 @@
 pub mod handler;
+pub mod premove;
 pub mod reaction;
+pub mod rematch;
 pub mod tv;
 pub mod urgent;
 pub mod heartbeat;
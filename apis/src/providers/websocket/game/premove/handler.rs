@@ -0,0 +1,10 @@
+use crate::providers::game_state::GameStateSignal;
+use leptos::*;
+use shared_types::GameId;
+
+pub fn handle_premove_cleared(game_id: GameId) {
+    let game_state = expect_context::<GameStateSignal>();
+    if game_state.signal.get_untracked().game_id == Some(game_id) {
+        game_state.clear_premove();
+    }
+}
@@ -35,7 +35,7 @@ pub fn handle_control(game_control: GameControl, gar: GameActionResponse) {
                 }
             }
         }
-        GameControl::DrawAccept(_) => {
+        GameControl::DrawAccept(_) | GameControl::ClaimRepetition(_) => {
             games.own_games_remove(&gar.game.game_id);
             if let Some(game_id) = navigation_controller.game_signal.get_untracked().game_id {
                 if gar.game.game_id == game_id {
@@ -59,6 +59,17 @@ pub fn handle_control(game_control: GameControl, gar: GameActionResponse) {
                 }
             }
         }
+        GameControl::ClaimVictory(color) => {
+            games.own_games_remove(&gar.game.game_id);
+            if let Some(game_id) = navigation_controller.game_signal.get_untracked().game_id {
+                if gar.game.game_id == game_id {
+                    game_state.set_game_status(GameStatus::Finished(GameResult::Winner(color)));
+                    game_state.set_game_response(gar.game.clone());
+                    let timer = expect_context::<TimerSignal>();
+                    timer.update_from(&gar.game);
+                }
+            }
+        }
         GameControl::TakebackAccept(_) => {
             games.own_games_add(gar.game.to_owned());
             if let Some(game_id) = navigation_controller.game_signal.get_untracked().game_id {
@@ -57,7 +57,7 @@ pub fn reset_game_state_for_takeback(game: &GameResponse) {
     let mut history = History::new();
     game.history.clone_into(&mut history.moves);
     game.game_type.clone_into(&mut history.game_type);
-    if let Ok(state) = State::new_from_history(&history) {
+    if let Ok(state) = State::new_from_history_with(&history, game.repetition_rule.is_automatic()) {
         game_state.set_state(state, game.black_player.uid, game.white_player.uid);
     };
 }
@@ -76,7 +76,9 @@ pub fn reset_game_state(game: &GameResponse) {
         if let GameStatus::Finished(result) = &game.game_status {
             result.clone_into(&mut history.result);
         }
-        if let Ok(state) = State::new_from_history(&history) {
+        if let Ok(state) =
+            State::new_from_history_with(&history, game.repetition_rule.is_automatic())
+        {
             game_state.set_state(state, game.black_player.uid, game.white_player.uid);
         }
     });
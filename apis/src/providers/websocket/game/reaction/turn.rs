@@ -18,6 +18,7 @@ pub fn handle_turn(turn: Turn, gar: GameActionResponse) {
         if gar.game.game_id == game_id {
             timer.update_from(&gar.game);
             game_state.clear_gc();
+            game_state.clear_premove();
             game_state.set_game_response(gar.game.clone());
             if game_state.signal.get_untracked().state.history.moves != gar.game.history {
                 match turn {
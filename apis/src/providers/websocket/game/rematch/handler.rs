@@ -0,0 +1,30 @@
+use crate::{common::RematchUpdate, providers::game_state::GameStateSignal};
+use leptos::*;
+use leptos_router::use_navigate;
+
+pub fn handle_rematch(update: RematchUpdate) {
+    let game_state = expect_context::<GameStateSignal>();
+    let viewing = move |game_id| game_state.signal.get_untracked().game_id == Some(game_id);
+    match update {
+        RematchUpdate::Offered { game_id, user_id } => {
+            if viewing(game_id) {
+                game_state.set_rematch_offer(Some(user_id));
+            }
+        }
+        RematchUpdate::Withdrawn(game_id) => {
+            if viewing(game_id) {
+                game_state.set_rematch_offer(None);
+            }
+        }
+        RematchUpdate::Accepted {
+            game_id,
+            new_game_id,
+        } => {
+            if viewing(game_id) {
+                game_state.set_rematch_offer(None);
+                let navigate = use_navigate();
+                navigate(&format!("/game/{new_game_id}"), Default::default());
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ pub mod chat;
 mod context;
 pub mod game;
 pub mod ping;
+pub mod quick_pair;
 pub mod response_handler;
 pub mod tournament;
 pub mod user_search;
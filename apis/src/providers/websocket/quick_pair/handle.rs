@@ -0,0 +1,10 @@
+use crate::{common::QuickPairUpdate, providers::quick_pair::QuickPairSignal};
+use leptos::*;
+
+pub fn handle_quick_pair(update: QuickPairUpdate) {
+    let quick_pair = expect_context::<QuickPairSignal>();
+    match update {
+        QuickPairUpdate::Searching(details) => quick_pair.signal.set(Some(details)),
+        QuickPairUpdate::Stopped => quick_pair.signal.set(None),
+    }
+}
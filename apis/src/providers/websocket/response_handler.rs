@@ -5,8 +5,9 @@ use leptos::*;
 
 use super::{
     challenge::handler::handle_challenge, chat::handle::handle_chat, game::handler::handle_game,
-    ping::handle::handle_ping, tournament::handler::handle_tournament,
-    user_search::handle::handle_user_search, user_status::handle::handle_user_status,
+    ping::handle::handle_ping, quick_pair::handle::handle_quick_pair,
+    tournament::handler::handle_tournament, user_search::handle::handle_user_search,
+    user_status::handle::handle_user_status,
 };
 
 pub fn handle_response(m: String) {
@@ -21,6 +22,7 @@ pub fn handle_response(m: String) {
                     Chat(message) => handle_chat(message),
                     UserSearch(results) => handle_user_search(results),
                     Tournament(tournament_update) => handle_tournament(tournament_update),
+                    QuickPair(update) => handle_quick_pair(update),
                     todo => {
                         log!("Got {todo:?} which is currently still unimplemented");
                     } // GameRequiresAction, UserStatusChange, ...
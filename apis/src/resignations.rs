@@ -0,0 +1,28 @@
+use actix::{Message, Recipient};
+use actix_web::web::Data;
+use leptos::*;
+use shared_types::GameId;
+use uuid::Uuid;
+
+// Games a user got resigned from outside of a game connection, e.g. when the account went away.
+// The lobby tells the opponents and spectators and drops what it cached about the games.
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct GamesResigned {
+    pub user_id: Uuid,
+    pub game_ids: Vec<GameId>,
+}
+
+pub fn announce_resignations(user_id: Uuid, game_ids: Vec<GameId>) -> Result<(), ServerFnError> {
+    if game_ids.is_empty() {
+        return Ok(());
+    }
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    req.app_data::<Data<Recipient<GamesResigned>>>()
+        .ok_or("Failed to get lobby")
+        .map_err(ServerFnError::new)?
+        .do_send(GamesResigned { user_id, game_ids });
+    Ok(())
+}
@@ -6,14 +6,40 @@ use uuid::Uuid;
 pub struct AccountResponse {
     pub username: String,
     pub email: String,
+    pub email_verified: bool,
+    pub totp_enabled: bool,
+    pub totp_required: bool,
+    pub notifications: NotificationSettingsResponse,
     pub id: Uuid,
     pub user: UserResponse,
 }
 
+// The e-mail digests a user opted into
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default)]
+pub struct NotificationSettingsResponse {
+    pub your_turn: bool,
+    pub time_running_low: bool,
+    pub tournament_starting: bool,
+    pub tournament_invitation: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub enum LoginResponse {
+    LoggedIn(AccountResponse),
+    // The password was right, the login completes with a code from the authenticator app
+    SecondFactor,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TotpEnrolmentResponse {
+    pub secret: String,
+    pub qr_code: String, // SVG of the otpauth URL
+}
+
 use cfg_if::cfg_if;
 cfg_if! { if #[cfg(feature = "ssr")] {
 use db_lib::{
-    models::User,
+    models::{NotificationSettings, User},
     DbConn,
 };
 use leptos::*;
@@ -21,9 +47,19 @@ impl AccountResponse {
     pub async fn from_uuid(id: &Uuid, conn: &mut DbConn<'_>) -> Result<Self, ServerFnError> {
         let user = User::find_by_uuid(id, conn).await?;
         let response = UserResponse::from_model(&user, conn).await.map_err(ServerFnError::new)?;
+        let settings = NotificationSettings::for_user(&user.id, conn).await?;
         Ok(Self {
             username: user.username,
-            email: user.email,
+            email: user.email.unwrap_or_default(),
+            email_verified: user.email_verified,
+            totp_enabled: user.totp_enabled,
+            totp_required: user.admin,
+            notifications: NotificationSettingsResponse {
+                your_turn: settings.your_turn,
+                time_running_low: settings.time_running_low,
+                tournament_starting: settings.tournament_starting,
+                tournament_invitation: settings.tournament_invitation,
+            },
             id: user.id,
             user: response,
         })
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_types::GameId;
+
+// An entry of the moderation audit log
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AdminActionResponse {
+    pub id: i32,
+    pub admin: String,
+    pub action: String,
+    pub target_user: Option<String>,
+    pub target_game: Option<GameId>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+use cfg_if::cfg_if;
+cfg_if! { if #[cfg(feature = "ssr")] {
+use db_lib::{
+    db_error::DbError,
+    models::{AdminAction, Game, User},
+    DbConn,
+};
+impl AdminActionResponse {
+    pub async fn from_model(action: &AdminAction, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        let admin = User::find_by_uuid(&action.admin_id, conn).await?;
+        let target_user = match action.target_user_id {
+            Some(id) => Some(User::find_by_uuid(&id, conn).await?.username),
+            None => None,
+        };
+        let target_game = match action.target_game_id {
+            Some(id) => Some(GameId(Game::find_by_uuid(&id, conn).await?.nanoid)),
+            None => None,
+        };
+        Ok(Self {
+            id: action.id,
+            admin: admin.username,
+            action: action.action.clone(),
+            target_user,
+            target_game,
+            reason: action.reason.clone(),
+            created_at: action.created_at,
+        })
+    }
+}
+}}
@@ -3,7 +3,10 @@ use crate::responses::user::UserResponse;
 use chrono::prelude::*;
 use hive_lib::{ColorChoice, GameType};
 use serde::{Deserialize, Serialize};
-use shared_types::{ChallengeDetails, ChallengeId, ChallengeVisibility, GameSpeed, TimeMode};
+use shared_types::{
+    ChallengeDetails, ChallengeId, ChallengeVisibility, ClockType, GameSpeed, RepetitionRule,
+    TimeMode,
+};
 use std::collections::hash_map::Values;
 use std::str;
 use std::str::FromStr;
@@ -24,6 +27,8 @@ pub struct ChallengeResponse {
     pub time_mode: TimeMode,         // Correspondence, Timed, Untimed
     pub time_base: Option<i32>,      // Secons
     pub time_increment: Option<i32>, // Seconds
+    pub clock_type: ClockType,
+    pub repetition_rule: RepetitionRule,
     pub speed: GameSpeed,
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
@@ -47,8 +52,10 @@ impl ChallengeResponse {
         challenger: User,
         conn: &mut DbConn<'_>,
     ) -> Result<Self> {
-        let game_speed = GameSpeed::from_base_increment(challenge.time_base, challenge.time_increment);
-        let challenger_rating = Rating::for_uuid(&challenger.id, &game_speed, conn).await?;
+        let clock_type = ClockType::from_str(&challenge.clock_type)?;
+        let game_speed = GameSpeed::from_base_increment(challenge.time_base, challenge.time_increment, clock_type);
+        let game_type = GameType::from_str(&challenge.game_type)?;
+        let challenger_rating = Rating::for_uuid(&challenger.id, &game_speed, &game_type, conn).await?;
         let opponent = match challenge.opponent_id {
             None => None,
             Some(id) => Some(UserResponse::from_uuid(&id, conn).await?),
@@ -67,6 +74,8 @@ impl ChallengeResponse {
             time_mode: TimeMode::from_str(&challenge.time_mode)?,
             time_base: challenge.time_base,
             time_increment: challenge.time_increment,
+            clock_type,
+            repetition_rule: RepetitionRule::from_str(&challenge.repetition_rule)?,
             speed: game_speed,
             band_upper: challenge.band_upper,
             band_lower: challenge.band_lower,
@@ -83,6 +92,8 @@ fn is_compatible(challenge: &ChallengeResponse, user: &str, details: &ChallengeD
         && details.time_mode == challenge.time_mode
         && details.time_base == challenge.time_base
         && details.time_increment == challenge.time_increment
+        && details.clock_type == challenge.clock_type
+        && details.repetition_rule == challenge.repetition_rule
         && match details.color_choice {
             ColorChoice::Random => challenge.color_choice == ColorChoice::Random,
             ColorChoice::White => challenge.color_choice == ColorChoice::Black,
@@ -110,6 +121,8 @@ fn has_same_details(challenge: &ChallengeResponse, user: &str, details: &Challen
         && details.time_mode == challenge.time_mode
         && details.time_base == challenge.time_base
         && details.time_increment == challenge.time_increment
+        && details.clock_type == challenge.clock_type
+        && details.repetition_rule == challenge.repetition_rule
         && details.color_choice == challenge.color_choice
         && challenge_opponent == details.opponent.as_deref()
         && challenge.challenger.username == user
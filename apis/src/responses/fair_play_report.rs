@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_types::FairPlayFlag;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FairPlayReportResponse {
+    pub username: String,
+    pub games: i32,
+    pub moves: i32,
+    pub engine_match_rate: Option<f64>,
+    pub move_time_variation: Option<f64>,
+    pub performance_gap: Option<f64>,
+    pub flags: Vec<FairPlayFlag>,
+    pub analysed_at: DateTime<Utc>,
+}
+
+use cfg_if::cfg_if;
+cfg_if! { if #[cfg(feature = "ssr")] {
+use db_lib::models::{FairPlayReport, User};
+impl FairPlayReportResponse {
+    pub fn from_model(report: &FairPlayReport, user: &User) -> Self {
+        Self {
+            username: user.username.clone(),
+            games: report.games,
+            moves: report.moves,
+            engine_match_rate: report.engine_match_rate,
+            move_time_variation: report.move_time_variation,
+            performance_gap: report.performance_gap,
+            flags: report.flags(),
+            analysed_at: report.analysed_at,
+        }
+    }
+}
+}}
@@ -1,9 +1,13 @@
 use crate::responses::user::UserResponse;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use hive_lib::{Bug, GameControl, GameResult, GameStatus, GameType, History, Position, State};
+use hive_lib::{
+    Bug, Color, GameControl, GameResult, GameStatus, GameType, History, Position, State,
+};
 use serde::{Deserialize, Serialize};
-use shared_types::{Conclusion, GameId, GameSpeed, GameStart, TimeMode};
+use shared_types::{
+    ClockType, Conclusion, GameId, GameSpeed, GameStart, RepetitionRule, TimeMode,
+};
 use std::{collections::HashMap, time::Duration};
 use uuid::Uuid;
 
@@ -19,6 +23,7 @@ pub struct GameAbstractResponse {
     pub time_mode: TimeMode,
     pub time_base: Option<i32>,
     pub time_increment: Option<i32>,
+    pub clock_type: ClockType,
     pub speed: GameSpeed,
     pub conclusion: Conclusion,
 }
@@ -50,6 +55,7 @@ pub struct GameResponse {
     pub time_mode: TimeMode,
     pub time_base: Option<i32>,
     pub time_increment: Option<i32>,
+    pub clock_type: ClockType,
     pub speed: GameSpeed,
     pub black_time_left: Option<Duration>,
     pub white_time_left: Option<Duration>,
@@ -59,7 +65,12 @@ pub struct GameResponse {
     pub hashes: Vec<u64>,
     pub conclusion: Conclusion,
     pub repetitions: Vec<usize>,
+    pub repetition_rule: RepetitionRule,
     pub game_start: GameStart,
+    pub armageddon: bool,
+    pub annulled: bool,
+    pub move_times: Vec<Option<Duration>>,
+    pub move_dates: Vec<Option<DateTime<Utc>>>,
 }
 
 impl PartialEq for GameResponse {
@@ -73,11 +84,13 @@ impl PartialEq for GameResponse {
 
 impl GameResponse {
     pub fn white_rating(&self) -> u64 {
-        self.white_player.rating_for_speed(&self.speed)
+        self.white_player
+            .rating_for_speed(&self.game_type, &self.speed)
     }
 
     pub fn black_rating(&self) -> u64 {
-        self.black_player.rating_for_speed(&self.speed)
+        self.black_player
+            .rating_for_speed(&self.game_type, &self.speed)
     }
 
     pub fn create_state(&self) -> State {
@@ -85,15 +98,30 @@ impl GameResponse {
             &GameStatus::NotStarted | &GameStatus::InProgress => GameResult::Unknown,
             GameStatus::Finished(result) => result.clone(),
         };
-        State::new_from_history(&History::new_from_gamestate(
-            self.history.clone(),
-            &self.hashes,
-            result,
-            self.game_type,
-        ))
+        State::new_from_history_with(
+            &History::new_from_gamestate(self.history.clone(), &self.hashes, result, self.game_type),
+            self.repetition_rule.is_automatic(),
+        )
         .expect("State to be valid, as game was")
     }
 
+    // How long each move took, the first move of each player and moves without a date are None
+    pub fn move_durations(&self) -> Vec<Option<Duration>> {
+        self.move_dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| match (i.checked_sub(1), date) {
+                (Some(prev), Some(date)) if i > 1 => self
+                    .move_dates
+                    .get(prev)
+                    .copied()
+                    .flatten()
+                    .and_then(|prev| date.signed_duration_since(prev).to_std().ok()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn time_left(&self) -> Result<std::time::Duration> {
         if self.turn < 2 {
             return Ok(std::time::Duration::from_nanos(u64::MAX));
@@ -127,6 +155,71 @@ impl GameResponse {
         }
         Ok(std::time::Duration::from_nanos(u64::MAX))
     }
+
+    fn pgn_date(&self) -> String {
+        self.created_at.format("%d-%b-%Y_%H:%M:%S").to_string()
+    }
+
+    pub fn pgn(&self) -> String {
+        let game_result = match &self.game_status {
+            GameStatus::Finished(result) => match result {
+                GameResult::Draw => "Draw".to_owned(),
+                GameResult::Unknown => "Unkown".to_owned(),
+                GameResult::Winner(Color::White) => "WhiteWins".to_owned(),
+                GameResult::Winner(Color::Black) => "BlackWins".to_owned(),
+            },
+            _ => self.game_status.to_string(),
+        };
+        let mut file = format!(
+            "[GameType \"{}\"]\n\
+        [Date \"{}\"]\n\
+        [Site \"hivegame.com\"]\n\
+        [White \"{}\"]\n\
+        [Black \"{}\"]\n\
+        [Result \"{}\"]\n\n",
+            self.game_type,
+            self.pgn_date(),
+            self.white_player.username,
+            self.black_player.username,
+            game_result
+        );
+        for (i, (mv, dest)) in self.history.iter().enumerate() {
+            match self.move_times.get(i).copied().flatten() {
+                Some(time_left) => file.push_str(&format!(
+                    "{}. {} {} {{[%clk {}]}}\n",
+                    i + 1,
+                    mv,
+                    dest,
+                    clk(time_left)
+                )),
+                None => file.push_str(&format!("{}. {} {}\n", i + 1, mv, dest)),
+            }
+        }
+        if self.finished {
+            file.push_str(&format!("\n{game_result}\n"));
+        }
+        file
+    }
+
+    pub fn pgn_filename(&self) -> String {
+        format!(
+            "{}_{}_vs_{}.pgn",
+            self.pgn_date(),
+            self.white_player.username,
+            self.black_player.username
+        )
+    }
+}
+
+// The clock of a move in the h:mm:ss format PGN comments use
+fn clk(time_left: Duration) -> String {
+    let seconds = time_left.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
 }
 
 use cfg_if::cfg_if;
@@ -138,7 +231,7 @@ use db_lib::{
     DbConn,
 };
 use hive_lib::{
-    Color, GameStatus::Finished, Piece,
+    GameStatus::Finished, Piece,
 };
 use std::str::FromStr;
 
@@ -155,17 +248,36 @@ impl GameResponse {
 
     pub async fn from_model(game: &Game, conn: &mut DbConn<'_>) -> Result<Self> {
         let history = Box::new(History::new_from_str(&game.history)?);
-        let state = Box::new(State::new_from_history(&history)?);
-        GameResponse::new_from(game, state, conn).await
+        let state = Box::new(State::new_from_history_with(
+            &history,
+            game.automatic_repetition(),
+        )?);
+        let white_player = UserResponse::from_uuid(&game.white_id, conn).await?;
+        let black_player = UserResponse::from_uuid(&game.black_id, conn).await?;
+        GameResponse::new_from(game, state, white_player, black_player, conn).await
+    }
+
+    // For callers which already hold the game's state and players, this skips
+    // replaying the history and loading the users
+    pub async fn from_state(
+        game: &Game,
+        state: State,
+        white_player: UserResponse,
+        black_player: UserResponse,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Self> {
+        GameResponse::new_from(game, Box::new(state), white_player, black_player, conn).await
     }
 
     async fn new_from(
         game: &Game,
         state: Box<State>,
+        white_player: UserResponse,
+        black_player: UserResponse,
         conn: &mut DbConn<'_>,
     ) -> Result<Self> {
-        let white_player = UserResponse::from_uuid(&game.white_id, conn).await?;
-        let black_player = UserResponse::from_uuid(&game.black_id, conn).await?;
+        let game_type = GameType::from_str(&game.game_type)?;
+        let game_speed = GameSpeed::from_str(&game.speed)?;
         let (white_rating, black_rating, white_rating_change, black_rating_change) = {
             if let Finished(_) = GameStatus::from_str(&game.game_status).expect("GameStatus parsed") {
                 (
@@ -176,8 +288,8 @@ impl GameResponse {
                 )
             } else {
                 (
-                    Some(white_player.rating_for_speed(&GameSpeed::from_str(&game.speed)?) as f64),
-                    Some(black_player.rating_for_speed(&GameSpeed::from_str(&game.speed)?) as f64),
+                    Some(white_player.rating_for_speed(&game_type, &game_speed) as f64),
+                    Some(black_player.rating_for_speed(&game_type, &game_speed) as f64),
                     None,
                     None,
                 )
@@ -227,13 +339,23 @@ impl GameResponse {
             time_mode: TimeMode::from_str(&game.time_mode).unwrap(),
             time_base: game.time_base,
             time_increment: game.time_increment,
+            clock_type: ClockType::from_str(&game.clock_type)?,
             last_interaction: game.last_interaction,
             speed: GameSpeed::from_str(&game.speed)?,
             created_at: game.created_at,
             updated_at: game.updated_at,
             conclusion: Conclusion::from_str(&game.conclusion)?,
             repetitions: state.repeating_moves.clone(),
+            repetition_rule: RepetitionRule::from_str(&game.repetition_rule)?,
             game_start: GameStart::from_str(&game.game_start)?,
+            armageddon: game.armageddon,
+            annulled: game.annulled,
+            move_times: game
+                .move_times
+                .iter()
+                .map(|nanos| nanos.map(|nanos| Duration::from_nanos(nanos as u64)))
+                .collect(),
+            move_dates: game.move_dates.clone(),
         })
     }
 
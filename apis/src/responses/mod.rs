@@ -1,18 +1,26 @@
 mod account;
+mod admin_action;
 mod challenge;
+mod fair_play_report;
 mod game;
 mod invitation;
 mod rating;
+mod rating_history;
 mod tournament;
 mod tournament_series;
 mod user;
 mod heartbeat;
 pub use heartbeat::HeartbeatResponse;
-pub use account::AccountResponse;
+pub use admin_action::AdminActionResponse;
+pub use account::{
+    AccountResponse, LoginResponse, NotificationSettingsResponse, TotpEnrolmentResponse,
+};
 pub use challenge::{create_challenge_handler, ChallengeResponse};
+pub use fair_play_report::FairPlayReportResponse;
 pub use game::GameResponse;
 pub use invitation::InvitationResponse;
 pub use rating::RatingResponse;
+pub use rating_history::RatingHistoryResponse;
 pub use tournament::TournamentAbstractResponse;
 pub use tournament::TournamentResponse;
 pub use user::UserResponse;
@@ -1,9 +1,11 @@
+use hive_lib::GameType;
 use serde::{Deserialize, Serialize};
 use shared_types::{Certainty, GameSpeed};
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct RatingResponse {
     pub speed: GameSpeed,
+    pub game_type: GameType,
     pub rating: u64,
     pub played: i64,
     pub win: i64,
@@ -21,31 +23,32 @@ use std::str::FromStr;
 use uuid::Uuid;
 use anyhow::Result;
 impl RatingResponse {
-    pub async fn from_uuid(id: &Uuid, game_speed: &GameSpeed, conn: &mut DbConn<'_>) -> Result<Self> {
-        let rating = Rating::for_uuid(id, game_speed, conn).await?;
+    pub async fn from_uuid(id: &Uuid, game_speed: &GameSpeed, game_type: &GameType, conn: &mut DbConn<'_>) -> Result<Self> {
+        let rating = Rating::for_uuid(id, game_speed, game_type, conn).await?;
         Ok(Self::from_rating(&rating))
     }
 
-    pub async fn from_user(user: &User, game_speed: &GameSpeed, conn: &mut DbConn<'_>) -> Result<Self> {
-        let rating = Rating::for_uuid(&user.id, game_speed, conn).await?;
+    pub async fn from_user(user: &User, game_speed: &GameSpeed, game_type: &GameType, conn: &mut DbConn<'_>) -> Result<Self> {
+        let rating = Rating::for_uuid(&user.id, game_speed, game_type, conn).await?;
         Ok(Self::from_rating(&rating))
     }
 
-    pub async fn from_username(username: &str, game_speed: &GameSpeed, conn: &mut DbConn<'_>) -> Result<Self> {
+    pub async fn from_username(username: &str, game_speed: &GameSpeed, game_type: &GameType, conn: &mut DbConn<'_>) -> Result<Self> {
         let user = User::find_by_username(username, conn).await?;
-        let rating = Rating::for_uuid(&user.id, game_speed, conn).await?;
+        let rating = Rating::for_uuid(&user.id, game_speed, game_type, conn).await?;
         Ok(Self::from_rating(&rating))
     }
 
     pub fn from_rating(rating: &Rating) -> Self {
         Self {
             speed: GameSpeed::from_str(&rating.speed).expect("Rating to have a valid GameSpeed"),
+            game_type: GameType::from_str(&rating.game_type).expect("Rating to have a valid GameType"),
             rating: rating.rating.floor() as u64,
             played: rating.played,
             win: rating.won,
             loss: rating.lost,
             draw: rating.draw,
-            certainty: Certainty::from_deviation(rating.deviation),
+            certainty: Certainty::from_deviation(rating.current_deviation()),
         }
     }
 }
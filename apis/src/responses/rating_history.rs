@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use hive_lib::GameType;
+use serde::{Deserialize, Serialize};
+use shared_types::GameSpeed;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct RatingHistoryResponse {
+    pub game_type: GameType,
+    pub speed: GameSpeed,
+    pub rating: f64,
+    pub rating_change: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
+use anyhow::Result;
+use db_lib::{
+    models::{RatingHistory, User},
+    DbConn,
+};
+use std::str::FromStr;
+impl RatingHistoryResponse {
+    pub async fn from_username(
+        username: &str,
+        game_type: &GameType,
+        game_speed: &GameSpeed,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Self>> {
+        let user = User::find_by_username(username, conn).await?;
+        RatingHistory::for_uuid(&user.id, game_type, game_speed, conn)
+            .await?
+            .iter()
+            .map(Self::from_model)
+            .collect()
+    }
+
+    pub fn from_model(history: &RatingHistory) -> Result<Self> {
+        Ok(Self {
+            game_type: GameType::from_str(&history.game_type)?,
+            speed: GameSpeed::from_str(&history.speed)?,
+            rating: history.rating,
+            rating_change: history.rating_change,
+            created_at: history.created_at,
+        })
+    }
+}
+}}
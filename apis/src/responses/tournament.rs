@@ -2,7 +2,8 @@ use super::{GameResponse, UserResponse};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use shared_types::{
-    ScoringMode, Standings, StartMode, Tiebreaker, TimeMode, TournamentId, TournamentStatus,
+    ClockType, RepetitionRule, ScoringMode, Standings, StartMode, Tiebreaker, TimeMode,
+    TournamentId, TournamentStatus,
 };
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -22,6 +23,7 @@ pub struct TournamentResponse {
     pub name: String,
     pub description: String,
     pub scoring: ScoringMode,
+    pub armageddon: bool,
     pub tiebreakers: Vec<Tiebreaker>,
     pub invitees: Vec<UserResponse>,
     pub players: HashMap<Uuid, UserResponse>,
@@ -35,6 +37,8 @@ pub struct TournamentResponse {
     pub time_mode: TimeMode,
     pub time_base: Option<i32>,
     pub time_increment: Option<i32>,
+    pub clock_type: ClockType,
+    pub repetition_rule: RepetitionRule,
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
     pub status: TournamentStatus,
@@ -50,7 +54,6 @@ pub struct TournamentResponse {
 cfg_if::cfg_if! { if #[cfg(feature = "ssr")] {
 use anyhow::Result;
 use db_lib::{models::Tournament, DbConn};
-use shared_types::TournamentGameResult;
 use std::str::FromStr;
 
 impl TournamentAbstractResponse {
@@ -98,15 +101,8 @@ impl TournamentResponse {
         }
         let games = tournament.games(conn).await?;
         let mut game_responses = Vec::new();
-        let mut standings = Standings::new();
+        let mut standings = tournament.standings(&games)?;
         for game in games {
-            standings.add_result(
-                game.white_id,
-                game.black_id,
-                game.white_rating.unwrap_or(0.0),
-                game.black_rating.unwrap_or(0.0),
-                TournamentGameResult::from_str(&game.tournament_game_result)?,
-            );
             game_responses.push(GameResponse::from_model(&game, conn).await?);
         }
         standings.enforce_tiebreakers();
@@ -117,6 +113,7 @@ impl TournamentResponse {
             description: tournament.description.clone(),
             standings,
             scoring: ScoringMode::from_str(&tournament.scoring)?,
+            armageddon: tournament.armageddon,
             players,
             organizers,
             games: game_responses,
@@ -133,6 +130,8 @@ impl TournamentResponse {
             time_mode: TimeMode::from_str(&tournament.time_mode)?,
             time_base: tournament.time_base,
             time_increment: tournament.time_increment,
+            clock_type: ClockType::from_str(&tournament.clock_type)?,
+            repetition_rule: RepetitionRule::from_str(&tournament.repetition_rule)?,
             band_upper: tournament.band_upper,
             band_lower: tournament.band_lower,
             status: TournamentStatus::from_str(&tournament.status)?,
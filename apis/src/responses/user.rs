@@ -1,6 +1,7 @@
 use super::rating::RatingResponse;
+use hive_lib::GameType;
 use serde::{Deserialize, Serialize};
-use shared_types::GameSpeed;
+use shared_types::{rated_game_type, GameSpeed};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -10,7 +11,12 @@ pub struct UserResponse {
     pub uid: Uuid,
     pub patreon: bool,
     pub admin: bool,
-    pub ratings: HashMap<GameSpeed, RatingResponse>,
+    pub guest: bool,
+    pub bio: String, // Markdown, rendered with bio_html
+    pub country: Option<String>,
+    pub preferred_game_type: Option<GameType>,
+    pub links: Vec<String>,
+    pub ratings: HashMap<GameType, HashMap<GameSpeed, RatingResponse>>,
 }
 
 impl UserResponse {
@@ -20,62 +26,40 @@ impl UserResponse {
             uid: uuid,
             patreon: false,
             admin: false,
+            guest: false,
+            bio: String::new(),
+            country: None,
+            preferred_game_type: None,
+            links: Vec::new(),
             ratings: HashMap::new(),
         }
     }
 
-    pub fn rating_for_speed(&self, game_speed: &GameSpeed) -> u64 {
-        match game_speed {
-            GameSpeed::Blitz => self.blitz(),
-            GameSpeed::Correspondence => self.correspondence(),
-            GameSpeed::Bullet => self.bullet(),
-            GameSpeed::Rapid => self.rapid(),
-            GameSpeed::Classic => self.classic(),
-            GameSpeed::Puzzle => self.puzzle(),
-            GameSpeed::Untimed => 0,
-        }
-    }
-
-    pub fn bullet(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Bullet) {
-            return rating_response.rating;
-        }
-        0
+    // Either the uploaded image or one generated from the user's id
+    pub fn avatar_url(&self) -> String {
+        format!("/@/{}/avatar", self.username)
     }
 
-    pub fn puzzle(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Puzzle) {
-            return rating_response.rating;
-        }
-        0
-    }
-
-    pub fn blitz(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Blitz) {
-            return rating_response.rating;
-        }
-        0
-    }
-
-    pub fn correspondence(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Correspondence) {
-            return rating_response.rating;
-        }
-        0
-    }
-
-    pub fn classic(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Classic) {
-            return rating_response.rating;
-        }
-        0
+    pub fn rating_response(
+        &self,
+        game_type: &GameType,
+        game_speed: &GameSpeed,
+    ) -> Option<&RatingResponse> {
+        let game_speed = match game_speed {
+            GameSpeed::Untimed => &GameSpeed::Correspondence,
+            _ => game_speed,
+        };
+        self.ratings
+            .get(&rated_game_type(game_type))
+            .and_then(|ratings| ratings.get(game_speed))
     }
 
-    pub fn rapid(&self) -> u64 {
-        if let Some(rating_response) = self.ratings.get(&GameSpeed::Rapid) {
-            return rating_response.rating;
+    pub fn rating_for_speed(&self, game_type: &GameType, game_speed: &GameSpeed) -> u64 {
+        if *game_speed == GameSpeed::Untimed {
+            return 0;
         }
-        0
+        self.rating_response(game_type, game_speed)
+            .map_or(0, |rating_response| rating_response.rating)
     }
 }
 
@@ -85,6 +69,8 @@ use db_lib::{
     DbConn,
 };
 use anyhow::Result;
+use shared_types::rated_game_types;
+use std::str::FromStr;
 impl UserResponse {
     pub async fn from_uuid(id: &Uuid, conn: &mut DbConn<'_>) -> Result<Self> {
         let user = User::find_by_uuid(id, conn).await?;
@@ -98,15 +84,27 @@ impl UserResponse {
 
     pub async fn from_model(user: &User, conn: &mut DbConn<'_>) -> Result<Self> {
         let mut ratings = HashMap::new();
-        for game_speed in GameSpeed::all_rated().into_iter() {
-            let rating = RatingResponse::from_user(user, &game_speed, conn).await?;
-            ratings.insert(game_speed, rating);
+        for game_type in rated_game_types().into_iter() {
+            let mut speeds = HashMap::new();
+            for game_speed in GameSpeed::all_rated().into_iter() {
+                let rating = RatingResponse::from_user(user, &game_speed, &game_type, conn).await?;
+                speeds.insert(game_speed, rating);
+            }
+            ratings.insert(game_type, speeds);
         }
         let response = UserResponse {
             username: user.username.clone(),
             uid: user.id,
             patreon: user.patreon,
-            admin: user.admin,
+            admin: user.is_admin(),
+            guest: user.guest,
+            bio: user.bio.clone(),
+            country: user.country.clone(),
+            preferred_game_type: user
+                .preferred_game_type
+                .as_deref()
+                .and_then(|game_type| GameType::from_str(game_type).ok()),
+            links: user.links(),
             ratings,
         };
         Ok(response)
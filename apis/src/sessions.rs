@@ -0,0 +1,23 @@
+use actix::{Message, Recipient};
+use actix_web::web::Data;
+use leptos::*;
+use uuid::Uuid;
+
+// Closes every websocket of a user whose account can't be used anymore. The lobby that holds them
+// lives in the server binary, it is handed to the app as a recipient of this message.
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct EndSessions {
+    pub user_id: Uuid,
+}
+
+pub fn end_sessions(user_id: Uuid) -> Result<(), ServerFnError> {
+    let req = use_context::<actix_web::HttpRequest>()
+        .ok_or("Failed to get HttpRequest")
+        .map_err(ServerFnError::new)?;
+    req.app_data::<Data<Recipient<EndSessions>>>()
+        .ok_or("Failed to get lobby")
+        .map_err(ServerFnError::new)?
+        .do_send(EndSessions { user_id });
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use crate::{
     common::{ChallengeUpdate, GameActionResponse, GameReaction, GameUpdate, ServerMessage},
     responses::GameResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        auth_error::AuthError,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+    },
 };
 use anyhow::Result;
 use db_lib::{
@@ -11,13 +14,16 @@ use db_lib::{
 };
 use diesel_async::scoped_futures::ScopedFutureExt;
 use diesel_async::AsyncConnection;
-use shared_types::{ChallengeId, GameSpeed};
+use hive_lib::GameType;
+use shared_types::{ChallengeId, ClockType, GameSpeed};
+use std::str::FromStr;
 use uuid::Uuid;
 
 pub struct AcceptHandler {
     challenger_id: ChallengeId,
     user_id: Uuid,
     username: String,
+    guest: bool,
     pool: DbPool,
 }
 
@@ -26,12 +32,14 @@ impl AcceptHandler {
         challenger_id: ChallengeId,
         username: &str,
         user_id: Uuid,
+        guest: bool,
         pool: &DbPool,
     ) -> Result<Self> {
         Ok(Self {
             challenger_id,
             user_id,
             username: username.to_owned(),
+            guest,
             pool: pool.clone(),
         })
     }
@@ -40,8 +48,16 @@ impl AcceptHandler {
         let mut conn = get_conn(&self.pool).await?;
         let mut messages = Vec::new();
         let challenge = Challenge::find_by_challenge_id(&self.challenger_id, &mut conn).await?;
-        let speed = GameSpeed::from_base_increment(challenge.time_base, challenge.time_increment);
-        let rating = Rating::for_uuid(&self.user_id, &speed, &mut conn)
+        if self.guest && challenge.rated {
+            Err(AuthError::RegistrationRequired)?
+        }
+        let speed = GameSpeed::from_base_increment(
+            challenge.time_base,
+            challenge.time_increment,
+            ClockType::from_str(&challenge.clock_type)?,
+        );
+        let game_type = GameType::from_str(&challenge.game_type)?;
+        let rating = Rating::for_uuid(&self.user_id, &speed, &game_type, &mut conn)
             .await?
             .rating;
         if let Some(band_upper) = challenge.band_upper {
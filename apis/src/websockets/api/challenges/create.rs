@@ -1,7 +1,10 @@
 use crate::{
     common::{ChallengeUpdate, ServerMessage},
     responses::ChallengeResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        auth_error::AuthError,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+    },
 };
 use anyhow::Result;
 use db_lib::{
@@ -16,14 +19,21 @@ use uuid::Uuid;
 pub struct CreateHandler {
     details: ChallengeDetails,
     user_id: Uuid,
+    guest: bool,
     pool: DbPool,
 }
 
 impl CreateHandler {
-    pub async fn new(details: ChallengeDetails, user_id: Uuid, pool: &DbPool) -> Result<Self> {
+    pub async fn new(
+        details: ChallengeDetails,
+        user_id: Uuid,
+        guest: bool,
+        pool: &DbPool,
+    ) -> Result<Self> {
         Ok(Self {
             details,
             user_id,
+            guest,
             pool: pool.clone(),
         })
     }
@@ -31,9 +41,15 @@ impl CreateHandler {
     pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
         let mut conn = get_conn(&self.pool).await?;
         let opponent = match &self.details.opponent {
-            Some(username) => Some((User::find_by_username(username, &mut conn).await?).id),
+            Some(username) => Some(User::find_by_username(username, &mut conn).await?),
             None => None,
         };
+        // Guests only play casual games, on either side of the board
+        let guest_involved = self.guest || opponent.as_ref().is_some_and(|user| user.guest);
+        if self.details.rated && guest_involved {
+            Err(AuthError::RegistrationRequired)?
+        }
+        let opponent = opponent.map(|user| user.id);
 
         let new_challenge = NewChallenge::new(self.user_id, opponent, &self.details)?;
         let challenge = Challenge::create(&new_challenge, &mut conn).await?;
@@ -15,6 +15,7 @@ pub struct ChallengeHandler {
     pool: DbPool,
     user_id: Uuid,
     username: String,
+    guest: bool,
 }
 
 impl ChallengeHandler {
@@ -22,6 +23,7 @@ impl ChallengeHandler {
         action: ChallengeAction,
         username: &str,
         user_id: Uuid,
+        guest: bool,
         pool: &DbPool,
     ) -> Result<Self> {
         Ok(Self {
@@ -29,22 +31,29 @@ impl ChallengeHandler {
             challenge_action: action,
             user_id,
             username: username.to_owned(),
+            guest,
         })
     }
 
     pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
         let messages = match self.challenge_action.clone() {
             ChallengeAction::Create(details) => {
-                CreateHandler::new(details, self.user_id, &self.pool)
+                CreateHandler::new(details, self.user_id, self.guest, &self.pool)
                     .await?
                     .handle()
                     .await?
             }
             ChallengeAction::Accept(challenge_id) => {
-                AcceptHandler::new(challenge_id, &self.username, self.user_id, &self.pool)
-                    .await?
-                    .handle()
-                    .await?
+                AcceptHandler::new(
+                    challenge_id,
+                    &self.username,
+                    self.user_id,
+                    self.guest,
+                    &self.pool,
+                )
+                .await?
+                .handle()
+                .await?
             }
             ChallengeAction::Delete(challenge_id) => {
                 DeleteHandler::new(challenge_id, self.user_id, &self.pool)
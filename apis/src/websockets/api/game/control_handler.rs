@@ -3,17 +3,17 @@ use crate::{
         GameReaction, {GameActionResponse, GameUpdate, ServerMessage},
     },
     responses::GameResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        api::tournaments::armageddon::armageddon_messages,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+        presence::Presence,
+    },
 };
 use anyhow::Result;
-use db_lib::{
-    get_conn,
-    models::{Game, User},
-    DbConn, DbPool,
-};
+use db_lib::{get_conn, models::Game, DbConn, DbPool};
 use diesel_async::scoped_futures::ScopedFutureExt;
 use diesel_async::AsyncConnection;
-use hive_lib::{GameControl, GameError};
+use hive_lib::{Color, GameControl, GameError, State};
 use shared_types::{GameId, TimeMode};
 use uuid::Uuid;
 
@@ -23,6 +23,7 @@ pub struct GameControlHandler {
     user_id: Uuid,
     username: String,
     game: Game,
+    presence: actix_web::web::Data<Presence>,
 }
 
 impl GameControlHandler {
@@ -31,6 +32,7 @@ impl GameControlHandler {
         game: &Game,
         username: &str,
         user_id: Uuid,
+        presence: actix_web::web::Data<Presence>,
         pool: &DbPool,
     ) -> Self {
         Self {
@@ -39,6 +41,7 @@ impl GameControlHandler {
             username: username.to_owned(),
             pool: pool.clone(),
             control: control.to_owned(),
+            presence,
         }
     }
 
@@ -71,15 +74,11 @@ impl GameControlHandler {
         });
         match self.control {
             GameControl::DrawOffer(_) | GameControl::TakebackRequest(_) => {
-                let current_user = User::find_by_uuid(&game.current_player_id, &mut conn).await?;
-                let games = current_user.get_games_with_notifications(&mut conn).await?;
-                let mut game_responses = Vec::new();
-                for game in games {
-                    game_responses.push(GameResponse::from_model(&game, &mut conn).await?);
-                }
                 messages.push(InternalServerMessage {
                     destination: MessageDestination::User(game.current_player_id),
-                    message: ServerMessage::Game(Box::new(GameUpdate::Urgent(game_responses))),
+                    message: ServerMessage::Game(Box::new(GameUpdate::Urgent(vec![
+                        game_response.clone()
+                    ]))),
                 });
             }
             _ => {}
@@ -90,6 +89,9 @@ impl GameControlHandler {
                 message: ServerMessage::Game(Box::new(GameUpdate::Tv(game_response))),
             });
         };
+        if game.finished {
+            messages.extend(armageddon_messages(&game, &mut conn).await?);
+        }
         Ok(messages)
     }
 
@@ -150,6 +152,38 @@ impl GameControlHandler {
         Ok(self.game.delete(conn).await?)
     }
 
+    async fn handle_claim_victory(&self, conn: &mut DbConn<'_>) -> Result<Game> {
+        // Only realtime games where the opponent has been gone long enough
+        let opponent_id = match self.game.user_color(self.user_id) {
+            Some(Color::White) => self.game.black_id,
+            _ => self.game.white_id,
+        };
+        let gone_long_enough = self.presence.gone_long_enough(&opponent_id);
+        if self.game.time_mode != TimeMode::RealTime.to_string() || !gone_long_enough {
+            Err(GameError::NoVictoryClaim {
+                game: self.game.nanoid.to_owned(),
+            })?
+        }
+        let game = self.game.claim_victory(&self.control, conn).await?;
+        Ok(game)
+    }
+
+    async fn handle_claim_repetition(&self, conn: &mut DbConn<'_>) -> Result<Game> {
+        // The current position has to have occurred three times
+        let state = State::new_from_str_with(
+            &self.game.history,
+            &self.game.game_type,
+            self.game.automatic_repetition(),
+        )?;
+        if !state.repetition_claimable() {
+            Err(GameError::NoRepetition {
+                game: self.game.nanoid.to_owned(),
+            })?
+        }
+        let game = self.game.claim_repetition(&self.control, conn).await?;
+        Ok(game)
+    }
+
     async fn handle_draw_reject(&self, conn: &mut DbConn<'_>) -> Result<Game> {
         self.ensure_previous_gc_present()?;
         let game = self.game.write_game_control(&self.control, conn).await?;
@@ -182,6 +216,8 @@ impl GameControlHandler {
                 game
             }
             GameControl::Resign(_) => self.handle_resign(conn).await?,
+            GameControl::ClaimRepetition(_) => self.handle_claim_repetition(conn).await?,
+            GameControl::ClaimVictory(_) => self.handle_claim_victory(conn).await?,
             GameControl::DrawOffer(_) => self.handle_draw_offer(conn).await?,
             GameControl::DrawAccept(_) => self.handle_draw_accept(conn).await?,
             GameControl::DrawReject(_) => self.handle_draw_reject(conn).await?,
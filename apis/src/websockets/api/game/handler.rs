@@ -1,10 +1,15 @@
 use super::start::StartHandler;
 use super::{
     control_handler::GameControlHandler, join_handler::JoinHandler,
+    premove_handler::PremoveHandler, rematch_handler::RematchHandler,
     timeout_handler::TimeoutHandler, turn_handler::TurnHandler,
 };
+use crate::websockets::game_states::GameStates;
 use crate::websockets::internal_server_message::InternalServerMessage;
 use crate::websockets::messages::WsMessage;
+use crate::websockets::premoves::Premoves;
+use crate::websockets::presence::Presence;
+use crate::websockets::rematch_offers::RematchOffers;
 use crate::websockets::tournament_game_start::TournamentGameStart;
 use crate::{common::GameAction, websockets::chat::Chats};
 use anyhow::Result;
@@ -12,7 +17,7 @@ use db_lib::get_conn;
 use db_lib::{models::Game, DbPool};
 use diesel_async::scoped_futures::ScopedFutureExt;
 use diesel_async::AsyncConnection;
-use hive_lib::{GameError, GameStatus};
+use hive_lib::{GameControl, GameError, GameStatus};
 use shared_types::GameId;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -25,6 +30,10 @@ pub struct GameActionHandler {
     received_from: actix::Recipient<WsMessage>,
     chat_storage: actix_web::web::Data<Chats>,
     game_start: actix_web::web::Data<TournamentGameStart>,
+    rematch_offers: actix_web::web::Data<RematchOffers>,
+    premoves: actix_web::web::Data<Premoves>,
+    game_states: actix_web::web::Data<GameStates>,
+    presence: actix_web::web::Data<Presence>,
     username: String,
 }
 
@@ -36,17 +45,31 @@ impl GameActionHandler {
         received_from: actix::Recipient<WsMessage>,
         chat_storage: actix_web::web::Data<Chats>,
         game_start: actix_web::web::Data<TournamentGameStart>,
+        rematch_offers: actix_web::web::Data<RematchOffers>,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
+        presence: actix_web::web::Data<Presence>,
         pool: &DbPool,
     ) -> Result<Self> {
         let (username, user_id) = user_details;
-        let mut connection = get_conn(pool).await?;
-
-        let game = connection
-            .transaction::<_, anyhow::Error, _>(move |conn| {
-                // find_by_game_id automatically times the game out if needed
-                async move { Ok(Game::find_by_game_id(game_id, conn).await?) }.scope_boxed()
-            })
-            .await?;
+        // Turns make up most of the traffic, they pick up the game where the last turn left it and
+        // writing the turn times it out if needed
+        let cached = match game_action {
+            GameAction::Turn(_) => game_states.running(game_id),
+            _ => None,
+        };
+        let game = match cached {
+            Some(game) => game,
+            None => {
+                let mut connection = get_conn(pool).await?;
+                connection
+                    .transaction::<_, anyhow::Error, _>(move |conn| {
+                        // find_by_game_id automatically times the game out if needed
+                        async move { Ok(Game::find_by_game_id(game_id, conn).await?) }.scope_boxed()
+                    })
+                    .await?
+            }
+        };
 
         Ok(Self {
             pool: pool.clone(),
@@ -56,6 +79,10 @@ impl GameActionHandler {
             received_from,
             chat_storage,
             game_start,
+            rematch_offers,
+            premoves,
+            game_states,
+            presence,
             user_id,
         })
     }
@@ -63,29 +90,76 @@ impl GameActionHandler {
     pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
         let messages = match self.game_action.clone() {
             GameAction::CheckTime => {
-                TimeoutHandler::new(&self.game, &self.username, self.user_id, &self.pool)
-                    .handle()
-                    .await?
+                let messages =
+                    TimeoutHandler::new(&self.game, &self.username, self.user_id, &self.pool)
+                        .handle()
+                        .await?;
+                self.game_states
+                    .invalidate(&GameId(self.game.nanoid.clone()));
+                messages
             }
             GameAction::Turn(turn) => {
                 self.ensure_not_finished()?;
                 self.ensure_user_is_player()?;
-                TurnHandler::new(turn, &self.game, &self.username, self.user_id, &self.pool)
-                    .handle()
-                    .await?
+                TurnHandler::new(
+                    turn,
+                    &self.game,
+                    &self.username,
+                    self.user_id,
+                    self.premoves.clone(),
+                    self.game_states.clone(),
+                    &self.pool,
+                )
+                .handle()
+                .await?
+            }
+            GameAction::Premove(turn) => {
+                self.ensure_not_finished()?;
+                self.ensure_user_is_player()?;
+                PremoveHandler::new(
+                    turn,
+                    &self.game,
+                    &self.username,
+                    self.user_id,
+                    self.premoves.clone(),
+                    &self.pool,
+                )
+                .handle()
+                .await?
+            }
+            GameAction::CancelPremove => {
+                self.premoves
+                    .cancel(&GameId(self.game.nanoid.clone()), self.user_id);
+                Vec::new()
             }
             GameAction::Control(control) => {
                 self.ensure_not_finished()?;
                 self.ensure_user_is_player()?;
-                GameControlHandler::new(
+                let messages = GameControlHandler::new(
                     &control,
                     &self.game,
                     &self.username,
                     self.user_id,
+                    self.presence.clone(),
                     &self.pool,
                 )
                 .handle()
-                .await?
+                .await?;
+                // A queued premove no longer fits the board
+                if matches!(
+                    control,
+                    GameControl::TakebackAccept(_)
+                        | GameControl::Resign(_)
+                        | GameControl::ClaimRepetition(_)
+                        | GameControl::ClaimVictory(_)
+                        | GameControl::Abort(_)
+                        | GameControl::DrawAccept(_)
+                ) {
+                    self.premoves.remove(&GameId(self.game.nanoid.clone()));
+                }
+                self.game_states
+                    .invalidate(&GameId(self.game.nanoid.clone()));
+                messages
             }
             GameAction::Join => {
                 JoinHandler::new(
@@ -102,7 +176,7 @@ impl GameActionHandler {
             GameAction::Start => {
                 self.ensure_not_finished()?;
                 self.ensure_user_is_player()?;
-                StartHandler::new(
+                let messages = StartHandler::new(
                     &self.game,
                     self.user_id,
                     self.username.clone(),
@@ -110,12 +184,38 @@ impl GameActionHandler {
                     &self.pool,
                 )
                 .handle()
+                .await?;
+                self.game_states
+                    .invalidate(&GameId(self.game.nanoid.clone()));
+                messages
+            }
+            GameAction::Rematch(action) => {
+                self.ensure_finished()?;
+                self.ensure_user_is_player()?;
+                RematchHandler::new(
+                    action,
+                    &self.game,
+                    &self.username,
+                    self.user_id,
+                    self.rematch_offers.clone(),
+                    &self.pool,
+                )
+                .handle()
                 .await?
             }
         };
         Ok(messages)
     }
 
+    fn ensure_finished(&self) -> Result<()> {
+        if !self.game.finished {
+            Err(GameError::GameNotOver {
+                game: self.game.nanoid.to_owned(),
+            })?;
+        }
+        Ok(())
+    }
+
     fn ensure_not_finished(&self) -> Result<()> {
         if let GameStatus::Finished(_) = GameStatus::from_str(&self.game.game_status).unwrap() {
             Err(GameError::GameIsOver {
@@ -0,0 +1,81 @@
+use crate::{
+    common::{GameUpdate, ServerMessage},
+    websockets::{
+        internal_server_message::{InternalServerMessage, MessageDestination},
+        premoves::Premoves,
+    },
+};
+use anyhow::Result;
+use db_lib::{get_conn, models::Game, DbPool};
+use hive_lib::{GameError, Turn};
+use shared_types::{GameId, GameSpeed, TimeMode};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub struct PremoveHandler {
+    turn: Turn,
+    game: Game,
+    username: String,
+    user_id: Uuid,
+    premoves: actix_web::web::Data<Premoves>,
+    pool: DbPool,
+}
+
+impl PremoveHandler {
+    pub fn new(
+        turn: Turn,
+        game: &Game,
+        username: &str,
+        user_id: Uuid,
+        premoves: actix_web::web::Data<Premoves>,
+        pool: &DbPool,
+    ) -> Self {
+        Self {
+            turn,
+            game: game.to_owned(),
+            username: username.to_owned(),
+            user_id,
+            premoves,
+            pool: pool.clone(),
+        }
+    }
+
+    pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
+        if TimeMode::from_str(&self.game.time_mode)? != TimeMode::RealTime
+            || !GameSpeed::from_str(&self.game.speed)?.allows_premoves()
+        {
+            Err(GameError::NoPremoves {
+                game: self.game.nanoid.to_owned(),
+            })?
+        }
+        if let Turn::Shutout = self.turn {
+            Err(GameError::InvalidTurn {
+                username: self.username.to_owned(),
+                game: self.game.nanoid.to_owned(),
+                turn: format!("{}", self.game.turn),
+            })?
+        }
+        let game_id = GameId(self.game.nanoid.clone());
+        // Premoves are for the opponent's turn, on the own turn they would dodge the clock
+        let mut conn = get_conn(&self.pool).await?;
+        let game = Game::find_by_game_id(&game_id, &mut conn).await?;
+        if game.finished || game.current_player_id == self.user_id {
+            Err(GameError::NotPremoveTime {
+                game: self.game.nanoid.to_owned(),
+            })?
+        }
+        self.premoves.set(&game_id, self.user_id, self.turn.clone());
+        // The opponent might have moved in the meantime and nobody will pick the premove up,
+        // then it is taken back and the move has to be played on the clock
+        let game = Game::find_by_game_id(&game_id, &mut conn).await?;
+        if game.current_player_id == self.user_id
+            && self.premoves.take(&game_id, self.user_id).is_some()
+        {
+            return Ok(vec![InternalServerMessage {
+                destination: MessageDestination::User(self.user_id),
+                message: ServerMessage::Game(Box::new(GameUpdate::PremoveCleared(game_id))),
+            }]);
+        }
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    common::{
+        ChallengeUpdate, GameActionResponse, GameReaction, GameUpdate, RematchAction,
+        RematchUpdate, ServerMessage,
+    },
+    responses::GameResponse,
+    websockets::{
+        internal_server_message::{InternalServerMessage, MessageDestination},
+        rematch_offers::RematchOffers,
+    },
+};
+use anyhow::Result;
+use db_lib::{
+    get_conn,
+    models::{Challenge, Game, NewChallenge, NewGame},
+    DbPool,
+};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+use hive_lib::{ColorChoice, GameError, GameType};
+use shared_types::{
+    ChallengeDetails, ChallengeId, ChallengeVisibility, ClockType, GameId, RepetitionRule, TimeMode,
+};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub struct RematchHandler {
+    action: RematchAction,
+    game: Game,
+    username: String,
+    user_id: Uuid,
+    rematch_offers: actix_web::web::Data<RematchOffers>,
+    pool: DbPool,
+}
+
+impl RematchHandler {
+    pub fn new(
+        action: RematchAction,
+        game: &Game,
+        username: &str,
+        user_id: Uuid,
+        rematch_offers: actix_web::web::Data<RematchOffers>,
+        pool: &DbPool,
+    ) -> Self {
+        Self {
+            action,
+            game: game.to_owned(),
+            username: username.to_owned(),
+            user_id,
+            rematch_offers,
+            pool: pool.clone(),
+        }
+    }
+
+    pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
+        if self.game.tournament_id.is_some() {
+            Err(GameError::TournamentRematch)?
+        }
+        let game_id = GameId(self.game.nanoid.clone());
+        let opponent = if self.user_id == self.game.white_id {
+            self.game.black_id
+        } else {
+            self.game.white_id
+        };
+        if self.rematch_offers.rematched(&game_id) {
+            Err(GameError::AlreadyRematched {
+                game: self.game.nanoid.clone(),
+            })?
+        }
+        let offered_by = self.rematch_offers.offered_by(&game_id);
+        match self.action {
+            // Offering when the opponent already did is as good as accepting
+            RematchAction::Offer if offered_by == Some(opponent) => self.accept(game_id).await,
+            RematchAction::Offer => {
+                self.rematch_offers.offer(&game_id, self.user_id, opponent);
+                Ok(self.to_players(RematchUpdate::Offered {
+                    game_id,
+                    user_id: self.user_id,
+                }))
+            }
+            RematchAction::Accept if offered_by == Some(opponent) => self.accept(game_id).await,
+            RematchAction::Accept => Err(GameError::NoRematchOffer {
+                game: self.game.nanoid.clone(),
+            })?,
+            RematchAction::Decline => {
+                self.rematch_offers.remove(&game_id);
+                Ok(self.to_players(RematchUpdate::Withdrawn(game_id)))
+            }
+        }
+    }
+
+    async fn accept(&self, game_id: GameId) -> Result<Vec<InternalServerMessage>> {
+        let opponent = if self.user_id == self.game.white_id {
+            self.game.black_id
+        } else {
+            self.game.white_id
+        };
+        if !self.rematch_offers.accept(&game_id, opponent) {
+            Err(GameError::AlreadyRematched {
+                game: self.game.nanoid.clone(),
+            })?
+        }
+        // Colours get swapped: the old black player challenges as white
+        let details = ChallengeDetails {
+            rated: self.game.rated,
+            game_type: GameType::from_str(&self.game.game_type)?,
+            visibility: ChallengeVisibility::Private,
+            opponent: None,
+            color_choice: ColorChoice::White,
+            time_mode: TimeMode::from_str(&self.game.time_mode)?,
+            time_base: self.game.time_base,
+            time_increment: self.game.time_increment,
+            clock_type: ClockType::from_str(&self.game.clock_type)?,
+            repetition_rule: RepetitionRule::from_str(&self.game.repetition_rule)?,
+            band_upper: None,
+            band_lower: None,
+        };
+        let (white_id, black_id) = (self.game.black_id, self.game.white_id);
+        let mut conn = get_conn(&self.pool).await?;
+        let (game, deleted_challenges, game_response) = conn
+            .transaction::<_, anyhow::Error, _>(move |tc| {
+                async move {
+                    let new_challenge = NewChallenge::new(white_id, Some(black_id), &details)?;
+                    let challenge = Challenge::create(&new_challenge, tc).await?;
+                    let new_game = NewGame::new(white_id, black_id, &challenge);
+                    let (game, deleted_challenges) =
+                        Game::create_and_delete_challenges(new_game, tc).await?;
+                    let game_response = GameResponse::from_model(&game, tc).await?;
+                    Ok((game, deleted_challenges, game_response))
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        let mut messages = self.to_players(RematchUpdate::Accepted {
+            game_id,
+            new_game_id: game_response.game_id.clone(),
+        });
+        for user_id in [game.white_id, game.black_id] {
+            messages.push(InternalServerMessage {
+                destination: MessageDestination::User(user_id),
+                message: ServerMessage::Game(Box::new(GameUpdate::Reaction(GameActionResponse {
+                    game_action: GameReaction::New,
+                    game: game_response.clone(),
+                    game_id: game_response.game_id.clone(),
+                    user_id: self.user_id,
+                    username: self.username.to_owned(),
+                }))),
+            });
+        }
+        for challenge_nanoid in deleted_challenges {
+            if challenge_nanoid != ChallengeId(game.nanoid.clone()) {
+                messages.push(InternalServerMessage {
+                    destination: MessageDestination::Global,
+                    message: ServerMessage::Challenge(ChallengeUpdate::Removed(challenge_nanoid)),
+                });
+            }
+        }
+        Ok(messages)
+    }
+
+    fn to_players(&self, update: RematchUpdate) -> Vec<InternalServerMessage> {
+        [self.game.white_id, self.game.black_id]
+            .into_iter()
+            .map(|user_id| InternalServerMessage {
+                destination: MessageDestination::User(user_id),
+                message: ServerMessage::Game(Box::new(GameUpdate::Rematch(update.clone()))),
+            })
+            .collect()
+    }
+}
@@ -3,7 +3,10 @@ use crate::{
         GameReaction, {GameActionResponse, GameUpdate, ServerMessage},
     },
     responses::GameResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        api::tournaments::armageddon::armageddon_messages,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+    },
 };
 use anyhow::Result;
 use db_lib::{get_conn, models::Game, DbPool};
@@ -43,6 +46,7 @@ impl TimeoutHandler {
                     username: self.username.clone(),
                 }))),
             });
+            messages.extend(armageddon_messages(&self.game, &mut conn).await?);
         }
 
         Ok(messages)
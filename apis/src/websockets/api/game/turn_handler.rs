@@ -3,18 +3,24 @@ use crate::{
         GameReaction, {GameActionResponse, GameUpdate, ServerMessage},
     },
     responses::GameResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        api::tournaments::armageddon::armageddon_messages,
+        game_states::{CachedGame, GameStates},
+        internal_server_message::{InternalServerMessage, MessageDestination},
+        premoves::Premoves,
+    },
 };
 use anyhow::Result;
 use db_lib::{
     get_conn,
-    models::{Game, User},
-    DbPool,
+    models::{ConditionalMove, Game},
+    DbConn, DbPool,
 };
 use diesel_async::scoped_futures::ScopedFutureExt;
 use diesel_async::AsyncConnection;
 use hive_lib::{GameError, State, Turn};
 use shared_types::{GameId, TimeMode};
+use std::str::FromStr;
 use uuid::Uuid;
 
 pub struct TurnHandler {
@@ -23,16 +29,28 @@ pub struct TurnHandler {
     user_id: Uuid,
     username: String,
     game: Game,
+    premoves: actix_web::web::Data<Premoves>,
+    game_states: actix_web::web::Data<GameStates>,
 }
 
 impl TurnHandler {
-    pub fn new(turn: Turn, game: &Game, username: &str, user_id: Uuid, pool: &DbPool) -> Self {
+    pub fn new(
+        turn: Turn,
+        game: &Game,
+        username: &str,
+        user_id: Uuid,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
+        pool: &DbPool,
+    ) -> Self {
         Self {
             game: game.to_owned(),
             user_id,
             username: username.to_owned(),
             pool: pool.clone(),
             turn,
+            premoves,
+            game_states,
         }
     }
 
@@ -47,47 +65,180 @@ impl TurnHandler {
                 turn: format!("{}", self.game.turn),
             })?,
         };
-        let mut state = State::new_from_str(&self.game.history, &self.game.game_type)?;
+        let cached = self.game_states.load(&self.game, &mut conn).await?;
+        let before = cached.state.clone();
+        let mut state = before.clone();
         state.play_turn_from_position(piece, position)?;
 
-        let game = conn
+        // Every turn played along with the player who played it and the game and state after it,
+        // and whoever had a premove that could not be played
+        let (played, dropped) = conn
             .transaction::<_, anyhow::Error, _>(move |tc| {
-                async move { Ok(self.game.update_gamestate(&state, tc).await?) }.scope_boxed()
+                async move {
+                    let game = self.game.update_gamestate(&state, tc).await?;
+                    let mut played = vec![(self.turn.clone(), self.user_id, game, state)];
+                    let mut dropped = None;
+                    match TimeMode::from_str(&self.game.time_mode)? {
+                        TimeMode::Correspondence => {
+                            self.play_conditional_moves(&mut played, before, tc).await?
+                        }
+                        TimeMode::RealTime => dropped = self.play_premoves(&mut played, tc).await?,
+                        TimeMode::Untimed => {}
+                    }
+                    Ok((played, dropped))
+                }
+                .scope_boxed()
             })
             .await?;
+        let (game, state) = played
+            .last()
+            .map(|(_, _, game, state)| (game.clone(), state.clone()))
+            .expect("At least the user's turn got played");
+        self.game_states.advance(&game, &state);
 
         let mut messages = Vec::new();
-        let next_to_move = User::find_by_uuid(&game.current_player_id, &mut conn).await?;
-        let games = next_to_move.get_games_with_notifications(&mut conn).await?;
-        let mut game_responses = Vec::new();
-        for game in games {
-            game_responses.push(GameResponse::from_model(&game, &mut conn).await?);
+        if let Some(user_id) = dropped {
+            messages.push(InternalServerMessage {
+                destination: MessageDestination::User(user_id),
+                message: ServerMessage::Game(Box::new(GameUpdate::PremoveCleared(GameId(
+                    game.nanoid.clone(),
+                )))),
+            });
+        }
+        let mut response = None;
+        for (turn, user_id, game, state) in played {
+            let game_response = Self::response(&game, state, &cached, &mut conn).await?;
+            let username = if user_id == self.user_id {
+                self.username.to_owned()
+            } else if user_id == game.white_id {
+                cached.white_player.username.clone()
+            } else {
+                cached.black_player.username.clone()
+            };
+            response = Some(game_response.clone());
+            messages.push(InternalServerMessage {
+                destination: MessageDestination::Game(GameId(self.game.nanoid.clone())),
+                message: ServerMessage::Game(Box::new(GameUpdate::Reaction(GameActionResponse {
+                    game_id: GameId(game.nanoid.to_owned()),
+                    game: game_response,
+                    game_action: GameReaction::Turn(turn),
+                    user_id,
+                    username,
+                }))),
+            });
+        }
+        // Only this game changed for whoever is to move, the client adds it to their urgent ones
+        if let (false, Some(response)) = (game.finished, &response) {
+            messages.push(InternalServerMessage {
+                destination: MessageDestination::User(game.current_player_id),
+                message: ServerMessage::Game(Box::new(GameUpdate::Urgent(vec![response.clone()]))),
+            });
         }
-        messages.push(InternalServerMessage {
-            destination: MessageDestination::User(game.current_player_id),
-            message: ServerMessage::Game(Box::new(GameUpdate::Urgent(game_responses))),
-        });
-        let response = GameResponse::from_model(&game, &mut conn).await?;
-        messages.push(InternalServerMessage {
-            destination: MessageDestination::Game(GameId(self.game.nanoid.clone())),
-            message: ServerMessage::Game(Box::new(GameUpdate::Reaction(GameActionResponse {
-                game_id: GameId(game.nanoid.to_owned()),
-                game: response.clone(),
-                game_action: GameReaction::Turn(self.turn.clone()),
-                user_id: self.user_id.to_owned(),
-                username: self.username.to_owned(),
-            }))),
-        });
         // TODO: Just add the few top games and keep them rated
-        if response.time_mode == TimeMode::RealTime {
+        if let (TimeMode::RealTime, Some(response)) =
+            (TimeMode::from_str(&game.time_mode)?, response)
+        {
             messages.push(InternalServerMessage {
                 destination: MessageDestination::Global,
                 message: ServerMessage::Game(Box::new(GameUpdate::Tv(response))),
             });
         };
+        if game.finished {
+            messages.extend(armageddon_messages(&game, &mut conn).await?);
+        }
         Ok(messages)
     }
 
+    // The state only stands for the game when the turn didn't run out of time, finished games
+    // get fresh players as their ratings changed
+    async fn response(
+        game: &Game,
+        state: State,
+        cached: &CachedGame,
+        conn: &mut DbConn<'_>,
+    ) -> Result<GameResponse> {
+        if state.turn == game.turn as usize && !game.finished {
+            GameResponse::from_state(
+                game,
+                state,
+                cached.white_player.clone(),
+                cached.black_player.clone(),
+                conn,
+            )
+            .await
+        } else {
+            GameResponse::from_model(game, conn).await
+        }
+    }
+
+    // Keeps answering with the conditional moves the players prepared until
+    // one of them runs out or the game ends
+    async fn play_conditional_moves(
+        &self,
+        played: &mut Vec<(Turn, Uuid, Game, State)>,
+        mut before: State,
+        conn: &mut DbConn<'_>,
+    ) -> Result<()> {
+        while let Some((turn, _, game, state)) = played.last().cloned() {
+            if game.finished {
+                break;
+            }
+            let Some(Turn::Move(piece, position)) =
+                ConditionalMove::take_reply(&game, &before, &turn, &state, conn).await?
+            else {
+                break;
+            };
+            let mut next = state.clone();
+            if next.play_turn_from_position(piece, position).is_err() {
+                ConditionalMove::delete_for_game_and_user(&game.id, &game.current_player_id, conn)
+                    .await?;
+                break;
+            }
+            let next_game = game.update_gamestate(&next, conn).await?;
+            played.push((
+                Turn::Move(piece, position),
+                game.current_player_id,
+                next_game,
+                next,
+            ));
+            before = state;
+        }
+        Ok(())
+    }
+
+    // Plays the premove of whoever is to move next, premoves which became
+    // illegal in the meantime are dropped and their player is returned
+    async fn play_premoves(
+        &self,
+        played: &mut Vec<(Turn, Uuid, Game, State)>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Option<Uuid>> {
+        let game_id = GameId(self.game.nanoid.clone());
+        while let Some((_, _, game, state)) = played.last().cloned() {
+            if game.finished {
+                self.premoves.remove(&game_id);
+                break;
+            }
+            let Some(Turn::Move(piece, position)) =
+                self.premoves.take(&game_id, game.current_player_id)
+            else {
+                break;
+            };
+            let mut next = state.clone();
+            if next.play_turn_from_position(piece, position).is_err() {
+                return Ok(Some(game.current_player_id));
+            }
+            let next_game = game.update_gamestate_premove(&next, conn).await?;
+            played.push((
+                Turn::Move(piece, position),
+                game.current_player_id,
+                next_game,
+                next,
+            ));
+        }
+        Ok(None)
+    }
+
     fn users_turn(&self) -> Result<()> {
         if !((self.game.turn % 2 == 0 && self.game.white_id == self.user_id)
             || (self.game.turn % 2 == 1 && self.game.black_id == self.user_id))
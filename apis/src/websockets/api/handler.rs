@@ -1,18 +1,22 @@
 use super::chat::handler::ChatHandler;
 use super::game::handler::GameActionHandler;
 use super::search::handler::UserSearchHandler;
-use crate::common::{ClientRequest, GameAction};
+use crate::common::{ClientRequest, GameAction, QuickPairAction, TournamentAction};
 use crate::websockets::api::challenges::handler::ChallengeHandler;
 use crate::websockets::api::ping::handler::PingHandler;
 use crate::websockets::api::tournaments::handler::TournamentHandler;
 use crate::websockets::api::user_status::handler::UserStatusHandler;
 use crate::websockets::auth_error::AuthError;
 use crate::websockets::chat::Chats;
+use crate::websockets::game_states::GameStates;
 use crate::websockets::internal_server_message::InternalServerMessage;
 use crate::websockets::messages::WsMessage;
+use crate::websockets::premoves::Premoves;
+use crate::websockets::presence::Presence;
+use crate::websockets::rematch_offers::RematchOffers;
 use crate::websockets::tournament_game_start::TournamentGameStart;
 use anyhow::Result;
-use db_lib::DbPool;
+use db_lib::{get_conn, models::User, DbPool};
 use shared_types::{ChatDestination, SimpleUser};
 use uuid::Uuid;
 
@@ -20,12 +24,17 @@ pub struct RequestHandler {
     command: ClientRequest,
     chat_storage: actix_web::web::Data<Chats>,
     game_start: actix_web::web::Data<TournamentGameStart>,
+    rematch_offers: actix_web::web::Data<RematchOffers>,
+    premoves: actix_web::web::Data<Premoves>,
+    game_states: actix_web::web::Data<GameStates>,
+    presence: actix_web::web::Data<Presence>,
     received_from: actix::Recipient<WsMessage>, // This is the socket the message was received over
     pool: DbPool,
     user_id: Uuid,
     username: String,
     authed: bool,
     admin: bool,
+    guest: bool,
 }
 
 impl RequestHandler {
@@ -33,6 +42,10 @@ impl RequestHandler {
         command: ClientRequest,
         chat_storage: actix_web::web::Data<Chats>,
         game_start: actix_web::web::Data<TournamentGameStart>,
+        rematch_offers: actix_web::web::Data<RematchOffers>,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
+        presence: actix_web::web::Data<Presence>,
         sender_addr: actix::Recipient<WsMessage>,
         user: SimpleUser,
         pool: DbPool,
@@ -42,11 +55,16 @@ impl RequestHandler {
             command,
             chat_storage,
             game_start,
+            rematch_offers,
+            premoves,
+            game_states,
+            presence,
             pool,
             user_id: user.user_id,
             username: user.username,
             authed: user.authed,
             admin: user.admin,
+            guest: user.guest,
         }
     }
 
@@ -64,6 +82,26 @@ impl RequestHandler {
         Ok(())
     }
 
+    fn ensure_registered(&self) -> Result<()> {
+        if self.guest {
+            Err(AuthError::RegistrationRequired)?
+        }
+        Ok(())
+    }
+
+    // Bans and deletions also reach connections that were opened before them
+    async fn ensure_not_banned(&self) -> Result<()> {
+        let mut conn = get_conn(&self.pool).await?;
+        let user = User::find_by_uuid(&self.user_id, &mut conn).await?;
+        if user.deleted {
+            Err(AuthError::Unauthorized)?
+        }
+        if user.is_banned() {
+            Err(AuthError::Banned)?
+        }
+        Ok(())
+    }
+
     pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
         let messages = match self.command.clone() {
             ClientRequest::UserSearch(pattern) => {
@@ -73,6 +111,7 @@ impl RequestHandler {
             }
             ClientRequest::Chat(message_container) => {
                 self.ensure_auth()?;
+                self.ensure_not_banned().await?;
                 if self.user_id != message_container.message.user_id {
                     Err(AuthError::Unauthorized)?
                 }
@@ -82,11 +121,20 @@ impl RequestHandler {
                 ChatHandler::new(message_container, self.chat_storage.clone()).handle()
             }
             ClientRequest::Tournament(tournament_action) => {
+                match tournament_action {
+                    TournamentAction::Get(_) | TournamentAction::GetAll => {}
+                    _ => {
+                        self.ensure_registered()?;
+                        self.ensure_not_banned().await?;
+                    }
+                };
                 TournamentHandler::new(
                     tournament_action,
                     &self.username,
                     self.user_id,
                     self.chat_storage.clone(),
+                    self.premoves.clone(),
+                    self.game_states.clone(),
                     &self.pool,
                 )
                 .await?
@@ -99,7 +147,14 @@ impl RequestHandler {
                 game_id,
             } => {
                 match game_action {
-                    GameAction::Turn(_) | GameAction::Control(_) => self.ensure_auth()?,
+                    GameAction::Turn(_)
+                    | GameAction::Control(_)
+                    | GameAction::Premove(_)
+                    | GameAction::CancelPremove
+                    | GameAction::Rematch(_) => {
+                        self.ensure_auth()?;
+                        self.ensure_not_banned().await?;
+                    }
                     _ => {}
                 };
                 GameActionHandler::new(
@@ -109,6 +164,10 @@ impl RequestHandler {
                     self.received_from.clone(),
                     self.chat_storage.clone(),
                     self.game_start.clone(),
+                    self.rematch_offers.clone(),
+                    self.premoves.clone(),
+                    self.game_states.clone(),
+                    self.presence.clone(),
                     &self.pool,
                 )
                 .await?
@@ -117,12 +176,31 @@ impl RequestHandler {
             }
             ClientRequest::Challenge(challenge_action) => {
                 self.ensure_auth()?;
-                ChallengeHandler::new(challenge_action, &self.username, self.user_id, &self.pool)
-                    .await?
-                    .handle()
-                    .await?
+                self.ensure_not_banned().await?;
+                ChallengeHandler::new(
+                    challenge_action,
+                    &self.username,
+                    self.user_id,
+                    self.guest,
+                    &self.pool,
+                )
+                .await?
+                .handle()
+                .await?
             }
             ClientRequest::Away => UserStatusHandler::new().await?.handle().await?,
+            // The WsConnection hands it to the lobby once it got through here
+            ClientRequest::QuickPair(action) => {
+                self.ensure_auth()?;
+                if let QuickPairAction::Join(details) = action {
+                    // Like challenges, rated games are for registered players
+                    if details.rated {
+                        self.ensure_registered()?;
+                    }
+                    self.ensure_not_banned().await?;
+                }
+                Vec::new()
+            }
         };
         Ok(messages)
     }
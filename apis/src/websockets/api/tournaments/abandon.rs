@@ -1,7 +1,11 @@
 use crate::{
     common::{GameActionResponse, GameReaction, GameUpdate, ServerMessage, TournamentUpdate},
     responses::{GameResponse, TournamentResponse},
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        game_states::GameStates,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+        premoves::Premoves,
+    },
 };
 use anyhow::Result;
 use db_lib::{db_error::DbError, get_conn, models::Tournament, DbPool};
@@ -15,6 +19,8 @@ pub struct AbandonHandler {
     tournament_id: TournamentId,
     user_id: Uuid,
     username: String,
+    premoves: actix_web::web::Data<Premoves>,
+    game_states: actix_web::web::Data<GameStates>,
     pool: DbPool,
 }
 
@@ -23,12 +29,16 @@ impl AbandonHandler {
         tournament_id: TournamentId,
         user_id: Uuid,
         username: String,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
         pool: &DbPool,
     ) -> Result<Self> {
         Ok(Self {
             tournament_id,
             user_id,
             username,
+            premoves,
+            game_states,
             pool: pool.clone(),
         })
     }
@@ -57,6 +67,8 @@ impl AbandonHandler {
             .await?;
 
         for game in abandoned {
+            self.premoves.remove(&GameId(game.nanoid.clone()));
+            self.game_states.invalidate(&GameId(game.nanoid.clone()));
             let game_response = GameResponse::from_model(&game, &mut conn).await?;
             let color = game
                 .user_color(self.user_id)
@@ -1,7 +1,10 @@
 use crate::{
     common::{ServerMessage, TournamentUpdate},
     responses::TournamentResponse,
-    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+    websockets::{
+        api::tournaments::armageddon::armageddon_messages,
+        internal_server_message::{InternalServerMessage, MessageDestination},
+    },
 };
 use anyhow::Result;
 use db_lib::{
@@ -38,14 +41,15 @@ impl AdjudicateResultHandler {
 
     pub async fn handle(&self) -> Result<Vec<InternalServerMessage>> {
         let mut conn = get_conn(&self.pool).await?;
-        let tournament = conn
+        let (game, tournament) = conn
             .transaction::<_, anyhow::Error, _>(move |tc| {
                 async move {
                     let game = Game::find_by_game_id(&self.game_id, tc).await?;
-                    game.adjudicate_tournament_result(&self.user_id, &self.new_result, tc)
+                    let game = game
+                        .adjudicate_tournament_result(&self.user_id, &self.new_result, tc)
                         .await?;
                     let id = game.tournament_id.expect("Have a tournament_id");
-                    Ok(Tournament::find(id, tc).await?)
+                    Ok((game, Tournament::find(id, tc).await?))
                 }
                 .scope_boxed()
             })
@@ -53,9 +57,11 @@ impl AdjudicateResultHandler {
 
         let response = TournamentResponse::from_model(&tournament, &mut conn).await?;
 
-        Ok(vec![InternalServerMessage {
+        let mut messages = vec![InternalServerMessage {
             destination: MessageDestination::Global,
             message: ServerMessage::Tournament(TournamentUpdate::Modified(response)),
-        }])
+        }];
+        messages.extend(armageddon_messages(&game, &mut conn).await?);
+        Ok(messages)
     }
 }
@@ -0,0 +1,49 @@
+use crate::{
+    common::{GameActionResponse, GameReaction, GameUpdate, ServerMessage, TournamentUpdate},
+    responses::{GameResponse, TournamentResponse},
+    websockets::internal_server_message::{InternalServerMessage, MessageDestination},
+};
+use anyhow::Result;
+use db_lib::{
+    models::{Game, Tournament},
+    DbConn,
+};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+
+// Whoever settles the result of a tournament game also starts the armageddon of a match it left tied
+pub async fn armageddon_messages(
+    game: &Game,
+    conn: &mut DbConn<'_>,
+) -> Result<Vec<InternalServerMessage>> {
+    if game.tournament_id.is_none() {
+        return Ok(Vec::new());
+    }
+    let created = conn
+        .transaction::<_, anyhow::Error, _>(move |tc| {
+            async move { Ok(Tournament::armageddon_after(game, tc).await?) }.scope_boxed()
+        })
+        .await?;
+    let Some((tournament, armageddon)) = created else {
+        return Ok(Vec::new());
+    };
+    let tournament_response = TournamentResponse::from_model(&tournament, conn).await?;
+    let game_response = GameResponse::from_model(&armageddon, conn).await?;
+    let mut messages = vec![InternalServerMessage {
+        destination: MessageDestination::Global,
+        message: ServerMessage::Tournament(TournamentUpdate::Modified(tournament_response)),
+    }];
+    for player in [&game_response.white_player, &game_response.black_player] {
+        messages.push(InternalServerMessage {
+            destination: MessageDestination::User(player.uid),
+            message: ServerMessage::Game(Box::new(GameUpdate::Reaction(GameActionResponse {
+                game_action: GameReaction::New,
+                game: game_response.clone(),
+                game_id: game_response.game_id.clone(),
+                user_id: player.uid,
+                username: player.username.clone(),
+            }))),
+        });
+    }
+    Ok(messages)
+}
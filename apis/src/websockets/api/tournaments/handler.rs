@@ -7,7 +7,10 @@ use super::{
 };
 use crate::{
     common::TournamentAction,
-    websockets::{chat::Chats, internal_server_message::InternalServerMessage},
+    websockets::{
+        chat::Chats, game_states::GameStates, internal_server_message::InternalServerMessage,
+        premoves::Premoves,
+    },
 };
 use anyhow::Result;
 use db_lib::DbPool;
@@ -19,6 +22,8 @@ pub struct TournamentHandler {
     pub user_id: Uuid,
     pub username: String,
     pub chat_storage: actix_web::web::Data<Chats>,
+    pub premoves: actix_web::web::Data<Premoves>,
+    pub game_states: actix_web::web::Data<GameStates>,
 }
 
 impl TournamentHandler {
@@ -27,6 +32,8 @@ impl TournamentHandler {
         username: &str,
         user_id: Uuid,
         chat_storage: actix_web::web::Data<Chats>,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
         pool: &DbPool,
     ) -> Result<Self> {
         Ok(Self {
@@ -35,6 +42,8 @@ impl TournamentHandler {
             user_id,
             username: username.to_owned(),
             chat_storage,
+            premoves,
+            game_states,
         })
     }
 
@@ -128,6 +137,8 @@ impl TournamentHandler {
                     tournament_id,
                     self.user_id,
                     self.username.clone(),
+                    self.premoves.clone(),
+                    self.game_states.clone(),
                     &self.pool,
                 )
                 .await?
@@ -4,4 +4,8 @@ use serde::{Deserialize, Serialize};
 pub enum AuthError {
     #[error("You are not authorized to perform that action")]
     Unauthorized,
+    #[error("Guests can only play casual games, sign up to play rated games and tournaments")]
+    RegistrationRequired,
+    #[error("Your account is suspended")]
+    Banned,
 }
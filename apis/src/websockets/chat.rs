@@ -50,9 +50,37 @@ impl Chats {
         }
     }
 
+    // Everything the user wrote and every direct conversation they are part of
+    pub fn messages_of(&self, user_id: Uuid) -> Vec<ChatMessageContainer> {
+        let mut messages = written_by(user_id, &self.tournament.read().unwrap());
+        messages.extend(written_by(user_id, &self.games_public.read().unwrap()));
+        messages.extend(written_by(user_id, &self.games_private.read().unwrap()));
+        messages.extend(
+            self.direct
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|(users, _)| users.id.0 == user_id || users.id.1 == user_id)
+                .flat_map(|(_, chat)| chat.iter().cloned()),
+        );
+        messages
+    }
+
     pub fn insert_or_update_direct_lookup(&self, id1: Uuid, id2: Uuid) {
         let mut direct_lookup = self.direct_lookup.write().unwrap();
         direct_lookup.entry(id1).or_default().insert(id2);
         direct_lookup.entry(id2).or_default().insert(id1);
     }
 }
+
+fn written_by<K>(
+    user_id: Uuid,
+    chats: &HashMap<K, Vec<ChatMessageContainer>>,
+) -> Vec<ChatMessageContainer> {
+    chats
+        .values()
+        .flatten()
+        .filter(|container| container.message.user_id == user_id)
+        .cloned()
+        .collect()
+}
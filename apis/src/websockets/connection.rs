@@ -1,10 +1,13 @@
-use super::tournament_game_start::TournamentGameStart;
 use super::{api::handler::RequestHandler, internal_server_message::MessageDestination};
-use crate::common::{ClientRequest, ExternalServerError, ServerResult};
+use super::{
+    game_states::GameStates, premoves::Premoves, presence::Presence, rematch_offers::RematchOffers,
+    timers::Timers, tournament_game_start::TournamentGameStart,
+};
+use crate::common::{ClientRequest, ExternalServerError, GameUpdate, ServerMessage, ServerResult};
 use crate::websockets::{
     chat::Chats,
     lobby::Lobby,
-    messages::{ClientActorMessage, Connect, Disconnect, WsMessage},
+    messages::{ClientActorMessage, CloseConnection, Connect, Disconnect, QuickPair, WsMessage},
 };
 use actix::{
     fut, Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, ContextFutureSpawner, Handler,
@@ -25,8 +28,14 @@ pub struct WsConnection {
     username: String,
     authed: bool,
     admin: bool,
+    guest: bool,
     chat_storage: actix_web::web::Data<Chats>,
     game_start: actix_web::web::Data<TournamentGameStart>,
+    rematch_offers: actix_web::web::Data<RematchOffers>,
+    premoves: actix_web::web::Data<Premoves>,
+    game_states: actix_web::web::Data<GameStates>,
+    presence: actix_web::web::Data<Presence>,
+    timers: actix_web::web::Data<Timers>,
     lobby_addr: Addr<Lobby>,
     hb: Instant, // websocket heartbeat
     pool: DbPool,
@@ -40,7 +49,7 @@ impl Actor for WsConnection {
         let addr = ctx.address();
         self.lobby_addr
             .send(Connect {
-                addr: addr.recipient(),
+                addr,
                 game_id: String::from("lobby"), // self.game_id
                 user_id: self.user_uid,
                 username: self.username.clone(),
@@ -60,7 +69,7 @@ impl Actor for WsConnection {
         self.lobby_addr.do_send(Disconnect {
             user_id: self.user_uid,
             game_id: String::from("lobby"),
-            addr: ctx.address().recipient(),
+            addr: ctx.address(),
             username: self.username.clone(),
         });
         Running::Stop
@@ -72,19 +81,32 @@ impl WsConnection {
         user_uid: Option<Uuid>,
         username: Option<String>,
         admin: Option<bool>,
+        guest: Option<bool>,
         lobby: Addr<Lobby>,
         chat_storage: actix_web::web::Data<Chats>,
         game_start: actix_web::web::Data<TournamentGameStart>,
+        rematch_offers: actix_web::web::Data<RematchOffers>,
+        premoves: actix_web::web::Data<Premoves>,
+        game_states: actix_web::web::Data<GameStates>,
+        presence: actix_web::web::Data<Presence>,
+        timers: actix_web::web::Data<Timers>,
         pool: DbPool,
     ) -> WsConnection {
         let id = user_uid.unwrap_or(Uuid::new_v4());
         let name = username.unwrap_or(id.to_string());
         let admin = admin.unwrap_or_default();
+        let guest = guest.unwrap_or_default();
         WsConnection {
             user_uid: id,
             username: name,
             admin,
+            guest,
             game_start,
+            rematch_offers,
+            premoves,
+            game_states,
+            presence,
+            timers,
             authed: user_uid.is_some(),
             chat_storage,
             hb: Instant::now(),
@@ -139,9 +161,15 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsConnection {
                     username: username.clone(),
                     authed: self.authed,
                     admin: self.admin,
+                    guest: self.guest,
                 };
                 let chat_storage = self.chat_storage.clone();
                 let game_start = self.game_start.clone();
+                let rematch_offers = self.rematch_offers.clone();
+                let premoves = self.premoves.clone();
+                let game_states = self.game_states.clone();
+                let presence = self.presence.clone();
+                let timers = self.timers.clone();
                 let addr = ctx.address().recipient();
 
                 let future = async move {
@@ -149,6 +177,10 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsConnection {
                         request.clone(),
                         chat_storage,
                         game_start,
+                        rematch_offers,
+                        premoves,
+                        game_states,
+                        presence,
                         addr,
                         user,
                         pool,
@@ -156,7 +188,21 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsConnection {
                     let handler_result = handler.handle().await;
                     match handler_result {
                         Ok(messages) => {
+                            // The quick pairing pool lives in the lobby
+                            if let ClientRequest::QuickPair(action) = &request {
+                                lobby.do_send(QuickPair {
+                                    user_id,
+                                    username: username.clone(),
+                                    action: action.clone(),
+                                });
+                            }
                             for message in messages {
+                                // Keep the clocks in step with every game the request changed
+                                if let ServerMessage::Game(update) = &message.message {
+                                    if let GameUpdate::Reaction(gar) = update.as_ref() {
+                                        timers.update(&gar.game);
+                                    }
+                                }
                                 let serialized = serde_json::to_string(&ServerResult::Ok(
                                     Box::new(message.message),
                                 ))
@@ -215,3 +261,15 @@ impl Handler<WsMessage> for WsConnection {
         ctx.text(msg.0);
     }
 }
+
+impl Handler<CloseConnection> for WsConnection {
+    type Result = ();
+
+    fn handle(&mut self, _msg: CloseConnection, ctx: &mut Self::Context) {
+        ctx.close(Some(ws::CloseReason {
+            code: ws::CloseCode::Policy,
+            description: Some(String::from("This account can't be used anymore")),
+        }));
+        ctx.stop();
+    }
+}
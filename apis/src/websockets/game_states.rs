@@ -0,0 +1,93 @@
+use crate::responses::UserResponse;
+use anyhow::Result;
+use db_lib::{models::Game, DbConn};
+use hive_lib::State;
+use shared_types::GameId;
+use std::{collections::HashMap, sync::RwLock};
+
+#[derive(Clone, Debug)]
+pub struct CachedGame {
+    pub game: Game,
+    pub state: State,
+    pub white_player: UserResponse,
+    pub black_player: UserResponse,
+}
+
+// The row, engine state and players of running games as the last turn left them, so that a
+// turn doesn't have to go to the database or replay the whole history. Anything else that writes
+// to a running game has to invalidate it. Anything missing, e.g. after a restart, gets restored
+// from the database
+#[derive(Debug)]
+pub struct GameStates {
+    games: RwLock<HashMap<GameId, CachedGame>>,
+}
+
+impl Default for GameStates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameStates {
+    pub fn new() -> Self {
+        Self {
+            games: RwLock::new(HashMap::new()),
+        }
+    }
+
+    // The cached game, as long as it is at the same turn as the one in the database
+    pub fn get(&self, game: &Game) -> Option<CachedGame> {
+        let games = self.games.read().ok()?;
+        games
+            .get(&GameId(game.nanoid.clone()))
+            .filter(|cached| cached.state.turn == game.turn as usize)
+            .cloned()
+    }
+
+    // The running game as the last turn left it
+    pub fn running(&self, game_id: &GameId) -> Option<Game> {
+        let games = self.games.read().ok()?;
+        games.get(game_id).map(|cached| cached.game.clone())
+    }
+
+    pub async fn load(&self, game: &Game, conn: &mut DbConn<'_>) -> Result<CachedGame> {
+        if let Some(cached) = self.get(game) {
+            return Ok(cached);
+        }
+        let cached = CachedGame {
+            game: game.clone(),
+            state: State::new_from_str_with(
+                &game.history,
+                &game.game_type,
+                game.automatic_repetition(),
+            )?,
+            white_player: UserResponse::from_uuid(&game.white_id, conn).await?,
+            black_player: UserResponse::from_uuid(&game.black_id, conn).await?,
+        };
+        if !game.finished {
+            if let Ok(mut games) = self.games.write() {
+                games.insert(GameId(game.nanoid.clone()), cached.clone());
+            }
+        }
+        Ok(cached)
+    }
+
+    // Moves the cached game on to the state of the game after a turn, finished games get dropped
+    pub fn advance(&self, game: &Game, state: &State) {
+        if let Ok(mut games) = self.games.write() {
+            let game_id = GameId(game.nanoid.clone());
+            if game.finished || state.turn != game.turn as usize {
+                games.remove(&game_id);
+            } else if let Some(cached) = games.get_mut(&game_id) {
+                cached.game = game.clone();
+                cached.state = state.clone();
+            }
+        }
+    }
+
+    pub fn invalidate(&self, game_id: &GameId) {
+        if let Ok(mut games) = self.games.write() {
+            games.remove(game_id);
+        }
+    }
+}
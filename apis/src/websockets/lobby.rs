@@ -1,46 +1,78 @@
-use super::internal_server_message::MessageDestination;
+use super::connection::WsConnection;
+use super::game_states::GameStates;
+use super::internal_server_message::{InternalServerMessage, MessageDestination};
 use super::messages::GameHB;
+use super::premoves::Premoves;
+use super::presence::Presence;
+use super::quick_pair::{QuickPairPool, Seeker};
+use super::rematch_offers::RematchOffers;
+use super::timers::Timers;
 use crate::{
     common::{
-        ChallengeUpdate, GameUpdate, ServerMessage, ServerResult, TournamentUpdate, UserStatus,
+        ChallengeUpdate, GameActionResponse, GameReaction, GameUpdate, QuickPairAction,
+        QuickPairUpdate, RematchUpdate, ServerMessage, ServerResult, TournamentUpdate, UserStatus,
         UserUpdate,
     },
-    responses::{
-        ChallengeResponse, GameResponse, HeartbeatResponse, TournamentResponse, UserResponse,
+    resignations::GamesResigned,
+    responses::{ChallengeResponse, GameResponse, TournamentResponse, UserResponse},
+    sessions::EndSessions,
+    websockets::messages::{
+        ClientActorMessage, CloseConnection, Connect, Disconnect, QuickPair, WsMessage,
     },
-    websockets::messages::{ClientActorMessage, Connect, Disconnect, WsMessage},
 };
 use actix::{
-    prelude::{Actor, Context, Handler, Recipient},
-    AsyncContext, WrapFuture,
+    prelude::{Actor, Addr, Context, Handler},
+    ActorFutureExt, AsyncContext, WrapFuture,
 };
+use actix_web::web::Data;
 use db_lib::{
     get_conn,
-    models::{Challenge, Game, Tournament, TournamentInvitation, User},
+    models::{
+        Challenge, Game, NewChallenge, NewGame, Rating, Tournament, TournamentInvitation, User,
+    },
     DbPool,
 };
 use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
-use hive_lib::GameStatus;
-use shared_types::{GameId, TimeMode};
+use hive_lib::{Color, GameControl};
+use shared_types::{ChallengeId, GameId};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 #[derive(Debug)]
 pub struct Lobby {
     id: String,
-    sessions: HashMap<Uuid, Vec<Recipient<WsMessage>>>, // user_id to (socket_)id
-    games_users: HashMap<GameId, HashSet<Uuid>>,        // game_id to set of users
-    users_games: HashMap<Uuid, HashSet<String>>,        // user_id to set of games
+    sessions: HashMap<Uuid, Vec<Addr<WsConnection>>>, // user_id to (socket_)id
+    games_users: HashMap<GameId, HashSet<Uuid>>,      // game_id to set of users
+    users_games: HashMap<Uuid, HashSet<String>>,      // user_id to set of games
+    quick_pair: QuickPairPool,
+    timers: Data<Timers>,
+    presence: Data<Presence>,
+    rematch_offers: Data<RematchOffers>,
+    premoves: Data<Premoves>,
+    game_states: Data<GameStates>,
     pool: DbPool,
 }
 
 impl Lobby {
-    pub fn new(pool: DbPool) -> Lobby {
+    pub fn new(
+        pool: DbPool,
+        timers: Data<Timers>,
+        presence: Data<Presence>,
+        rematch_offers: Data<RematchOffers>,
+        premoves: Data<Premoves>,
+        game_states: Data<GameStates>,
+    ) -> Lobby {
         Lobby {
             id: String::from("lobby"),
             sessions: HashMap::new(),
             games_users: HashMap::new(),
             users_games: HashMap::new(),
+            quick_pair: QuickPairPool::default(),
+            timers,
+            presence,
+            rematch_offers,
+            premoves,
+            game_states,
             pool,
         }
     }
@@ -54,57 +86,254 @@ impl Lobby {
             }
         }
     }
+
+    fn send_quick_pair_update(&self, update: QuickPairUpdate, id_to: &Uuid) {
+        let message = ServerResult::Ok(Box::new(ServerMessage::QuickPair(update)));
+        let serialized =
+            serde_json::to_string(&message).expect("Failed to serialize a server message");
+        self.send_message(&serialized, id_to);
+    }
+
+    fn pair_quick_pair_seekers(&mut self, ctx: &mut Context<Self>) {
+        for (one, two) in self.quick_pair.pairs() {
+            let pool = self.pool.clone();
+            let address = ctx.address().clone();
+            let future = async move {
+                let Ok(mut conn) = get_conn(&pool).await else {
+                    return;
+                };
+                let (white, black) = if rand::random() {
+                    (one, two)
+                } else {
+                    (two, one)
+                };
+                let details = white.details.challenge_details();
+                let (white_id, black_id) = (white.user_id, black.user_id);
+                // Quick pairing goes through a challenge between the two players
+                // so that the game gets created exactly like an accepted one
+                let created = conn
+                    .transaction::<_, anyhow::Error, _>(move |tc| {
+                        async move {
+                            let new_challenge =
+                                NewChallenge::new(white_id, Some(black_id), &details)?;
+                            let challenge = Challenge::create(&new_challenge, tc).await?;
+                            let new_game = NewGame::new(white_id, black_id, &challenge);
+                            let (game, deleted_challenges) =
+                                Game::create_and_delete_challenges(new_game, tc).await?;
+                            let game_response = GameResponse::from_model(&game, tc).await?;
+                            Ok((game, deleted_challenges, game_response))
+                        }
+                        .scope_boxed()
+                    })
+                    .await;
+                let mut messages = Vec::new();
+                match created {
+                    Ok((game, deleted_challenges, game_response)) => {
+                        for player in [&white, &black] {
+                            messages.push(InternalServerMessage {
+                                destination: MessageDestination::User(player.user_id),
+                                message: ServerMessage::QuickPair(QuickPairUpdate::Stopped),
+                            });
+                            messages.push(InternalServerMessage {
+                                destination: MessageDestination::User(player.user_id),
+                                message: ServerMessage::Game(Box::new(GameUpdate::Reaction(
+                                    GameActionResponse {
+                                        game_action: GameReaction::New,
+                                        game: game_response.clone(),
+                                        game_id: game_response.game_id.clone(),
+                                        user_id: player.user_id,
+                                        username: player.username.clone(),
+                                    },
+                                ))),
+                            });
+                        }
+                        for challenge_nanoid in deleted_challenges {
+                            if challenge_nanoid != ChallengeId(game.nanoid.clone()) {
+                                messages.push(InternalServerMessage {
+                                    destination: MessageDestination::Global,
+                                    message: ServerMessage::Challenge(ChallengeUpdate::Removed(
+                                        challenge_nanoid,
+                                    )),
+                                });
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("Failed to create a quick pairing game: {e}");
+                        for player in [&white, &black] {
+                            messages.push(InternalServerMessage {
+                                destination: MessageDestination::User(player.user_id),
+                                message: ServerMessage::QuickPair(QuickPairUpdate::Stopped),
+                            });
+                        }
+                    }
+                }
+                for message in messages {
+                    let serialized =
+                        serde_json::to_string(&ServerResult::Ok(Box::new(message.message)))
+                            .expect("Failed to serialize a server message");
+                    address.do_send(ClientActorMessage {
+                        destination: message.destination,
+                        serialized,
+                        from: None,
+                    });
+                }
+            };
+            ctx.spawn(future.into_actor(self));
+        }
+    }
 }
 
 impl Actor for Lobby {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.run_interval(QuickPairPool::INTERVAL, |lobby, ctx| {
+            lobby.pair_quick_pair_seekers(ctx)
+        });
+    }
 }
 
-impl Handler<GameHB> for Lobby {
+impl Handler<QuickPair> for Lobby {
     type Result = ();
 
-    fn handle(&mut self, _msg: GameHB, ctx: &mut Context<Self>) {
-        for (game_id, user_ids) in self.games_users.clone() {
-            if game_id.0.as_str() == "lobby" {
-                continue;
+    fn handle(&mut self, msg: QuickPair, ctx: &mut Context<Self>) {
+        match msg.action {
+            QuickPairAction::Leave => {
+                self.quick_pair.leave(&msg.user_id);
+                self.send_quick_pair_update(QuickPairUpdate::Stopped, &msg.user_id);
             }
-            let pool = self.pool.clone();
-            let sessions = self.sessions.clone();
-            let future = async move {
-                if let Ok(mut conn) = get_conn(&pool).await {
-                    if let Ok(game) = Game::find_by_game_id(&game_id, &mut conn).await {
-                        if game.game_status == GameStatus::InProgress.to_string()
-                            && game.time_mode != TimeMode::Untimed.to_string()
-                        {
-                            if let Ok((id, white, black)) = game.get_heartbeat() {
-                                let hb = HeartbeatResponse {
-                                    game_id: id,
-                                    white_time_left: white,
-                                    black_time_left: black,
-                                };
-                                let message = ServerResult::Ok(Box::new(ServerMessage::Game(
-                                    Box::new(GameUpdate::Heartbeat(hb)),
-                                )));
-                                let serialized = serde_json::to_string(&message)
-                                    .expect("Failed to serialize a server message");
-                                for user_id in user_ids {
-                                    if let Some(sockets) = sessions.get(&user_id) {
-                                        for socket in sockets {
-                                            socket.do_send(WsMessage(serialized.clone()));
-                                        }
-                                    }
-                                }
-                            }
+            QuickPairAction::Join(details) => {
+                let (user_id, username) = (msg.user_id, msg.username);
+                let pool = self.pool.clone();
+                let future = async move {
+                    let mut conn = get_conn(&pool).await.ok()?;
+                    Rating::for_uuid(&user_id, &details.speed(), &details.game_type, &mut conn)
+                        .await
+                        .ok()
+                        .map(|rating| rating.rating)
+                };
+                let actor_future = future.into_actor(self).map(move |rating, lobby, _| {
+                    // The user might have disconnected while we fetched the rating
+                    match rating {
+                        Some(rating) if lobby.sessions.contains_key(&user_id) => {
+                            lobby
+                                .quick_pair
+                                .join(Seeker::new(user_id, &username, details, rating));
+                            lobby.send_quick_pair_update(
+                                QuickPairUpdate::Searching(details),
+                                &user_id,
+                            );
                         }
+                        _ => lobby.send_quick_pair_update(QuickPairUpdate::Stopped, &user_id),
                     }
+                });
+                ctx.spawn(actor_future);
+            }
+        }
+    }
+}
+
+impl Handler<GameHB> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, _msg: GameHB, _ctx: &mut Context<Self>) {
+        for (game_id, user_ids) in self.games_users.iter() {
+            if let Some(hb) = self.timers.heartbeat(game_id) {
+                let message = ServerResult::Ok(Box::new(ServerMessage::Game(Box::new(
+                    GameUpdate::Heartbeat(hb),
+                ))));
+                let serialized =
+                    serde_json::to_string(&message).expect("Failed to serialize a server message");
+                for user_id in user_ids {
+                    self.send_message(&serialized, user_id);
                 }
-            };
-            let actor_future = future.into_actor(self);
-            ctx.wait(actor_future);
+            }
+        }
+    }
+}
+
+impl Handler<EndSessions> for Lobby {
+    type Result = ();
+
+    // The connections disconnect as they close, which cleans up after them
+    fn handle(&mut self, msg: EndSessions, _: &mut Context<Self>) {
+        if let Some(sockets) = self.sessions.get(&msg.user_id) {
+            for socket in sockets {
+                socket.do_send(CloseConnection);
+            }
         }
     }
 }
 
+impl Handler<GamesResigned> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: GamesResigned, ctx: &mut Context<Self>) {
+        for game_id in &msg.game_ids {
+            self.timers.remove(game_id);
+            self.premoves.remove(game_id);
+            self.game_states.invalidate(game_id);
+        }
+        let pool = self.pool.clone();
+        let address = ctx.address();
+        let future = async move {
+            let Ok(mut conn) = get_conn(&pool).await else {
+                println!("Failed to announce resignations of {}", msg.user_id);
+                return;
+            };
+            for game_id in msg.game_ids {
+                let game = match Game::find_by_game_id(&game_id, &mut conn).await {
+                    Ok(game) => game,
+                    Err(e) => {
+                        println!("Failed to load resigned game {game_id}: {e}");
+                        continue;
+                    }
+                };
+                let Some(color) = game.user_color(msg.user_id) else {
+                    continue;
+                };
+                let game_response = match GameResponse::from_model(&game, &mut conn).await {
+                    Ok(game_response) => game_response,
+                    Err(e) => {
+                        println!("Failed to load resigned game {game_id}: {e}");
+                        continue;
+                    }
+                };
+                let resigned = if color == Color::White {
+                    &game_response.white_player
+                } else {
+                    &game_response.black_player
+                };
+                let message = ServerResult::Ok(Box::new(ServerMessage::Game(Box::new(
+                    GameUpdate::Reaction(GameActionResponse {
+                        game_action: GameReaction::Control(GameControl::Resign(color)),
+                        game_id: game_id.clone(),
+                        user_id: resigned.uid,
+                        username: resigned.username.clone(),
+                        game: game_response.clone(),
+                    }),
+                ))));
+                let serialized =
+                    serde_json::to_string(&message).expect("Failed to serialize a server message");
+                // The players hear about it wherever they are, spectators on the game
+                for destination in [
+                    MessageDestination::User(game.white_id),
+                    MessageDestination::User(game.black_id),
+                    MessageDestination::GameSpectators(game_id, game.white_id, game.black_id),
+                ] {
+                    address.do_send(ClientActorMessage {
+                        destination,
+                        serialized: serialized.clone(),
+                        from: None,
+                    });
+                }
+            }
+        };
+        ctx.spawn(future.into_actor(self));
+    }
+}
+
 impl Handler<Disconnect> for Lobby {
     type Result = ();
 
@@ -118,6 +347,18 @@ impl Handler<Disconnect> for Lobby {
         }
         // If that was the last WS connection for that user
         if !self.sessions.contains_key(&msg.user_id) {
+            self.presence.disconnected(msg.user_id);
+            self.quick_pair.leave(&msg.user_id);
+            // Leaving expires the rematch offers the user is involved in
+            for (game_id, from, to) in self.rematch_offers.expire(msg.user_id) {
+                let message = ServerResult::Ok(Box::new(ServerMessage::Game(Box::new(
+                    GameUpdate::Rematch(RematchUpdate::Withdrawn(game_id)),
+                ))));
+                let serialized =
+                    serde_json::to_string(&message).expect("Failed to serialize a server message");
+                self.send_message(&serialized, &from);
+                self.send_message(&serialized, &to);
+            }
             if let Some(games) = self.users_games.remove(&msg.user_id) {
                 for game in games.iter() {
                     let game_id = GameId(game.to_string());
@@ -168,6 +409,7 @@ impl Handler<Connect> for Lobby {
             .entry(msg.user_id)
             .or_default()
             .push(msg.addr.clone());
+        self.presence.connected(msg.user_id);
         let pool = self.pool.clone();
         let address = ctx.address().clone();
         let games_users = self.games_users.clone();
@@ -374,12 +616,13 @@ impl Handler<ClientActorMessage> for Lobby {
                         .or_default()
                         .insert(from);
                 }
-                // Send the message to everyone
-                self.games_users
-                    .get(&game_id)
-                    .expect("Game to exists")
-                    .iter()
-                    .for_each(|client| self.send_message(&cam.serialized, client));
+                // Send the message to everyone, server side updates can come for games nobody has
+                // open
+                if let Some(users) = self.games_users.get(&game_id) {
+                    users
+                        .iter()
+                        .for_each(|client| self.send_message(&cam.serialized, client));
+                }
             }
             MessageDestination::GameSpectators(game_id, white_id, black_id) => {
                 // Make sure the user is in the game:
@@ -390,15 +633,13 @@ impl Handler<ClientActorMessage> for Lobby {
                         .insert(from);
                 }
                 // Send the message to everyone except white_id and black_id
-                self.games_users
-                    .get(&game_id)
-                    .expect("Game to exists")
-                    .iter()
-                    .for_each(|user| {
+                if let Some(users) = self.games_users.get(&game_id) {
+                    users.iter().for_each(|user| {
                         if *user != white_id && *user != black_id {
                             self.send_message(&cam.serialized, user);
                         }
                     });
+                }
             }
             MessageDestination::User(user_id) => {
                 self.send_message(&cam.serialized, &user_id);
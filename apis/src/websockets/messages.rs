@@ -1,7 +1,8 @@
+use crate::common::QuickPairAction;
 use actix::prelude::*;
 use uuid::Uuid;
 
-use super::internal_server_message::MessageDestination;
+use super::{connection::WsConnection, internal_server_message::MessageDestination};
 
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
@@ -10,7 +11,7 @@ pub struct WsMessage(pub String);
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
 pub struct Connect {
-    pub addr: Recipient<WsMessage>,
+    pub addr: Addr<WsConnection>,
     pub game_id: String,
     pub user_id: Uuid,
     pub username: String,
@@ -19,16 +20,28 @@ pub struct Connect {
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
 pub struct Disconnect {
-    pub addr: Recipient<WsMessage>,
+    pub addr: Addr<WsConnection>,
     pub game_id: String,
     pub user_id: Uuid,
     pub username: String,
 }
 
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct CloseConnection;
+
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
 pub struct GameHB { }
 
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct QuickPair {
+    pub user_id: Uuid,
+    pub username: String,
+    pub action: QuickPairAction,
+}
+
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
 pub struct ClientActorMessage {
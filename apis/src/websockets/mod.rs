@@ -2,8 +2,14 @@ pub mod api;
 pub mod auth_error;
 pub mod chat;
 pub mod connection;
+pub mod game_states;
 pub mod internal_server_message;
 pub mod lobby;
 pub mod messages;
+pub mod premoves;
+pub mod presence;
+pub mod quick_pair;
+pub mod rematch_offers;
 pub mod start_connection;
+pub mod timers;
 pub mod tournament_game_start;
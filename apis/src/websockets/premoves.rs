@@ -0,0 +1,55 @@
+use hive_lib::Turn;
+use shared_types::GameId;
+use std::{collections::HashMap, sync::RwLock};
+use uuid::Uuid;
+
+// At most one queued move per game, played as soon as the opponent's turn lands
+#[derive(Debug)]
+pub struct Premoves {
+    // game_id to (queued by, turn)
+    premoves: RwLock<HashMap<GameId, (Uuid, Turn)>>,
+}
+
+impl Default for Premoves {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Premoves {
+    pub fn new() -> Self {
+        Self {
+            premoves: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn set(&self, game_id: &GameId, user_id: Uuid, turn: Turn) {
+        if let Ok(mut premoves) = self.premoves.write() {
+            premoves.insert(game_id.clone(), (user_id, turn));
+        }
+    }
+
+    pub fn cancel(&self, game_id: &GameId, user_id: Uuid) {
+        if let Ok(mut premoves) = self.premoves.write() {
+            if premoves.get(game_id).is_some_and(|(by, _)| *by == user_id) {
+                premoves.remove(game_id);
+            }
+        }
+    }
+
+    // Hands out the user's premove, a premove of the other player stays queued
+    pub fn take(&self, game_id: &GameId, user_id: Uuid) -> Option<Turn> {
+        let mut premoves = self.premoves.write().ok()?;
+        if premoves.get(game_id).is_some_and(|(by, _)| *by == user_id) {
+            premoves.remove(game_id).map(|(_, turn)| turn)
+        } else {
+            None
+        }
+    }
+
+    pub fn remove(&self, game_id: &GameId) {
+        if let Ok(mut premoves) = self.premoves.write() {
+            premoves.remove(game_id);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use shared_types::CLAIM_VICTORY_AFTER;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::RwLock,
+    time::Duration,
+};
+use uuid::Uuid;
+
+// When users lost their last connection, kept up to date by the Lobby
+#[derive(Debug)]
+pub struct Presence {
+    // user_id to when the user left, None while connected
+    last_seen: RwLock<HashMap<Uuid, Option<DateTime<Utc>>>>,
+    claim_victory_after: Duration,
+}
+
+impl Default for Presence {
+    fn default() -> Self {
+        Self::new(CLAIM_VICTORY_AFTER)
+    }
+}
+
+impl Presence {
+    pub fn new(claim_victory_after: Duration) -> Self {
+        Self {
+            last_seen: RwLock::new(HashMap::new()),
+            claim_victory_after,
+        }
+    }
+
+    pub fn connected(&self, user_id: Uuid) {
+        if let Ok(mut last_seen) = self.last_seen.write() {
+            last_seen.insert(user_id, None);
+        }
+    }
+
+    pub fn disconnected(&self, user_id: Uuid) {
+        if let Ok(mut last_seen) = self.last_seen.write() {
+            last_seen.insert(user_id, Some(Utc::now()));
+        }
+    }
+
+    // How long the user has been gone. Only users seen leaving count, after a restart the
+    // clients need a moment to reconnect.
+    pub fn gone_for(&self, user_id: &Uuid) -> Option<Duration> {
+        let last_seen = self.last_seen.read().ok()?;
+        let since = (*last_seen.get(user_id)?)?;
+        Utc::now().signed_duration_since(since).to_std().ok()
+    }
+
+    // Whether the opponents of the user can claim the win of their realtime games
+    pub fn gone_long_enough(&self, user_id: &Uuid) -> bool {
+        self.gone_for(user_id)
+            .is_some_and(|gone| gone >= self.claim_victory_after)
+    }
+
+    // Forgets users who left and have no running game anymore
+    pub fn prune(&self, players: &HashSet<Uuid>) {
+        if let Ok(mut last_seen) = self.last_seen.write() {
+            last_seen.retain(|user_id, left| left.is_none() || players.contains(user_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_gone_for() {
+        let presence = Presence::default();
+        let user_id = Uuid::new_v4();
+        assert_eq!(presence.gone_for(&user_id), None);
+        presence.connected(user_id);
+        assert_eq!(presence.gone_for(&user_id), None);
+        presence.disconnected(user_id);
+        assert!(presence.gone_for(&user_id).is_some());
+        presence.connected(user_id);
+        assert_eq!(presence.gone_for(&user_id), None);
+    }
+
+    #[test]
+    fn tests_gone_long_enough() {
+        let user_id = Uuid::new_v4();
+        let presence = Presence::new(Duration::ZERO);
+        assert!(!presence.gone_long_enough(&user_id));
+        presence.disconnected(user_id);
+        assert!(presence.gone_long_enough(&user_id));
+        let presence = Presence::new(Duration::from_secs(3600));
+        presence.disconnected(user_id);
+        assert!(!presence.gone_long_enough(&user_id));
+    }
+
+    #[test]
+    fn tests_prune() {
+        let presence = Presence::default();
+        let (connected, playing, gone) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        presence.connected(connected);
+        presence.disconnected(playing);
+        presence.disconnected(gone);
+        presence.prune(&HashSet::from([playing]));
+        assert!(presence.gone_for(&playing).is_some());
+        assert_eq!(presence.gone_for(&gone), None);
+        let last_seen = presence.last_seen.read().unwrap();
+        assert!(last_seen.contains_key(&connected));
+        assert!(!last_seen.contains_key(&gone));
+    }
+}
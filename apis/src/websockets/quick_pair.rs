@@ -0,0 +1,188 @@
+use shared_types::QuickPairDetails;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// Rating difference accepted right after joining the pool
+const START_RANGE: f64 = 100.0;
+// How much the accepted difference grows for every second spent waiting
+const WIDEN_PER_SECOND: f64 = 10.0;
+const MAX_RANGE: f64 = 1000.0;
+
+#[derive(Debug, Clone)]
+pub struct Seeker {
+    pub user_id: Uuid,
+    pub username: String,
+    pub details: QuickPairDetails,
+    pub rating: f64,
+    pub joined: Instant,
+}
+
+impl Seeker {
+    pub fn new(user_id: Uuid, username: &str, details: QuickPairDetails, rating: f64) -> Self {
+        Self {
+            user_id,
+            username: username.to_owned(),
+            details,
+            rating,
+            joined: Instant::now(),
+        }
+    }
+
+    fn range(&self, now: Instant) -> f64 {
+        let waited = now.saturating_duration_since(self.joined);
+        (START_RANGE + WIDEN_PER_SECOND * waited.as_secs_f64()).min(MAX_RANGE)
+    }
+
+    fn accepts(&self, other: &Seeker, now: Instant) -> bool {
+        let difference = (self.rating - other.rating).abs();
+        self.user_id != other.user_id
+            && self.details == other.details
+            && difference <= self.range(now)
+            && difference <= other.range(now)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QuickPairPool {
+    seekers: Vec<Seeker>, // ordered by time of joining
+}
+
+impl QuickPairPool {
+    pub const INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn join(&mut self, seeker: Seeker) {
+        self.leave(&seeker.user_id);
+        self.seekers.push(seeker);
+    }
+
+    pub fn leave(&mut self, user_id: &Uuid) -> bool {
+        let before = self.seekers.len();
+        self.seekers.retain(|seeker| seeker.user_id != *user_id);
+        before != self.seekers.len()
+    }
+
+    // Takes the pairs that can play each other out of the pool. Whoever has
+    // been waiting the longest gets the closest rated opponent first.
+    pub fn pairs(&mut self) -> Vec<(Seeker, Seeker)> {
+        let now = Instant::now();
+        let mut paired = vec![false; self.seekers.len()];
+        let mut pairs = Vec::new();
+        for i in 0..self.seekers.len() {
+            if paired[i] {
+                continue;
+            }
+            let seeker = &self.seekers[i];
+            let opponent = (i + 1..self.seekers.len())
+                .filter(|j| !paired[*j] && seeker.accepts(&self.seekers[*j], now))
+                .min_by(|a, b| {
+                    let a = (seeker.rating - self.seekers[*a].rating).abs();
+                    let b = (seeker.rating - self.seekers[*b].rating).abs();
+                    a.total_cmp(&b)
+                });
+            if let Some(j) = opponent {
+                paired[i] = true;
+                paired[j] = true;
+                pairs.push((i, j));
+            }
+        }
+        let pairs = pairs
+            .into_iter()
+            .map(|(i, j)| (self.seekers[i].clone(), self.seekers[j].clone()))
+            .collect();
+        let mut index = 0;
+        self.seekers.retain(|_| {
+            index += 1;
+            !paired[index - 1]
+        });
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hive_lib::GameType;
+
+    const DETAILS: QuickPairDetails = QuickPairDetails {
+        game_type: GameType::MLP,
+        rated: true,
+        time_base: 300,
+        time_increment: 5,
+    };
+
+    fn seeker(rating: f64, waited: u64) -> Seeker {
+        let mut seeker = Seeker::new(Uuid::new_v4(), "player", DETAILS, rating);
+        seeker.joined = Instant::now() - Duration::from_secs(waited);
+        seeker
+    }
+
+    #[test]
+    fn tests_pairs_inside_rating_window() {
+        let mut pool = QuickPairPool::default();
+        let (first, second) = (seeker(1500.0, 0), seeker(1580.0, 0));
+        pool.join(first.clone());
+        pool.join(second.clone());
+        let pairs = pool.pairs();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0.user_id, first.user_id);
+        assert_eq!(pairs[0].1.user_id, second.user_id);
+        assert!(pool.pairs().is_empty());
+    }
+
+    #[test]
+    fn tests_no_pair_outside_rating_window() {
+        let mut pool = QuickPairPool::default();
+        pool.join(seeker(1500.0, 0));
+        pool.join(seeker(1800.0, 0));
+        assert!(pool.pairs().is_empty());
+        // Both stay in the pool for the next round
+        assert_eq!(pool.seekers.len(), 2);
+    }
+
+    #[test]
+    fn tests_window_widens_while_waiting() {
+        let mut pool = QuickPairPool::default();
+        pool.join(seeker(1500.0, 30));
+        pool.join(seeker(1800.0, 30));
+        assert_eq!(pool.pairs().len(), 1);
+    }
+
+    #[test]
+    fn tests_closest_opponent_first() {
+        let mut pool = QuickPairPool::default();
+        let first = seeker(1500.0, 0);
+        let far = seeker(1590.0, 0);
+        let close = seeker(1520.0, 0);
+        pool.join(first.clone());
+        pool.join(far.clone());
+        pool.join(close.clone());
+        let pairs = pool.pairs();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].1.user_id, close.user_id);
+        assert_eq!(pool.seekers[0].user_id, far.user_id);
+    }
+
+    #[test]
+    fn tests_different_details_never_pair() {
+        let mut pool = QuickPairPool::default();
+        let mut casual = seeker(1500.0, 0);
+        casual.details.rated = false;
+        pool.join(casual);
+        pool.join(seeker(1500.0, 0));
+        assert!(pool.pairs().is_empty());
+    }
+
+    #[test]
+    fn tests_leave() {
+        let mut pool = QuickPairPool::default();
+        let first = seeker(1500.0, 0);
+        pool.join(first.clone());
+        // Joining again replaces the earlier search
+        pool.join(first.clone());
+        assert_eq!(pool.seekers.len(), 1);
+        assert!(pool.leave(&first.user_id));
+        assert!(!pool.leave(&first.user_id));
+        pool.join(seeker(1500.0, 0));
+        assert!(pool.pairs().is_empty());
+    }
+}
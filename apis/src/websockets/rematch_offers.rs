@@ -0,0 +1,166 @@
+use shared_types::GameId;
+use std::{collections::HashMap, sync::RwLock};
+use uuid::Uuid;
+
+// Open rematch offers only live as long as both players stay connected
+#[derive(Debug)]
+pub struct RematchOffers {
+    // game_id to (offered by, offered to)
+    offers: RwLock<HashMap<GameId, (Uuid, Uuid)>>,
+    // Games that already got their rematch to the players still connected, a game only gets one
+    // and is forgotten once both players left
+    rematched: RwLock<HashMap<GameId, Vec<Uuid>>>,
+}
+
+impl Default for RematchOffers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RematchOffers {
+    pub fn new() -> Self {
+        Self {
+            offers: RwLock::new(HashMap::new()),
+            rematched: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn rematched(&self, game_id: &GameId) -> bool {
+        self.rematched
+            .read()
+            .map(|rematched| rematched.contains_key(game_id))
+            .unwrap_or(true)
+    }
+
+    pub fn offer(&self, game_id: &GameId, from: Uuid, to: Uuid) {
+        if let Ok(mut offers) = self.offers.write() {
+            offers.insert(game_id.clone(), (from, to));
+        }
+    }
+
+    // Takes the offer the opponent made, only one of two concurrent accepts gets it
+    pub fn accept(&self, game_id: &GameId, opponent: Uuid) -> bool {
+        let (Ok(mut offers), Ok(mut rematched)) = (self.offers.write(), self.rematched.write())
+        else {
+            return false;
+        };
+        if rematched.contains_key(game_id) {
+            return false;
+        }
+        match offers.get(game_id) {
+            Some((from, to)) if *from == opponent => {
+                rematched.insert(game_id.clone(), vec![*from, *to]);
+                offers.remove(game_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn offered_by(&self, game_id: &GameId) -> Option<Uuid> {
+        self.offers
+            .read()
+            .ok()
+            .and_then(|offers| offers.get(game_id).map(|(from, _)| *from))
+    }
+
+    pub fn remove(&self, game_id: &GameId) -> Option<(Uuid, Uuid)> {
+        self.offers
+            .write()
+            .ok()
+            .and_then(|mut offers| offers.remove(game_id))
+    }
+
+    // Drops every offer made by or to the user and returns them, rematched games the user played
+    // in are forgotten once the opponent left as well
+    pub fn expire(&self, user_id: Uuid) -> Vec<(GameId, Uuid, Uuid)> {
+        if let Ok(mut rematched) = self.rematched.write() {
+            rematched.retain(|_, players| {
+                players.retain(|player| *player != user_id);
+                !players.is_empty()
+            });
+        }
+        let mut expired = Vec::new();
+        if let Ok(mut offers) = self.offers.write() {
+            offers.retain(|game_id, (from, to)| {
+                if *from == user_id || *to == user_id {
+                    expired.push((game_id.clone(), *from, *to));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn tests_accept_only_the_opponents_offer() {
+        let offers = RematchOffers::new();
+        let game_id = GameId(String::from("game"));
+        let (one, two) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(!offers.accept(&game_id, one));
+        offers.offer(&game_id, one, two);
+        assert!(!offers.accept(&game_id, two));
+        assert!(offers.accept(&game_id, one));
+        assert!(offers.rematched(&game_id));
+        assert_eq!(offers.offered_by(&game_id), None);
+        offers.offer(&game_id, two, one);
+        assert!(!offers.accept(&game_id, two));
+    }
+
+    #[test]
+    fn tests_one_of_two_concurrent_accepts_wins() {
+        let offers = Arc::new(RematchOffers::new());
+        let game_id = GameId(String::from("game"));
+        let (one, two) = (Uuid::new_v4(), Uuid::new_v4());
+        offers.offer(&game_id, one, two);
+        let accepts = (0..2)
+            .map(|_| {
+                let offers = Arc::clone(&offers);
+                let game_id = game_id.clone();
+                thread::spawn(move || offers.accept(&game_id, one))
+            })
+            .collect::<Vec<_>>();
+        let won = accepts
+            .into_iter()
+            .map(|accept| accept.join().unwrap())
+            .filter(|won| *won)
+            .count();
+        assert_eq!(won, 1);
+    }
+
+    #[test]
+    fn tests_expire() {
+        let offers = RematchOffers::new();
+        let (one, two, three) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let open = GameId(String::from("open"));
+        let other = GameId(String::from("other"));
+        offers.offer(&open, one, two);
+        offers.offer(&other, two, three);
+        assert_eq!(offers.expire(one), vec![(open.clone(), one, two)]);
+        assert_eq!(offers.offered_by(&open), None);
+        assert_eq!(offers.offered_by(&other), Some(two));
+    }
+
+    #[test]
+    fn tests_expire_forgets_rematched_games_once_both_left() {
+        let offers = RematchOffers::new();
+        let game_id = GameId(String::from("game"));
+        let (one, two) = (Uuid::new_v4(), Uuid::new_v4());
+        offers.offer(&game_id, one, two);
+        assert!(offers.accept(&game_id, one));
+        offers.expire(one);
+        assert!(offers.rematched(&game_id));
+        offers.expire(two);
+        assert!(!offers.rematched(&game_id));
+    }
+}
@@ -1,12 +1,17 @@
+use crate::functions::auth::identity::SESSION_VERSION;
 use crate::websockets::{chat::Chats, connection::WsConnection, lobby::Lobby};
 use actix::Addr;
 use actix_identity::Identity;
+use actix_session::SessionExt;
 use actix_web::{get, web::Data, web::Payload, Error, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
 use db_lib::{get_conn, models::User, DbPool};
 use uuid::Uuid;
 
-use super::tournament_game_start::TournamentGameStart;
+use super::{
+    game_states::GameStates, premoves::Premoves, presence::Presence, rematch_offers::RematchOffers,
+    timers::Timers, tournament_game_start::TournamentGameStart,
+};
 
 #[get("/ws/")]
 pub async fn start_connection(
@@ -15,6 +20,11 @@ pub async fn start_connection(
     srv: Data<Addr<Lobby>>,
     chat_storage: Data<Chats>,
     game_start: Data<TournamentGameStart>,
+    rematch_offers: Data<RematchOffers>,
+    premoves: Data<Premoves>,
+    game_states: Data<GameStates>,
+    presence: Data<Presence>,
+    timers: Data<Timers>,
     pool: Data<DbPool>,
     identity: Option<Identity>,
 ) -> Result<HttpResponse, Error> {
@@ -23,15 +33,30 @@ pub async fn start_connection(
             if let Ok(uuid) = Uuid::parse_str(&id_string) {
                 match get_conn(&pool).await {
                     Ok(mut conn) => {
-                        if let Ok(user) = User::find_by_uuid(&uuid, &mut conn).await {
+                        let user = User::find_by_uuid(&uuid, &mut conn).await;
+                        let version = req
+                            .get_session()
+                            .get::<i32>(SESSION_VERSION)
+                            .ok()
+                            .flatten()
+                            .unwrap_or(0);
+                        if let Some(user) = user.ok().filter(|user| {
+                            !user.deleted && !user.is_banned() && user.session_version == version
+                        }) {
                             println!("Welcome {}!", user.username);
                             let ws = WsConnection::new(
                                 Some(uuid),
                                 Some(user.username),
-                                Some(user.admin),
+                                Some(user.is_admin()),
+                                Some(user.guest),
                                 srv.get_ref().clone(),
                                 chat_storage.clone(),
                                 game_start.clone(),
+                                rematch_offers.clone(),
+                                premoves.clone(),
+                                game_states.clone(),
+                                presence.clone(),
+                                timers.clone(),
                                 pool.get_ref().clone(),
                             );
                             let resp = ws::start(ws, &req, stream)?;
@@ -49,9 +74,15 @@ pub async fn start_connection(
         None,
         None,
         None,
+        None,
         srv.get_ref().clone(),
         chat_storage.clone(),
         game_start.clone(),
+        rematch_offers.clone(),
+        premoves.clone(),
+        game_states.clone(),
+        presence.clone(),
+        timers.clone(),
         pool.get_ref().clone(),
     );
 
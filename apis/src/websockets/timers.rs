@@ -0,0 +1,326 @@
+use crate::responses::{GameResponse, HeartbeatResponse};
+use chrono::{DateTime, Utc};
+use db_lib::models::Game;
+use hive_lib::GameStatus;
+use shared_types::{ClockType, GameId, TimeMode};
+use std::{collections::HashMap, str::FromStr, sync::RwLock, time::Duration};
+use tokio::sync::Notify;
+
+// The clocks of a running game as of its last move
+#[derive(Debug, Clone)]
+struct Clock {
+    white_time_left: Duration,
+    black_time_left: Duration,
+    white_to_move: bool,
+    since: DateTime<Utc>,
+    delay: Duration, // Delay clocks only start running after this
+    hourglass: bool, // The time the mover uses runs over to the opponent
+}
+
+impl Clock {
+    fn new(
+        time_mode: &TimeMode,
+        clock_type: ClockType,
+        time_increment: Option<i32>,
+        turn: usize,
+        time_left: (Option<Duration>, Option<Duration>),
+        last_interaction: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        if *time_mode == TimeMode::Untimed {
+            return None;
+        }
+        let delay = match (time_mode, clock_type) {
+            (TimeMode::RealTime, ClockType::Delay) => {
+                Duration::from_secs(time_increment.unwrap_or_default() as u64)
+            }
+            _ => Duration::ZERO,
+        };
+        Some(Self {
+            white_time_left: time_left.0?,
+            black_time_left: time_left.1?,
+            white_to_move: turn % 2 == 0,
+            since: last_interaction?,
+            delay,
+            hourglass: *time_mode == TimeMode::RealTime && clock_type == ClockType::Hourglass,
+        })
+    }
+
+    fn time_left(&self, now: DateTime<Utc>) -> (Duration, Duration) {
+        let used = now
+            .signed_duration_since(self.since)
+            .to_std()
+            .unwrap_or_default()
+            .saturating_sub(self.delay);
+        let mover_left = if self.white_to_move {
+            self.white_time_left
+        } else {
+            self.black_time_left
+        };
+        let gained = if self.hourglass {
+            used.min(mover_left)
+        } else {
+            Duration::ZERO
+        };
+        if self.white_to_move {
+            (
+                self.white_time_left.saturating_sub(used),
+                self.black_time_left + gained,
+            )
+        } else {
+            (
+                self.white_time_left + gained,
+                self.black_time_left.saturating_sub(used),
+            )
+        }
+    }
+
+    fn deadline(&self) -> DateTime<Utc> {
+        let left = if self.white_to_move {
+            self.white_time_left
+        } else {
+            self.black_time_left
+        };
+        chrono::Duration::from_std(left + self.delay)
+            .ok()
+            .and_then(|left| self.since.checked_add_signed(left))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+// Deadlines of the running timed games, kept in memory so that heartbeats and flagging
+// don't need a round-trip to the database
+#[derive(Debug)]
+pub struct Timers {
+    clocks: RwLock<HashMap<GameId, Clock>>,
+    changed: Notify,
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self {
+            clocks: RwLock::new(HashMap::new()),
+            changed: Notify::new(),
+        }
+    }
+
+    fn set(&self, game_id: &GameId, clock: Option<Clock>) {
+        if let Ok(mut clocks) = self.clocks.write() {
+            match clock {
+                Some(clock) => clocks.insert(game_id.clone(), clock),
+                None => clocks.remove(game_id),
+            };
+        }
+        self.changed.notify_one();
+    }
+
+    pub fn update(&self, game: &GameResponse) {
+        let clock = if game.game_status == GameStatus::InProgress {
+            Clock::new(
+                &game.time_mode,
+                game.clock_type,
+                game.time_increment,
+                game.turn,
+                (game.white_time_left, game.black_time_left),
+                game.last_interaction,
+            )
+        } else {
+            None
+        };
+        self.set(&game.game_id, clock);
+    }
+
+    pub fn update_from_model(&self, game: &Game) {
+        let clock = match TimeMode::from_str(&game.time_mode) {
+            Ok(time_mode) if game.game_status == GameStatus::InProgress.to_string() => Clock::new(
+                &time_mode,
+                ClockType::from_str(&game.clock_type).unwrap_or_default(),
+                game.time_increment,
+                game.turn as usize,
+                (
+                    game.white_time_left
+                        .map(|nanos| Duration::from_nanos(nanos as u64)),
+                    game.black_time_left
+                        .map(|nanos| Duration::from_nanos(nanos as u64)),
+                ),
+                game.last_interaction,
+            ),
+            _ => None,
+        };
+        self.set(&GameId(game.nanoid.clone()), clock);
+    }
+
+    // Replaces all clocks with the ones of the given running games
+    pub fn sync(&self, games: &[Game]) {
+        if let Ok(mut clocks) = self.clocks.write() {
+            clocks.clear();
+        }
+        for game in games {
+            self.update_from_model(game);
+        }
+    }
+
+    pub fn remove(&self, game_id: &GameId) {
+        self.set(game_id, None);
+    }
+
+    pub fn heartbeat(&self, game_id: &GameId) -> Option<HeartbeatResponse> {
+        let clocks = self.clocks.read().ok()?;
+        let (white_time_left, black_time_left) = clocks.get(game_id)?.time_left(Utc::now());
+        Some(HeartbeatResponse {
+            game_id: game_id.clone(),
+            white_time_left,
+            black_time_left,
+        })
+    }
+
+    pub fn next_deadline(&self) -> Option<DateTime<Utc>> {
+        let clocks = self.clocks.read().ok()?;
+        clocks.values().map(Clock::deadline).min()
+    }
+
+    // Takes the games whose clock ran out out of the timers
+    pub fn take_expired(&self) -> Vec<GameId> {
+        let now = Utc::now();
+        let Ok(mut clocks) = self.clocks.write() else {
+            return Vec::new();
+        };
+        let expired = clocks
+            .iter()
+            .filter(|(_, clock)| clock.deadline() <= now)
+            .map(|(game_id, _)| game_id.clone())
+            .collect::<Vec<_>>();
+        for game_id in expired.iter() {
+            clocks.remove(game_id);
+        }
+        expired
+    }
+
+    // Resolves once a clock got added, changed or removed
+    pub async fn changed(&self) {
+        self.changed.notified().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(white_to_move: bool, left: u64, since: DateTime<Utc>) -> Option<Clock> {
+        Clock::new(
+            &TimeMode::RealTime,
+            ClockType::Fischer,
+            Some(0),
+            if white_to_move { 0 } else { 1 },
+            (
+                Some(Duration::from_secs(left)),
+                Some(Duration::from_secs(left)),
+            ),
+            Some(since),
+        )
+    }
+
+    fn game_id(id: &str) -> GameId {
+        GameId(id.to_owned())
+    }
+
+    #[test]
+    fn tests_no_clock_for_untimed_games() {
+        let clock = Clock::new(
+            &TimeMode::Untimed,
+            ClockType::Fischer,
+            None,
+            0,
+            (None, None),
+            None,
+        );
+        assert!(clock.is_none());
+    }
+
+    #[test]
+    fn tests_only_the_mover_loses_time() {
+        let since = Utc::now();
+        let clock = clock(false, 60, since).unwrap();
+        let (white, black) = clock.time_left(since + chrono::Duration::seconds(10));
+        assert_eq!(white, Duration::from_secs(60));
+        assert_eq!(black, Duration::from_secs(50));
+        assert_eq!(clock.deadline(), since + chrono::Duration::seconds(60));
+    }
+
+    #[test]
+    fn tests_delay_pushes_the_deadline() {
+        let since = Utc::now();
+        let clock = Clock::new(
+            &TimeMode::RealTime,
+            ClockType::Delay,
+            Some(5),
+            0,
+            (Some(Duration::from_secs(60)), Some(Duration::from_secs(60))),
+            Some(since),
+        )
+        .unwrap();
+        assert_eq!(clock.deadline(), since + chrono::Duration::seconds(65));
+        let (white, _) = clock.time_left(since + chrono::Duration::seconds(5));
+        assert_eq!(white, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn tests_hourglass_moves_the_time_to_the_opponent() {
+        let since = Utc::now();
+        let clock = Clock::new(
+            &TimeMode::RealTime,
+            ClockType::Hourglass,
+            Some(0),
+            0,
+            (Some(Duration::from_secs(60)), Some(Duration::from_secs(60))),
+            Some(since),
+        )
+        .unwrap();
+        let (white, black) = clock.time_left(since + chrono::Duration::seconds(10));
+        assert_eq!(white, Duration::from_secs(50));
+        assert_eq!(black, Duration::from_secs(70));
+        // Only the sand that was left can run over
+        let (white, black) = clock.time_left(since + chrono::Duration::seconds(90));
+        assert_eq!(white, Duration::ZERO);
+        assert_eq!(black, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn tests_next_deadline_is_the_earliest() {
+        let timers = Timers::new();
+        assert_eq!(timers.next_deadline(), None);
+        let now = Utc::now();
+        timers.set(&game_id("late"), clock(true, 600, now));
+        timers.set(&game_id("soon"), clock(false, 30, now));
+        assert_eq!(
+            timers.next_deadline(),
+            Some(now + chrono::Duration::seconds(30))
+        );
+        timers.remove(&game_id("soon"));
+        assert_eq!(
+            timers.next_deadline(),
+            Some(now + chrono::Duration::seconds(600))
+        );
+    }
+
+    #[test]
+    fn tests_take_expired() {
+        let timers = Timers::new();
+        let now = Utc::now();
+        timers.set(
+            &game_id("flagged"),
+            clock(true, 30, now - chrono::Duration::seconds(31)),
+        );
+        timers.set(&game_id("running"), clock(true, 30, now));
+        assert_eq!(timers.take_expired(), vec![game_id("flagged")]);
+        // Expired games are taken only once
+        assert!(timers.take_expired().is_empty());
+        assert!(timers.heartbeat(&game_id("flagged")).is_none());
+        assert!(timers.heartbeat(&game_id("running")).is_some());
+    }
+}
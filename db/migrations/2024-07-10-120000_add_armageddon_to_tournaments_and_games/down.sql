@@ -0,0 +1,2 @@
+alter table tournaments drop column armageddon;
+alter table games drop column armageddon;
@@ -0,0 +1,2 @@
+alter table tournaments add column armageddon boolean not null default false;
+alter table games add column armageddon boolean not null default false;
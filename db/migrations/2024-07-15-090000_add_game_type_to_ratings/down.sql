@@ -0,0 +1,2 @@
+delete from ratings where game_type = 'Base';
+alter table ratings drop column game_type;
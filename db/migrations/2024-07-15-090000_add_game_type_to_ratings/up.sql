@@ -0,0 +1,74 @@
+alter table ratings add column game_type text not null default 'Base+MLP';
+
+create temporary table mixed_ratings as select * from ratings;
+
+update ratings set
+  played = 0,
+  won = 0,
+  lost = 0,
+  draw = 0,
+  rating = 1500.0,
+  deviation = 500.0,
+  volatility = 0.09,
+  updated_at = now();
+
+insert into ratings (user_uid, played, won, lost, draw, rating, deviation, volatility, created_at, updated_at, speed, game_type)
+select user_uid, 0, 0, 0, 0, 1500.0, 500.0, 0.09, now(), now(), speed, 'Base'
+from mixed_ratings;
+
+-- every game counted towards a single rating per speed until now, so split
+-- the finished games by game type and sum up the recorded rating changes.
+-- those changes were computed against the combined rating, so the result is
+-- only a provisional estimate: the server replays every game against the
+-- rating of its game type on its next start, see pending_rebuilds.
+with results as (
+  select white_id as user_uid, speed, game_type, rated, white_rating_change as rating_change,
+    game_status = 'Finished(1-0)' as won,
+    game_status = 'Finished(0-1)' as lost,
+    game_status = 'Finished(½-½)' as drawn
+  from games where finished
+  union all
+  select black_id as user_uid, speed, game_type, rated, black_rating_change as rating_change,
+    game_status = 'Finished(0-1)' as won,
+    game_status = 'Finished(1-0)' as lost,
+    game_status = 'Finished(½-½)' as drawn
+  from games where finished
+), totals as (
+  select user_uid,
+    case when speed = 'Untimed' then 'Correspondence' else speed end as speed,
+    case when game_type = 'Base' then 'Base' else 'Base+MLP' end as game_type,
+    count(*) as played,
+    count(*) filter (where won) as won,
+    count(*) filter (where lost) as lost,
+    count(*) filter (where drawn) as drawn,
+    coalesce(sum(rating_change) filter (where rated), 0.0) as rating_change,
+    count(*) filter (where rated) as rated_games
+  from results
+  group by 1, 2, 3
+)
+update ratings set
+  played = totals.played,
+  won = totals.won,
+  lost = totals.lost,
+  draw = totals.drawn,
+  rating = 1500.0 + totals.rating_change,
+  -- the combined deviation only says how sure we are about this bucket when
+  -- most of the rated games were played in it, everyone else starts over
+  deviation = case
+    when totals.rated_games >= 20 and totals.rated_games * 2 >= mixed_played.rated_games
+      then mixed_ratings.deviation
+    else 500.0 end,
+  volatility = case
+    when totals.rated_games >= 20 and totals.rated_games * 2 >= mixed_played.rated_games
+      then mixed_ratings.volatility
+    else 0.09 end
+from totals
+join mixed_ratings on mixed_ratings.user_uid = totals.user_uid and mixed_ratings.speed = totals.speed
+join (
+  select user_uid, speed, sum(rated_games) as rated_games from totals group by 1, 2
+) mixed_played on mixed_played.user_uid = totals.user_uid and mixed_played.speed = totals.speed
+where ratings.user_uid = totals.user_uid
+  and ratings.speed = totals.speed
+  and ratings.game_type = totals.game_type;
+
+drop table mixed_ratings;
@@ -0,0 +1 @@
+drop table rating_history;
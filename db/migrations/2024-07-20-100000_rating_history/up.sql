@@ -0,0 +1,15 @@
+create table rating_history (
+  id int generated always as identity primary key,
+  user_uid uuid references users(id) on delete cascade not null,
+  game_id uuid references games(id) on delete cascade not null,
+  game_type text not null,
+  speed text not null,
+  rating float8 not null,
+  rating_change float8 not null,
+  created_at timestamp with time zone not null
+);
+
+create index rating_history_user_idx on rating_history (user_uid, game_type, speed, created_at);
+
+-- older games only know the combined rating they were played against, the
+-- history gets filled by replaying them, see pending_rebuilds.
@@ -0,0 +1 @@
+alter table ratings drop column rated_at;
@@ -0,0 +1,2 @@
+alter table ratings add column rated_at timestamp with time zone not null default now();
+update ratings set rated_at = updated_at;
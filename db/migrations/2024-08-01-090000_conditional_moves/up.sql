@@ -0,0 +1,9 @@
+create table conditional_moves (
+  id int generated always as identity primary key,
+  game_id uuid references games(id) on delete cascade not null,
+  user_id uuid references users(id) on delete cascade not null,
+  moves text not null,
+  created_at timestamp with time zone not null
+);
+
+create index conditional_moves_game_user_idx on conditional_moves (game_id, user_id);
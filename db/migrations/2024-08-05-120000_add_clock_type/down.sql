@@ -0,0 +1,3 @@
+alter table tournaments drop column clock_type;
+alter table games drop column clock_type;
+alter table challenges drop column clock_type;
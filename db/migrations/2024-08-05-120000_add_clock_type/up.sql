@@ -0,0 +1,3 @@
+alter table challenges add column clock_type text not null default 'Fischer';
+alter table games add column clock_type text not null default 'Fischer';
+alter table tournaments add column clock_type text not null default 'Fischer';
@@ -0,0 +1,2 @@
+alter table games drop column move_dates;
+alter table games drop column move_times;
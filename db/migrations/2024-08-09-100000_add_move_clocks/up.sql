@@ -0,0 +1,2 @@
+alter table games add column move_times bigint[] not null default '{}';
+alter table games add column move_dates timestamptz[] not null default '{}';
@@ -0,0 +1,3 @@
+alter table tournaments drop column repetition_rule;
+alter table games drop column repetition_rule;
+alter table challenges drop column repetition_rule;
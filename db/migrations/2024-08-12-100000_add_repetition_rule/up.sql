@@ -0,0 +1,3 @@
+alter table challenges add column repetition_rule text not null default 'Automatic';
+alter table games add column repetition_rule text not null default 'Automatic';
+alter table tournaments add column repetition_rule text not null default 'Automatic';
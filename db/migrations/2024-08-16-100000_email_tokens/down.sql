@@ -0,0 +1,2 @@
+drop table email_tokens;
+alter table users drop column email_verified;
@@ -0,0 +1,13 @@
+alter table users add column email_verified boolean not null default false;
+
+create table email_tokens (
+  id int generated always as identity primary key,
+  user_id uuid references users(id) on delete cascade not null,
+  purpose text not null,
+  token_hash text not null unique,
+  expires_at timestamp with time zone not null,
+  used_at timestamp with time zone,
+  created_at timestamp with time zone not null
+);
+
+create index email_tokens_user_purpose_idx on email_tokens (user_id, purpose);
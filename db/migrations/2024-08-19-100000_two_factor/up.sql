@@ -0,0 +1,11 @@
+alter table users add column totp_secret text;
+alter table users add column totp_enabled boolean not null default false;
+
+create table recovery_codes (
+  id int generated always as identity primary key,
+  user_id uuid references users(id) on delete cascade not null,
+  code_hash text not null,
+  used_at timestamp with time zone
+);
+
+create index recovery_codes_user_idx on recovery_codes (user_id);
@@ -0,0 +1,8 @@
+create table notification_settings (
+  user_id uuid primary key references users(id) on delete cascade,
+  your_turn boolean not null default false,
+  time_running_low boolean not null default false,
+  tournament_starting boolean not null default false,
+  tournament_invitation boolean not null default false,
+  last_digest_at timestamp with time zone not null default now()
+);
@@ -0,0 +1,3 @@
+update users set email = id::text || '@guest.invalid' where email is null;
+alter table users alter column email set not null;
+alter table users drop column guest;
@@ -0,0 +1,2 @@
+alter table users add column guest boolean not null default false;
+alter table users alter column email drop not null;
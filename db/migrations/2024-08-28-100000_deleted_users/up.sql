@@ -0,0 +1 @@
+alter table users add column deleted boolean not null default false;
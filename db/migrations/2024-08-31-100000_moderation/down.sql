@@ -0,0 +1,6 @@
+drop table admin_actions;
+
+alter table games drop column annulled;
+
+alter table users drop column suspended_until;
+alter table users drop column banned;
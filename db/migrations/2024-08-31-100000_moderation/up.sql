@@ -0,0 +1,16 @@
+alter table users add column banned boolean not null default false;
+alter table users add column suspended_until timestamp with time zone;
+
+alter table games add column annulled boolean not null default false;
+
+create table admin_actions (
+  id int generated always as identity primary key,
+  admin_id uuid references users(id) not null,
+  action text not null,
+  target_user_id uuid references users(id) on delete set null,
+  target_game_id uuid references games(id) on delete set null,
+  reason text not null,
+  created_at timestamp with time zone not null
+);
+
+create index admin_actions_created_at_idx on admin_actions (created_at desc);
@@ -0,0 +1 @@
+drop table fair_play_reports;
@@ -0,0 +1,13 @@
+create table fair_play_reports (
+  user_id uuid primary key references users(id) on delete cascade,
+  games int not null,
+  moves int not null,
+  engine_match_rate double precision,
+  move_time_variation double precision,
+  performance_gap double precision,
+  flags text[] not null,
+  flagged boolean not null,
+  analysed_at timestamp with time zone not null
+);
+
+create index fair_play_reports_flagged_idx on fair_play_reports (flagged, analysed_at desc);
@@ -0,0 +1,6 @@
+drop table avatars;
+
+alter table users drop column links;
+alter table users drop column preferred_game_type;
+alter table users drop column country;
+alter table users drop column bio;
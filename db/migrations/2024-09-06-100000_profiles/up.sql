@@ -0,0 +1,11 @@
+alter table users add column bio text not null default '';
+alter table users add column country text;
+alter table users add column preferred_game_type text;
+alter table users add column links text[] not null default '{}';
+
+create table avatars (
+  user_id uuid primary key references users(id) on delete cascade,
+  content_type text not null,
+  image bytea not null,
+  updated_at timestamp with time zone not null
+);
@@ -0,0 +1,6 @@
+create table user_statistics (
+  user_id uuid primary key references users(id) on delete cascade,
+  statistics jsonb not null,
+  stale boolean not null default false,
+  computed_at timestamp with time zone not null
+);
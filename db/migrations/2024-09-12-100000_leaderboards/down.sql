@@ -0,0 +1,2 @@
+drop index rating_history_leaderboard_idx;
+drop index ratings_leaderboard_idx;
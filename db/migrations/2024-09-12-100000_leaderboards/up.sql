@@ -0,0 +1,2 @@
+create index ratings_leaderboard_idx on ratings (game_type, speed, rating desc);
+create index rating_history_leaderboard_idx on rating_history (game_type, speed, created_at) include (user_uid, rating_change);
@@ -0,0 +1,8 @@
+alter table games add column finished_at timestamp with time zone;
+
+-- updated_at moves whenever a finished game is written to again, the last
+-- move is the best guess for older games
+update games set finished_at = coalesce(move_dates[array_length(move_dates, 1)], updated_at)
+where finished;
+
+create index games_finished_at_idx on games (finished_at) where finished;
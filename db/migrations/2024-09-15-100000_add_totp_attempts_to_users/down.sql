@@ -0,0 +1,3 @@
+alter table users drop column totp_last_step;
+alter table users drop column totp_locked_until;
+alter table users drop column totp_failed_attempts;
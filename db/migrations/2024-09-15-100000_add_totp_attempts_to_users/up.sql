@@ -0,0 +1,4 @@
+alter table users add column totp_failed_attempts integer not null default 0;
+alter table users add column totp_locked_until timestamp with time zone;
+-- the time step of the last accepted code, a code is only good once
+alter table users add column totp_last_step bigint;
@@ -0,0 +1 @@
+alter table ratings drop column last_rated_game_at;
@@ -0,0 +1,9 @@
+-- rated_at also moves when an inactive deviation decays, this only moves with rated games
+alter table ratings add column last_rated_game_at timestamp with time zone;
+
+update ratings set last_rated_game_at = (
+    select max(rating_history.created_at) from rating_history
+    where rating_history.user_uid = ratings.user_uid
+        and rating_history.game_type = ratings.game_type
+        and rating_history.speed = ratings.speed
+);
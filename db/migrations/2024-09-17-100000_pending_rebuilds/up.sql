@@ -0,0 +1,10 @@
+-- some data can only be rebuilt by replaying games, migrations leave a row
+-- here and the server does the replay once on its next start
+create table pending_rebuilds (
+  name text primary key,
+  requested_at timestamp with time zone not null default now()
+);
+
+-- the split by game type only estimated the ratings, and the rating history
+-- needs to be dated by when games finished and kept per game type
+insert into pending_rebuilds (name) values ('ratings');
@@ -0,0 +1 @@
+-- the flags get recomputed with the next report
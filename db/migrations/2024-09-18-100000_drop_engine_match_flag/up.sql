@@ -0,0 +1,5 @@
+-- the engine match rate is still reported, it just doesn't flag anyone anymore
+update fair_play_reports set
+  flags = array_remove(flags, 'EngineMatches'),
+  flagged = cardinality(array_remove(flags, 'EngineMatches')) > 0
+where 'EngineMatches' = any(flags);
@@ -0,0 +1,2 @@
+-- sessions remember the version they were signed in with, bumping it ends all of them
+alter table users add column session_version integer not null default 0;
@@ -0,0 +1,18 @@
+use db_lib::{config::DbConfig, db_error::DbError, get_conn, get_pool, models::Rating};
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
+
+#[tokio::main]
+async fn main() {
+    let config = DbConfig::from_env().expect("Failed to load config from env");
+    let pool = &get_pool(&config.database_url)
+        .await
+        .expect("Failed to get pool");
+    let mut conn = get_conn(pool).await.expect("to get connection");
+    let replayed = conn
+        .transaction::<_, DbError, _>(move |tc| {
+            async move { Rating::recompute_all(tc).await }.scope_boxed()
+        })
+        .await
+        .expect("Failed to recompute ratings");
+    println!("Recomputed ratings from {replayed} games");
+}
@@ -1,10 +1,29 @@
 use dotenvy::dotenv;
-use std::{env, env::VarError};
+use shared_types::CLAIM_VICTORY_AFTER;
+use std::{env, env::VarError, time::Duration};
 
 #[derive(Clone, Debug)]
 pub struct DbConfig {
     pub database_url: String,
     pub session_secret: String,
+    pub abort_unstarted_after: Duration, // Games without a first move get aborted after this
+    pub claim_victory_after: Duration,   // Realtime opponents gone this long can be claimed against
+}
+
+fn abort_unstarted_after() -> Duration {
+    let seconds = env::var("ABORT_UNSTARTED_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(300);
+    Duration::from_secs(seconds)
+}
+
+fn claim_victory_after() -> Duration {
+    env::var("CLAIM_VICTORY_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(CLAIM_VICTORY_AFTER)
 }
 
 impl DbConfig {
@@ -13,6 +32,8 @@ impl DbConfig {
         Ok(DbConfig {
             database_url: env::var("DATABASE_URL")?,
             session_secret: env::var("COOKIE_SECRET_KEY")?,
+            abort_unstarted_after: abort_unstarted_after(),
+            claim_victory_after: claim_victory_after(),
         })
     }
 
@@ -22,6 +43,8 @@ impl DbConfig {
             return Ok(DbConfig {
                 database_url: env::var("TEST_DATABASE_URL")?,
                 session_secret: env::var("TEST_COOKIE_SECRET_KEY")?,
+                abort_unstarted_after: abort_unstarted_after(),
+                claim_victory_after: claim_victory_after(),
             });
         }
         unreachable!("You called a test function in a non test binary!");
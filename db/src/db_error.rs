@@ -48,3 +48,24 @@ impl From<shared_types::ChallengeError> for DbError {
         }
     }
 }
+
+impl From<shared_types::RepetitionRuleError> for DbError {
+    fn from(err: shared_types::RepetitionRuleError) -> DbError {
+        match err {
+            shared_types::RepetitionRuleError::Invalid { found } => DbError::InvalidInput {
+                info: String::from("Invalid repetition rule"),
+                error: found,
+            },
+        }
+    }
+}
+
+impl From<shared_types::ClockTypeError> for DbError {
+    fn from(err: shared_types::ClockTypeError) -> DbError {
+        match err {
+            shared_types::ClockTypeError::Invalid { found } => {
+                DbError::TimeNotFound { reason: found }
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    db_error::DbError,
+    models::User,
+    schema::admin_actions::{self, created_at, dsl::admin_actions as admin_actions_table},
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, Associations, Identifiable, Insertable, Queryable, Selectable};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationAction {
+    Suspend,
+    Ban,
+    Unban,
+    CloseChallenges,
+    WithdrawFromTournaments,
+    AnnulGame,
+}
+
+impl fmt::Display for ModerationAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self {
+            ModerationAction::Suspend => "Suspend",
+            ModerationAction::Ban => "Ban",
+            ModerationAction::Unban => "Unban",
+            ModerationAction::CloseChallenges => "CloseChallenges",
+            ModerationAction::WithdrawFromTournaments => "WithdrawFromTournaments",
+            ModerationAction::AnnulGame => "AnnulGame",
+        };
+        write!(f, "{action}")
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = admin_actions)]
+pub struct NewAdminAction {
+    pub admin_id: Uuid,
+    pub action: String,
+    pub target_user_id: Option<Uuid>,
+    pub target_game_id: Option<Uuid>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// The audit log, every moderation step an admin takes ends up here
+#[derive(
+    Associations, Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone,
+)]
+#[diesel(belongs_to(User, foreign_key = admin_id))]
+#[diesel(table_name = admin_actions)]
+#[diesel(primary_key(id))]
+pub struct AdminAction {
+    pub id: i32,
+    pub admin_id: Uuid,
+    pub action: String,
+    pub target_user_id: Option<Uuid>,
+    pub target_game_id: Option<Uuid>,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AdminAction {
+    pub async fn log(
+        admin_id: &Uuid,
+        action: ModerationAction,
+        target_user_id: Option<Uuid>,
+        target_game_id: Option<Uuid>,
+        reason: &str,
+        conn: &mut DbConn<'_>,
+    ) -> Result<AdminAction, DbError> {
+        let new_action = NewAdminAction {
+            admin_id: *admin_id,
+            action: action.to_string(),
+            target_user_id,
+            target_game_id,
+            reason: reason.to_owned(),
+            created_at: Utc::now(),
+        };
+        Ok(new_action
+            .insert_into(admin_actions_table)
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn recent(limit: i64, conn: &mut DbConn<'_>) -> Result<Vec<AdminAction>, DbError> {
+        Ok(admin_actions_table
+            .order_by(created_at.desc())
+            .limit(limit)
+            .get_results(conn)
+            .await?)
+    }
+}
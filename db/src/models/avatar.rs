@@ -0,0 +1,85 @@
+use crate::{
+    db_error::DbError,
+    models::User,
+    schema::avatars::{self, dsl::avatars as avatars_table},
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, upsert::excluded, Identifiable, Insertable, Queryable};
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+// Avatars are shown small, anything bigger is a photo that was not scaled down
+pub const MAX_AVATAR_SIZE: usize = 100 * 1024;
+
+// Only formats every browser shows, recognised by their first bytes rather than by what the
+// upload claims to be
+fn content_type(image: &[u8]) -> Option<&'static str> {
+    match image {
+        [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, ..] => Some("image/png"),
+        [0xff, 0xd8, 0xff, ..] => Some("image/jpeg"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+#[derive(Insertable, Identifiable, Queryable, Selectable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = avatars)]
+#[diesel(primary_key(user_id))]
+pub struct Avatar {
+    pub user_id: Uuid,
+    pub content_type: String,
+    pub image: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Avatar {
+    pub fn new(user_id: &Uuid, image: Vec<u8>) -> Result<Self, DbError> {
+        if image.len() > MAX_AVATAR_SIZE {
+            return Err(DbError::InvalidInput {
+                info: format!("Avatars can be at most {} KB", MAX_AVATAR_SIZE / 1024),
+                error: format!("avatar of {} bytes", image.len()),
+            });
+        }
+        let Some(content_type) = content_type(&image) else {
+            return Err(DbError::InvalidInput {
+                info: String::from("Avatars have to be PNG, JPEG or WebP images"),
+                error: String::from("unknown avatar format"),
+            });
+        };
+        Ok(Self {
+            user_id: *user_id,
+            content_type: content_type.to_owned(),
+            image,
+            updated_at: Utc::now(),
+        })
+    }
+
+    pub async fn save(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        Ok(self
+            .insert_into(avatars_table)
+            .on_conflict(avatars::user_id)
+            .do_update()
+            .set((
+                avatars::content_type.eq(excluded(avatars::content_type)),
+                avatars::image.eq(excluded(avatars::image)),
+                avatars::updated_at.eq(excluded(avatars::updated_at)),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn find_by_user(
+        user_id: &Uuid,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Option<Self>, DbError> {
+        Ok(avatars_table.find(user_id).first(conn).await.optional()?)
+    }
+
+    pub async fn delete_for_user(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        Ok(diesel::delete(avatars_table.find(user_id))
+            .execute(conn)
+            .await?)
+    }
+}
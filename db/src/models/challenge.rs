@@ -12,7 +12,7 @@ use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 use nanoid::nanoid;
 use serde::Serialize;
-use shared_types::{ChallengeDetails, ChallengeId, TimeMode};
+use shared_types::{ChallengeDetails, ChallengeId, ClockType, TimeMode};
 use uuid::Uuid;
 
 #[derive(Insertable, Debug)]
@@ -32,6 +32,8 @@ pub struct NewChallenge {
     pub time_increment: Option<i32>, // Seconds
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
+    pub clock_type: String,
+    pub repetition_rule: String,
 }
 
 impl NewChallenge {
@@ -79,6 +81,12 @@ impl NewChallenge {
                 }
             }
         }
+        if d.time_mode != TimeMode::RealTime && d.clock_type != ClockType::Fischer {
+            return Err(DbError::InvalidInput {
+                info: String::from("Only real time games can use a different clock"),
+                error: format!("clock_type: {}", d.clock_type),
+            });
+        }
         if opponent_id == Some(challenger_id) {
             return Err(DbError::InvalidInput {
                 info: "You can't play here with yourself.".to_string(),
@@ -100,6 +108,8 @@ impl NewChallenge {
             time_increment: d.time_increment,
             band_upper: d.band_upper,
             band_lower: d.band_lower,
+            clock_type: d.clock_type.to_string(),
+            repetition_rule: d.repetition_rule.to_string(),
         })
     }
 }
@@ -125,6 +135,8 @@ pub struct Challenge {
     pub time_increment: Option<i32>, // Seconds
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
+    pub clock_type: String,
+    pub repetition_rule: String,
 }
 
 impl Challenge {
@@ -0,0 +1,211 @@
+use crate::{
+    db_error::DbError,
+    models::{Game, User},
+    schema::conditional_moves::{
+        self, dsl::conditional_moves as conditional_moves_table, game_id as game_id_column,
+        user_id as user_id_column,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, Associations, Identifiable, Insertable, Queryable, Selectable};
+use diesel_async::RunQueryDsl;
+use hive_lib::{GameError, GameStatus, History, State, Turn};
+use serde::{Deserialize, Serialize};
+use shared_types::TimeMode;
+use std::str::FromStr;
+use uuid::Uuid;
+
+fn invalid_line(error: GameError) -> DbError {
+    DbError::InvalidInput {
+        info: String::from("These conditional moves can't be played"),
+        error: error.to_string(),
+    }
+}
+
+fn line_to_string(moves: &[(String, String)]) -> String {
+    moves
+        .iter()
+        .map(|(piece, position)| format!("{piece} {position};"))
+        .collect()
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = conditional_moves)]
+pub struct NewConditionalMove {
+    pub game_id: Uuid,
+    pub user_id: Uuid,
+    pub moves: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewConditionalMove {
+    // A line starts with the opponent's next move and alternates with the
+    // user's replies, all of which have to be playable from the current position
+    pub fn new(game: &Game, user_id: Uuid, moves: &str) -> Result<Self, DbError> {
+        if TimeMode::from_str(&game.time_mode)? != TimeMode::Correspondence {
+            return Err(DbError::InvalidAction {
+                info: String::from("Conditional moves are only available in correspondence games"),
+            });
+        }
+        if game.finished {
+            return Err(DbError::GameIsOver);
+        }
+        if !game.user_is_player(user_id) {
+            return Err(DbError::Unauthorized);
+        }
+        if game.current_player_id == user_id {
+            return Err(DbError::InvalidAction {
+                info: String::from("Conditional moves can only be added on your opponent's turn"),
+            });
+        }
+        let line = History::new_from_str(moves).map_err(invalid_line)?;
+        if line.moves.is_empty() || line.moves.len() % 2 != 0 {
+            return Err(DbError::InvalidInput {
+                info: String::from("Every move of your opponent needs a reply"),
+                error: moves.to_owned(),
+            });
+        }
+        let mut state =
+            State::new_from_str_with(&game.history, &game.game_type, game.automatic_repetition())
+                .map_err(invalid_line)?;
+        for (piece, position) in line.moves.iter() {
+            if let GameStatus::Finished(_) = state.game_status {
+                return Err(DbError::InvalidInput {
+                    info: String::from("The game is over before the last conditional move"),
+                    error: moves.to_owned(),
+                });
+            }
+            match state
+                .turn_from_history(piece, position)
+                .map_err(invalid_line)?
+            {
+                Turn::Move(piece, position) => state
+                    .play_turn_from_position(piece, position)
+                    .map_err(invalid_line)?,
+                Turn::Shutout => {
+                    return Err(DbError::InvalidInput {
+                        info: String::from("Passes are played automatically"),
+                        error: moves.to_owned(),
+                    })
+                }
+            }
+        }
+        Ok(Self {
+            game_id: game.id,
+            user_id,
+            moves: line_to_string(&line.moves),
+            created_at: Utc::now(),
+        })
+    }
+
+    pub async fn insert(&self, conn: &mut DbConn<'_>) -> Result<ConditionalMove, DbError> {
+        Ok(self
+            .insert_into(conditional_moves_table)
+            .get_result(conn)
+            .await?)
+    }
+}
+
+#[derive(
+    Associations, Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone,
+)]
+#[diesel(belongs_to(User))]
+#[diesel(belongs_to(Game))]
+#[diesel(table_name = conditional_moves)]
+#[diesel(primary_key(id))]
+pub struct ConditionalMove {
+    pub id: i32,
+    pub game_id: Uuid,
+    pub user_id: Uuid,
+    pub moves: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ConditionalMove {
+    pub async fn for_game_and_user(
+        game_id: &Uuid,
+        user_id: &Uuid,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Self>, DbError> {
+        Ok(conditional_moves_table
+            .filter(game_id_column.eq(game_id))
+            .filter(user_id_column.eq(user_id))
+            .order_by(conditional_moves::id.asc())
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn delete_for_game(game_id: &Uuid, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        diesel::delete(conditional_moves_table.filter(game_id_column.eq(game_id)))
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_for_game_and_user(
+        game_id: &Uuid,
+        user_id: &Uuid,
+        conn: &mut DbConn<'_>,
+    ) -> Result<(), DbError> {
+        diesel::delete(
+            conditional_moves_table
+                .filter(game_id_column.eq(game_id))
+                .filter(user_id_column.eq(user_id)),
+        )
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        diesel::delete(conditional_moves_table.find(self.id))
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    // Called once `played` moved the game from `before` to `after`, returns the
+    // reply the player to move prepared for it. Lines which didn't foresee the
+    // move get dropped, the others advance past the move and the reply.
+    pub async fn take_reply(
+        game: &Game,
+        before: &State,
+        played: &Turn,
+        after: &State,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Option<Turn>, DbError> {
+        let lines = Self::for_game_and_user(&game.id, &game.current_player_id, conn).await?;
+        let mut reply = None;
+        for line in lines {
+            let moves = History::new_from_str(&line.moves)
+                .map(|history| history.moves)
+                .unwrap_or_default();
+            let expected = moves
+                .as_slice()
+                .first()
+                .and_then(|(piece, position)| before.turn_from_history(piece, position).ok());
+            let line_reply = moves
+                .get(1)
+                .and_then(|(piece, position)| after.turn_from_history(piece, position).ok());
+            match (expected, line_reply) {
+                (Some(expected), Some(line_reply))
+                    if expected == *played
+                        && reply.as_ref().is_none_or(|reply| *reply == line_reply) =>
+                {
+                    reply = Some(line_reply);
+                    if moves.len() > 2 {
+                        diesel::update(conditional_moves_table.find(line.id))
+                            .set(conditional_moves::moves.eq(line_to_string(&moves[2..])))
+                            .execute(conn)
+                            .await?;
+                    } else {
+                        line.delete(conn).await?;
+                    }
+                }
+                _ => line.delete(conn).await?,
+            }
+        }
+        Ok(reply)
+    }
+}
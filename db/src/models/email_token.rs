@@ -0,0 +1,157 @@
+use crate::{
+    db_error::DbError,
+    models::User,
+    schema::email_tokens::{
+        self, dsl::email_tokens as email_tokens_table, expires_at, purpose as purpose_column,
+        token_hash, used_at, user_id as user_id_column,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Duration, Utc};
+use diesel::{prelude::*, Associations, Identifiable, Insertable, Queryable, Selectable};
+use diesel_async::RunQueryDsl;
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use uuid::Uuid;
+
+const TOKEN_LENGTH: usize = 32;
+const RESEND_AFTER_MINUTES: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenPurpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+impl TokenPurpose {
+    // How long a link sent by mail stays valid
+    pub fn lifetime(&self) -> Duration {
+        match self {
+            TokenPurpose::VerifyEmail => Duration::days(2),
+            TokenPurpose::ResetPassword => Duration::hours(1),
+        }
+    }
+}
+
+impl fmt::Display for TokenPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let purpose = match self {
+            TokenPurpose::VerifyEmail => "VerifyEmail",
+            TokenPurpose::ResetPassword => "ResetPassword",
+        };
+        write!(f, "{purpose}")
+    }
+}
+
+// Only the hash of a token gets stored, so a leaked table can't be used to take over accounts
+pub(crate) fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn invalid_token() -> DbError {
+    DbError::InvalidInput {
+        info: String::from("This link is invalid or has expired"),
+        error: String::from("Unknown, used or expired token"),
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = email_tokens)]
+pub struct NewEmailToken {
+    pub user_id: Uuid,
+    pub purpose: String,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(
+    Associations, Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone,
+)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = email_tokens)]
+#[diesel(primary_key(id))]
+pub struct EmailToken {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub purpose: String,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EmailToken {
+    // Creates a token for the user and returns it in plain text, earlier tokens with the
+    // same purpose stop working
+    pub async fn issue(
+        user_id: &Uuid,
+        purpose: TokenPurpose,
+        conn: &mut DbConn<'_>,
+    ) -> Result<String, DbError> {
+        diesel::delete(
+            email_tokens_table
+                .filter(user_id_column.eq(user_id))
+                .filter(purpose_column.eq(purpose.to_string())),
+        )
+        .execute(conn)
+        .await?;
+        let token = nanoid!(TOKEN_LENGTH);
+        let now = Utc::now();
+        let new_token = NewEmailToken {
+            user_id: *user_id,
+            purpose: purpose.to_string(),
+            token_hash: hash_token(&token),
+            expires_at: now + purpose.lifetime(),
+            created_at: now,
+        };
+        new_token
+            .insert_into(email_tokens_table)
+            .execute(conn)
+            .await?;
+        Ok(token)
+    }
+
+    // Whether a token that still works went out within the last few minutes
+    pub async fn recently_issued(
+        user_id: &Uuid,
+        purpose: TokenPurpose,
+        conn: &mut DbConn<'_>,
+    ) -> Result<bool, DbError> {
+        let now = Utc::now();
+        let recent: i64 = email_tokens_table
+            .filter(user_id_column.eq(user_id))
+            .filter(purpose_column.eq(purpose.to_string()))
+            .filter(used_at.is_null())
+            .filter(expires_at.gt(now))
+            .filter(email_tokens::created_at.gt(now - Duration::minutes(RESEND_AFTER_MINUTES)))
+            .count()
+            .get_result(conn)
+            .await?;
+        Ok(recent > 0)
+    }
+
+    // Marks the token as used and returns whose it was, a token can only be redeemed once
+    pub async fn redeem(
+        token: &str,
+        purpose: TokenPurpose,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Uuid, DbError> {
+        let now = Utc::now();
+        diesel::update(
+            email_tokens_table
+                .filter(token_hash.eq(hash_token(token)))
+                .filter(purpose_column.eq(purpose.to_string()))
+                .filter(used_at.is_null())
+                .filter(expires_at.gt(now)),
+        )
+        .set(used_at.eq(now))
+        .returning(user_id_column)
+        .get_result(conn)
+        .await
+        .optional()?
+        .ok_or_else(invalid_token)
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    db_error::DbError,
+    models::{Game, User},
+    schema::{
+        fair_play_reports::{self, dsl::fair_play_reports as fair_play_reports_table},
+        games, users,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, upsert::excluded, Identifiable, Insertable, Queryable};
+use diesel_async::RunQueryDsl;
+use hive_lib::{Ai, Color, GameResult, GameStatus, GameType, History, State};
+use shared_types::{FairPlayFlag, FairPlayGame, FairPlayStats, TimeMode};
+use std::{collections::HashSet, str::FromStr};
+use uuid::Uuid;
+
+// How many of a player's latest games go into a report
+const GAMES_PER_REPORT: i64 = 20;
+// Openings are known by heart, matching the engine there means nothing
+const OPENING_TURNS: usize = 6;
+const REPORTS_SHOWN: i64 = 200;
+
+// What the fair-play analysis found for a player. It only points admins at accounts worth a
+// closer look, nothing happens to an account because of it.
+#[derive(Insertable, Identifiable, Queryable, Selectable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = fair_play_reports)]
+#[diesel(primary_key(user_id))]
+pub struct FairPlayReport {
+    pub user_id: Uuid,
+    pub games: i32,
+    pub moves: i32,
+    pub engine_match_rate: Option<f64>,
+    pub move_time_variation: Option<f64>,
+    pub performance_gap: Option<f64>,
+    pub flags: Vec<Option<String>>,
+    pub flagged: bool,
+    pub analysed_at: DateTime<Utc>,
+}
+
+impl FairPlayReport {
+    // Replays the games with the engine, this takes a while and is best kept off the runtime
+    pub fn analyse(user_id: Uuid, games: &[Game]) -> Self {
+        let analysed = games
+            .iter()
+            .filter_map(|game| FairPlayReport::analyse_game(game, user_id))
+            .collect::<Vec<_>>();
+        let stats = FairPlayStats::from_games(&analysed);
+        let flags = stats.flags();
+        Self {
+            user_id,
+            games: stats.games as i32,
+            moves: stats.moves as i32,
+            engine_match_rate: stats.engine_match_rate,
+            move_time_variation: stats.move_time_variation,
+            performance_gap: stats.performance_gap,
+            flags: flags.iter().map(|flag| Some(flag.to_string())).collect(),
+            flagged: !flags.is_empty(),
+            analysed_at: Utc::now(),
+        }
+    }
+
+    fn analyse_game(game: &Game, user_id: Uuid) -> Option<FairPlayGame> {
+        let color = game.user_color(user_id)?;
+        let history = History::new_from_str(&game.history).ok()?;
+        let game_type = GameType::from_str(&game.game_type).ok()?;
+        let mut state = State::new(game_type, game.tournament_queen_rule);
+        state.automatic_repetition = game.automatic_repetition();
+        let realtime = game.time_mode == TimeMode::RealTime.to_string();
+        let mut analysed = FairPlayGame::default();
+        for (turn, (piece, position)) in history.moves.iter().enumerate() {
+            if state.turn_color == color && turn >= OPENING_TURNS {
+                let ranked = Ai::ranked_turns(&state);
+                // Forced moves and positions where every move is as good say nothing
+                if let [(_, best), .., (_, worst)] = ranked.as_slice() {
+                    if best > worst {
+                        let played = state.turn_from_history(piece, position).ok()?;
+                        analysed.moves_analysed += 1;
+                        if ranked
+                            .iter()
+                            .any(|(turn, score)| score >= best && *turn == played)
+                        {
+                            analysed.engine_matches += 1;
+                        }
+                    }
+                }
+                if let (true, Some(Some(before)), Some(Some(after))) = (
+                    realtime,
+                    game.move_dates.get(turn - 1),
+                    game.move_dates.get(turn),
+                ) {
+                    analysed
+                        .think_times
+                        .push((*after - *before).num_milliseconds() as f64 / 1000.0);
+                }
+            }
+            state.play_turn_from_history(piece, position).ok()?;
+        }
+        if game.rated {
+            let (rating, opponent_rating) = match color {
+                Color::White => (game.white_rating, game.black_rating),
+                Color::Black => (game.black_rating, game.white_rating),
+            };
+            analysed.rating = rating;
+            analysed.opponent_rating = opponent_rating;
+            analysed.score = match GameStatus::from_str(&game.game_status) {
+                Ok(GameStatus::Finished(GameResult::Winner(winner))) if winner == color => 1.0,
+                Ok(GameStatus::Finished(GameResult::Draw)) => 0.5,
+                _ => 0.0,
+            };
+        }
+        Some(analysed)
+    }
+
+    // Everyone who finished a game since then
+    pub async fn players_since(
+        since: DateTime<Utc>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Uuid>, DbError> {
+        let players: Vec<(Uuid, Uuid)> = games::table
+            .filter(games::finished.eq(true))
+            .filter(games::finished_at.gt(since))
+            .select((games::white_id, games::black_id))
+            .load(conn)
+            .await?;
+        Ok(players
+            .into_iter()
+            .flat_map(|(white, black)| [white, black])
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect())
+    }
+
+    pub async fn games_to_analyse(
+        user_id: &Uuid,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Game>, DbError> {
+        Ok(games::table
+            .filter(games::white_id.eq(user_id).or(games::black_id.eq(user_id)))
+            .filter(games::finished.eq(true))
+            .filter(games::annulled.eq(false))
+            .order_by(games::updated_at.desc())
+            .limit(GAMES_PER_REPORT)
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn save(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        Ok(self
+            .insert_into(fair_play_reports_table)
+            .on_conflict(fair_play_reports::user_id)
+            .do_update()
+            .set((
+                fair_play_reports::games.eq(excluded(fair_play_reports::games)),
+                fair_play_reports::moves.eq(excluded(fair_play_reports::moves)),
+                fair_play_reports::engine_match_rate
+                    .eq(excluded(fair_play_reports::engine_match_rate)),
+                fair_play_reports::move_time_variation
+                    .eq(excluded(fair_play_reports::move_time_variation)),
+                fair_play_reports::performance_gap.eq(excluded(fair_play_reports::performance_gap)),
+                fair_play_reports::flags.eq(excluded(fair_play_reports::flags)),
+                fair_play_reports::flagged.eq(excluded(fair_play_reports::flagged)),
+                fair_play_reports::analysed_at.eq(excluded(fair_play_reports::analysed_at)),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Flagged accounts first, the most recently analysed on top
+    pub async fn latest(conn: &mut DbConn<'_>) -> Result<Vec<(Self, User)>, DbError> {
+        Ok(fair_play_reports_table
+            .inner_join(users::table)
+            .filter(users::deleted.eq(false))
+            .order_by((
+                fair_play_reports::flagged.desc(),
+                fair_play_reports::analysed_at.desc(),
+            ))
+            .limit(REPORTS_SHOWN)
+            .select((Self::as_select(), User::as_select()))
+            .load(conn)
+            .await?)
+    }
+
+    pub fn flags(&self) -> Vec<FairPlayFlag> {
+        self.flags
+            .iter()
+            .flatten()
+            .filter_map(|flag| FairPlayFlag::from_str(flag).ok())
+            .collect()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     db_error::DbError,
-    models::{Challenge, GameUser, Rating, Tournament},
+    models::{Challenge, ConditionalMove, GameUser, Rating, Tournament, UserStatistics},
     schema::{
         challenges::{self, nanoid as nanoid_field},
         games::{self, dsl::*, tournament_game_result},
@@ -15,13 +15,16 @@ use diesel_async::RunQueryDsl;
 use hive_lib::{Color, GameControl, GameResult, GameStatus, GameType, History, State};
 use serde::{Deserialize, Serialize};
 use shared_types::{
-    ChallengeId, Conclusion, GameId, GameSpeed, GameStart, TimeMode, TournamentGameResult,
+    ChallengeId, ClockType, Conclusion, GameId, GameSpeed, GameStart, RepetitionRule, TimeMode,
+    TournamentGameResult,
 };
 use std::str::FromStr;
 use std::time::Duration;
 use uuid::Uuid;
 
 pub static NANOS_IN_SECOND: u64 = 1000000000_u64;
+// What a premove costs on the clock regardless of how long the server took to play it
+pub static PREMOVE_CHARGE: Duration = Duration::from_millis(100);
 
 struct TimeInfo {
     white_time_left: Option<i64>,
@@ -74,6 +77,9 @@ pub struct NewGame {
     pub tournament_id: Option<Uuid>,
     pub tournament_game_result: String,
     pub game_start: String,
+    pub armageddon: bool,
+    pub clock_type: String,
+    pub repetition_rule: String,
 }
 
 impl NewGame {
@@ -126,13 +132,28 @@ impl NewGame {
             last_interaction: interaction,
             black_time_left: time_left,
             white_time_left: time_left,
-            speed: GameSpeed::from_base_increment(tournament.time_base, tournament.time_increment)
-                .to_string(),
+            speed: GameSpeed::from_base_increment(
+                tournament.time_base,
+                tournament.time_increment,
+                ClockType::from_str(&tournament.clock_type).unwrap_or_default(),
+            )
+            .to_string(),
             hashes: Vec::new(),
             conclusion: Conclusion::Unknown.to_string(),
             tournament_id: Some(tournament.id),
             tournament_game_result: TournamentGameResult::Unknown.to_string(),
             game_start: start,
+            armageddon: false,
+            clock_type: tournament.clock_type.to_owned(),
+            repetition_rule: tournament.repetition_rule.to_owned(),
+        }
+    }
+
+    // Tiebreak game for a drawn match, a draw counts as a win for black
+    pub fn new_armageddon(white: Uuid, black: Uuid, tournament: &Tournament) -> Self {
+        Self {
+            armageddon: true,
+            ..Self::new_from_tournament(white, black, tournament)
         }
     }
 
@@ -174,13 +195,20 @@ impl NewGame {
             last_interaction: None,
             black_time_left: time_left,
             white_time_left: time_left,
-            speed: GameSpeed::from_base_increment(challenge.time_base, challenge.time_increment)
-                .to_string(),
+            speed: GameSpeed::from_base_increment(
+                challenge.time_base,
+                challenge.time_increment,
+                ClockType::from_str(&challenge.clock_type).unwrap_or_default(),
+            )
+            .to_string(),
             hashes: Vec::new(),
             conclusion: Conclusion::Unknown.to_string(),
             tournament_id: None,
             tournament_game_result: TournamentGameResult::Unknown.to_string(),
             game_start: GameStart::Moves.to_string(),
+            armageddon: false,
+            clock_type: challenge.clock_type.to_owned(),
+            repetition_rule: challenge.repetition_rule.to_owned(),
         }
     }
 }
@@ -222,9 +250,23 @@ pub struct Game {
     pub tournament_id: Option<Uuid>,
     pub tournament_game_result: String,
     pub game_start: String,
+    pub armageddon: bool,
+    pub clock_type: String,
+    pub move_times: Vec<Option<i64>>, // The mover's time left after each move in nanos
+    pub move_dates: Vec<Option<DateTime<Utc>>>, // When each move was played
+    pub repetition_rule: String,
+    pub annulled: bool,
+    pub finished_at: Option<DateTime<Utc>>, // Unlike updated_at it stays put once the game ended
 }
 
 impl Game {
+    // Whether a threefold repetition ends the game without a claim
+    pub fn automatic_repetition(&self) -> bool {
+        RepetitionRule::from_str(&self.repetition_rule)
+            .unwrap_or_default()
+            .is_automatic()
+    }
+
     pub fn hashes(&self) -> Vec<u64> {
         // WARN: @leex reimplement this
         //self.hashes.iter().map(|i| *i as u64).collect::<Vec<u64>>()
@@ -320,11 +362,11 @@ impl Game {
         }
         if let Some(last) = self.last_interaction {
             if let Ok(time_passed) = Utc::now().signed_duration_since(last).to_std() {
-                if time_left > time_passed {
+                if time_left > self.time_charged(time_passed)? {
                     return Ok(self.clone());
                 }
             }
-            let (white_time, black_time, game_result) = if self.turn % 2 == 0 {
+            let (mut white_time, mut black_time, game_result) = if self.turn % 2 == 0 {
                 (
                     Some(0_i64),
                     self.black_time_left,
@@ -337,20 +379,23 @@ impl Game {
                     GameResult::Winner(Color::White),
                 )
             };
+            // The sand which ran out went to the opponent
+            if ClockType::from_str(&self.clock_type)? == ClockType::Hourglass {
+                let gained = time_left.as_nanos() as i64;
+                if self.turn % 2 == 0 {
+                    black_time = black_time.map(|time| time + gained);
+                } else {
+                    white_time = white_time.map(|time| time + gained);
+                }
+            }
             let tgr = TournamentGameResult::new(&game_result);
             let new_game_status = GameStatus::Finished(game_result.clone());
-            let (w_rating, b_rating, w_change, b_change) = Rating::update(
-                self.rated,
-                self.speed.clone(),
-                self.white_id,
-                self.black_id,
-                game_result,
-                conn,
-            )
-            .await?;
+            let (w_rating, b_rating, w_change, b_change) =
+                Rating::update(self, game_result, conn).await?;
             let game = diesel::update(games::table.find(self.id))
                 .set((
                     finished.eq(true),
+                    finished_at.eq(Some(Utc::now())),
                     tournament_game_result.eq(tgr.to_string()),
                     game_status.eq(new_game_status.to_string()),
                     white_rating.eq(w_rating),
@@ -364,6 +409,7 @@ impl Game {
                 ))
                 .get_result(conn)
                 .await?;
+            UserStatistics::mark_stale(&game, conn).await?;
             Ok(game)
         } else {
             todo!("Well this is not good and needs a better error message");
@@ -401,14 +447,42 @@ impl Game {
     }
 
     fn calculate_time_left(&self) -> Result<(Option<i64>, Option<i64>), DbError> {
+        self.calculate_time_left_charging(None)
+    }
+
+    // The time the player to move used, `charge` replaces the time since the
+    // last interaction when given
+    fn time_used(&self, charge: Option<Duration>) -> Duration {
+        charge.unwrap_or_else(|| {
+            self.last_interaction
+                .and_then(|last| Utc::now().signed_duration_since(last).to_std().ok())
+                .unwrap_or_default()
+        })
+    }
+
+    // What the used time costs on the clock, a delay clock only starts
+    // running once the delay is over
+    fn time_charged(&self, time_used: Duration) -> Result<Duration, DbError> {
+        if TimeMode::from_str(&self.time_mode)? == TimeMode::RealTime
+            && ClockType::from_str(&self.clock_type)? == ClockType::Delay
+        {
+            return Ok(time_used.saturating_sub(self.time_increment_duration()?));
+        }
+        Ok(time_used)
+    }
+
+    fn calculate_time_left_charging(
+        &self,
+        charge: Option<Duration>,
+    ) -> Result<(Option<i64>, Option<i64>), DbError> {
         let mut time_left = if self.turn % 2 == 0 {
             self.white_time_left_duration()?
         } else {
             self.black_time_left_duration()?
         };
         let (mut black_time, mut white_time) = (self.black_time_left, self.white_time_left);
-        if let Some(last) = self.last_interaction {
-            let time_passed = Utc::now().signed_duration_since(last).to_std().unwrap();
+        if self.last_interaction.is_some() {
+            let time_passed = self.time_charged(self.time_used(charge))?;
             if time_left > time_passed {
                 // substract passed time and add time_increment
                 time_left -= time_passed;
@@ -426,32 +500,49 @@ impl Game {
         Ok((white_time, black_time))
     }
 
-    fn calculate_time_left_add_increment(&self) -> Result<(Option<i64>, Option<i64>), DbError> {
-        let (mut white_time, mut black_time) = self.calculate_time_left()?;
+    fn calculate_time_left_add_increment(
+        &self,
+        premove: bool,
+    ) -> Result<(Option<i64>, Option<i64>), DbError> {
+        let charge = premove.then_some(PREMOVE_CHARGE);
+        let (mut white_time, mut black_time) = self.calculate_time_left_charging(charge)?;
         if let (Some(w), Some(b)) = (white_time, black_time) {
             if w == 0 || b == 0 {
                 return Ok((white_time, black_time));
             }
         }
 
-        let increment = self.time_increment_duration()?.as_nanos() as i64;
-        if self.turn % 2 == 0 {
-            white_time = white_time.map(|time| time + increment);
+        let increment = self.time_increment_duration()?;
+        let used = self.time_used(charge);
+        let (mover, opponent) = if self.turn % 2 == 0 {
+            (&mut white_time, &mut black_time)
         } else {
-            black_time = black_time.map(|time| time + increment);
+            (&mut black_time, &mut white_time)
+        };
+        match ClockType::from_str(&self.clock_type)? {
+            ClockType::Fischer => *mover = mover.map(|time| time + increment.as_nanos() as i64),
+            ClockType::Delay => {}
+            ClockType::Bronstein => {
+                *mover = mover.map(|time| time + used.min(increment).as_nanos() as i64)
+            }
+            ClockType::Hourglass => *opponent = opponent.map(|time| time + used.as_nanos() as i64),
         };
         Ok((white_time, black_time))
     }
 
-    fn get_time_info(&self, status: GameStatus) -> Result<TimeInfo, DbError> {
+    fn get_time_info(&self, status: GameStatus, premove: bool) -> Result<TimeInfo, DbError> {
         match TimeMode::from_str(&self.time_mode)? {
             TimeMode::Untimed => Ok(TimeInfo::new(status)),
-            TimeMode::RealTime => self.get_realtime_time_info(status),
+            TimeMode::RealTime => self.get_realtime_time_info(status, premove),
             TimeMode::Correspondence => self.get_correspondence_time_info(status),
         }
     }
 
-    fn get_realtime_time_info(&self, status: GameStatus) -> Result<TimeInfo, DbError> {
+    fn get_realtime_time_info(
+        &self,
+        status: GameStatus,
+        premove: bool,
+    ) -> Result<TimeInfo, DbError> {
         let mut time_info = TimeInfo::new(status);
         if self.turn < 2 && self.game_start == GameStart::Moves.to_string() {
             if self.turn == 0 {
@@ -461,7 +552,7 @@ impl Game {
             time_info.black_time_left = self.black_time_left;
         } else {
             (time_info.white_time_left, time_info.black_time_left) =
-                self.calculate_time_left_add_increment()?;
+                self.calculate_time_left_add_increment(premove)?;
             if self.turn % 2 == 0 {
                 if time_info.white_time_left == Some(0) {
                     time_info.timed_out = true;
@@ -517,6 +608,24 @@ impl Game {
         &self,
         state: &State,
         conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        self.write_gamestate(state, false, conn).await
+    }
+
+    // Same as update_gamestate but the mover only pays PREMOVE_CHARGE on the clock
+    pub async fn update_gamestate_premove(
+        &self,
+        state: &State,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        self.write_gamestate(state, true, conn).await
+    }
+
+    async fn write_gamestate(
+        &self,
+        state: &State,
+        premove: bool,
+        conn: &mut DbConn<'_>,
     ) -> Result<Game, DbError> {
         let mut new_history = state
             .history
@@ -540,14 +649,15 @@ impl Game {
         }
 
         let mut new_conclusion = Conclusion::Unknown;
-        let time_info = self.get_time_info(state.game_status.clone())?;
+        let time_info = self.get_time_info(state.game_status.clone(), premove)?;
+        let (mut new_move_times, mut new_move_dates) = self.move_clocks(state, &time_info);
 
         match time_info.new_game_status {
             GameStatus::Finished(GameResult::Draw) => new_conclusion = Conclusion::Board,
             GameStatus::Finished(GameResult::Winner(_)) => new_conclusion = Conclusion::Board,
             _ => {}
         }
-        if state.repeating_moves.len() > 2 {
+        if state.repeating_moves.len() > 2 && self.automatic_repetition() {
             new_conclusion = Conclusion::Repetition;
         }
 
@@ -561,15 +671,8 @@ impl Game {
                 panic!("GameResult is unknown but the game is over");
             };
             let tgr = TournamentGameResult::new(&game_result);
-            let (w_rating, b_rating, w_change, b_change) = Rating::update(
-                self.rated,
-                self.speed.clone(),
-                self.white_id,
-                self.black_id,
-                game_result,
-                conn,
-            )
-            .await?;
+            let (w_rating, b_rating, w_change, b_change) =
+                Rating::update(self, game_result, conn).await?;
             let new_turn = if time_info.timed_out {
                 self.turn
             } else {
@@ -578,13 +681,18 @@ impl Game {
             if time_info.timed_out {
                 new_conclusion = Conclusion::Timeout;
                 new_history.clone_from(&self.history);
+                new_move_times.clone_from(&self.move_times);
+                new_move_dates.clone_from(&self.move_dates);
             }
             let game = diesel::update(games::table.find(self.id))
                 .set((
                     history.eq(new_history),
+                    move_times.eq(new_move_times),
+                    move_dates.eq(new_move_dates),
                     current_player_id.eq(next_player),
                     turn.eq(new_turn),
                     finished.eq(true),
+                    finished_at.eq(Some(Utc::now())),
                     tournament_game_result.eq(tgr.to_string()),
                     game_status.eq(time_info.new_game_status.to_string()),
                     game_control_history.eq(game_control_history.concat(game_control_string)),
@@ -600,11 +708,14 @@ impl Game {
                 ))
                 .get_result(conn)
                 .await?;
+            UserStatistics::mark_stale(&game, conn).await?;
             Ok(game)
         } else {
             let game = diesel::update(games::table.find(self.id))
                 .set((
                     history.eq(new_history),
+                    move_times.eq(new_move_times),
+                    move_dates.eq(new_move_dates),
                     current_player_id.eq(next_player),
                     turn.eq(state.turn as i32),
                     game_status.eq(time_info.new_game_status.to_string()),
@@ -620,6 +731,32 @@ impl Game {
         }
     }
 
+    // Appends the mover's clock and the server time for every move the state added to the
+    // history, moves played before clocks were recorded stay empty
+    fn move_clocks(
+        &self,
+        state: &State,
+        time_info: &TimeInfo,
+    ) -> (Vec<Option<i64>>, Vec<Option<DateTime<Utc>>>) {
+        let mut times = self.move_times.clone();
+        let mut dates = self.move_dates.clone();
+        let now = Utc::now();
+        for played in times.len()..state.history.moves.len() {
+            if played < self.turn as usize {
+                times.push(None);
+                dates.push(None);
+            } else {
+                times.push(if played % 2 == 0 {
+                    time_info.white_time_left
+                } else {
+                    time_info.black_time_left
+                });
+                dates.push(Some(now));
+            }
+        }
+        (times, dates)
+    }
+
     pub fn user_is_player(&self, user_id: Uuid) -> bool {
         self.white_id == user_id || self.black_id == user_id
     }
@@ -695,20 +832,31 @@ impl Game {
             info: String::from("Could not recover History from history string."),
             error: e.to_string(),
         })?;
-        let state = State::new_from_history(&his).map_err(|e| DbError::InvalidInput {
-            info: String::from("Could not recover State from History."),
-            error: e.to_string(),
-        })?;
+        let state =
+            State::new_from_history_with(&his, self.automatic_repetition()).map_err(|e| {
+                DbError::InvalidInput {
+                    info: String::from("Could not recover State from History."),
+                    error: e.to_string(),
+                }
+            })?;
         let new_game_status = state.game_status.to_string();
         let next_player = if self.current_player_id == self.black_id {
             self.white_id
         } else {
             self.black_id
         };
+        let mut new_move_times = self.move_times.clone();
+        new_move_times.truncate(his.moves.len());
+        let mut new_move_dates = self.move_dates.clone();
+        new_move_dates.truncate(his.moves.len());
+        // The prepared lines answered moves which are no longer on the board
+        ConditionalMove::delete_for_game(&self.id, conn).await?;
         Ok(diesel::update(games::table.find(self.id))
             .set((
                 current_player_id.eq(next_player),
                 history.eq(new_history),
+                move_times.eq(new_move_times),
+                move_dates.eq(new_move_dates),
                 turn.eq(turn - popped),
                 game_status.eq(new_game_status),
                 game_control_history.eq(game_control_history.concat(game_control_string)),
@@ -724,9 +872,34 @@ impl Game {
         game_control: &GameControl,
         conn: &mut DbConn<'_>,
     ) -> Result<Game, DbError> {
-        let game_control_string = format!("{}. {game_control};", self.turn);
-
         let winner_color = game_control.color().opposite_color();
+        self.decide(game_control, winner_color, Conclusion::Resigned, conn)
+            .await
+    }
+
+    pub async fn claim_victory(
+        &self,
+        game_control: &GameControl,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        self.decide(
+            game_control,
+            game_control.color(),
+            Conclusion::Forfeit,
+            conn,
+        )
+        .await
+    }
+
+    // Ends the game off the board with a win for winner_color
+    async fn decide(
+        &self,
+        game_control: &GameControl,
+        winner_color: Color,
+        new_conclusion: Conclusion,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        let game_control_string = format!("{}. {game_control};", self.turn);
         let new_game_status = GameStatus::Finished(GameResult::Winner(winner_color));
 
         let (white_time, black_time) = match TimeMode::from_str(&self.time_mode)? {
@@ -738,15 +911,7 @@ impl Game {
         }
         let ((w_rating, b_rating, w_change, b_change), tgr) = match new_game_status.clone() {
             GameStatus::Finished(game_result) => (
-                Rating::update(
-                    self.rated,
-                    self.speed.clone(),
-                    self.white_id,
-                    self.black_id,
-                    game_result.clone(),
-                    conn,
-                )
-                .await?,
+                Rating::update(self, game_result.clone(), conn).await?,
                 TournamentGameResult::new(&game_result),
             ),
             _ => unreachable!(),
@@ -754,6 +919,7 @@ impl Game {
         let game = diesel::update(games::table.find(self.id))
             .set((
                 finished.eq(true),
+                finished_at.eq(Some(Utc::now())),
                 tournament_game_result.eq(tgr.to_string()),
                 game_status.eq(new_game_status.to_string()),
                 game_control_history.eq(game_control_history.concat(game_control_string)),
@@ -764,10 +930,11 @@ impl Game {
                 updated_at.eq(Utc::now()),
                 white_time_left.eq(white_time),
                 black_time_left.eq(black_time),
-                conclusion.eq(Conclusion::Resigned.to_string()),
+                conclusion.eq(new_conclusion.to_string()),
             ))
             .get_result(conn)
             .await?;
+        UserStatistics::mark_stale(&game, conn).await?;
         Ok(game)
     }
 
@@ -775,6 +942,23 @@ impl Game {
         &self,
         game_control: &GameControl,
         conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        self.draw(game_control, Conclusion::Draw, conn).await
+    }
+
+    pub async fn claim_repetition(
+        &self,
+        game_control: &GameControl,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Game, DbError> {
+        self.draw(game_control, Conclusion::Repetition, conn).await
+    }
+
+    async fn draw(
+        &self,
+        game_control: &GameControl,
+        new_conclusion: Conclusion,
+        conn: &mut DbConn<'_>,
     ) -> Result<Game, DbError> {
         let game_control_string = format!("{}. {game_control};", self.turn);
         let (white_time, black_time) = match TimeMode::from_str(&self.time_mode)? {
@@ -785,18 +969,12 @@ impl Game {
             return self.check_time(conn).await;
         }
         let tgr = TournamentGameResult::Draw;
-        let (w_rating, b_rating, w_change, b_change) = Rating::update(
-            self.rated,
-            self.speed.clone(),
-            self.white_id,
-            self.black_id,
-            GameResult::Draw,
-            conn,
-        )
-        .await?;
+        let (w_rating, b_rating, w_change, b_change) =
+            Rating::update(self, GameResult::Draw, conn).await?;
         let game = diesel::update(games::table.find(self.id))
             .set((
                 finished.eq(true),
+                finished_at.eq(Some(Utc::now())),
                 tournament_game_result.eq(tgr.to_string()),
                 game_control_history.eq(game_control_history.concat(game_control_string)),
                 game_status.eq(GameStatus::Finished(GameResult::Draw).to_string()),
@@ -807,10 +985,11 @@ impl Game {
                 updated_at.eq(Utc::now()),
                 white_time_left.eq(white_time),
                 black_time_left.eq(black_time),
-                conclusion.eq(Conclusion::Draw.to_string()),
+                conclusion.eq(new_conclusion.to_string()),
             ))
             .get_result(conn)
             .await?;
+        UserStatistics::mark_stale(&game, conn).await?;
         Ok(game)
     }
 
@@ -856,6 +1035,31 @@ impl Game {
         Ok(())
     }
 
+    // Games nobody started playing in time, tournament games are left to their organizers
+    pub async fn get_unstarted_before(
+        cutoff: DateTime<Utc>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Game>, DbError> {
+        Ok(games::table
+            .filter(finished.eq(false))
+            .filter(turn.lt(2))
+            .filter(tournament_id.is_null())
+            .filter(time_mode.ne(TimeMode::Correspondence.to_string()))
+            .filter(game_start.eq_any([GameStart::Ready.to_string(), GameStart::Moves.to_string()]))
+            .filter(updated_at.lt(cutoff))
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn get_running_timed(conn: &mut DbConn<'_>) -> Result<Vec<Game>, DbError> {
+        Ok(games::table
+            .filter(finished.eq(false))
+            .filter(game_status.eq(GameStatus::InProgress.to_string()))
+            .filter(time_mode.ne(TimeMode::Untimed.to_string()))
+            .get_results(conn)
+            .await?)
+    }
+
     pub async fn get_ongoing_games_for_username(
         username: &str,
         conn: &mut DbConn<'_>,
@@ -927,6 +1131,22 @@ impl Game {
         }
     }
 
+    // Takes a finished game back out of both players' ratings, the game itself stays viewable
+    pub async fn annul(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        if !self.finished || self.annulled {
+            return Err(DbError::InvalidAction {
+                info: String::from("Only finished games can be annulled, and only once"),
+            });
+        }
+        Rating::revert(self, conn).await?;
+        let game = diesel::update(games::table.find(self.id))
+            .set(annulled.eq(true))
+            .get_result(conn)
+            .await?;
+        UserStatistics::mark_stale(&game, conn).await?;
+        Ok(game)
+    }
+
     pub async fn start(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
         if self.finished || self.turn > 0 || self.game_status != GameStatus::NotStarted.to_string()
         {
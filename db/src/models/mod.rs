@@ -1,7 +1,15 @@
+mod admin_action;
+mod avatar;
 mod challenge;
+mod conditional_move;
+mod email_token;
+mod fair_play_report;
 mod game;
 mod game_user;
+mod notification_settings;
 mod rating;
+mod rating_history;
+mod recovery_code;
 mod tournament;
 mod tournament_invitation;
 mod tournament_organizer;
@@ -9,10 +17,19 @@ mod tournament_series;
 mod tournament_series_organizer;
 mod tournament_user;
 mod user;
+mod user_statistics;
+pub use admin_action::{AdminAction, ModerationAction};
+pub use avatar::{Avatar, MAX_AVATAR_SIZE};
 pub use challenge::{Challenge, NewChallenge};
+pub use conditional_move::{ConditionalMove, NewConditionalMove};
+pub use email_token::{EmailToken, TokenPurpose};
+pub use fair_play_report::FairPlayReport;
 pub use game::{Game, NewGame};
 pub use game_user::GameUser;
+pub use notification_settings::{Digest, NotificationSettings};
 pub use rating::{NewRating, Rating};
+pub use rating_history::{NewRatingHistory, RatingHistory};
+pub use recovery_code::RecoveryCode;
 pub use tournament::{NewTournament, Tournament};
 pub use tournament_invitation::TournamentInvitation;
 pub use tournament_organizer::TournamentOrganizer;
@@ -20,3 +37,4 @@ pub use tournament_series::{NewTournamentSeries, TournamentSeries};
 pub use tournament_series_organizer::TournamentSeriesOrganizer;
 pub use tournament_user::TournamentUser;
 pub use user::{NewUser, User};
+pub use user_statistics::UserStatistics;
@@ -0,0 +1,185 @@
+use crate::{
+    db_error::DbError,
+    models::{Game, Tournament, User},
+    schema::{
+        games,
+        notification_settings::{self, dsl::notification_settings as notification_settings_table},
+        tournaments, tournaments_invitations, tournaments_users, users,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Duration, Utc};
+use diesel::{prelude::*, upsert::excluded, Identifiable, Insertable, Queryable};
+use diesel_async::RunQueryDsl;
+use shared_types::{TimeMode, TournamentStatus};
+use uuid::Uuid;
+
+// A game or tournament is brought up once it gets this close to its deadline or start
+const SOON: Duration = Duration::hours(24);
+
+// What happened since the last digest mail
+#[derive(Debug, Default)]
+pub struct Digest {
+    pub your_turn: Vec<Game>,
+    pub time_running_low: Vec<Game>,
+    pub tournaments_starting: Vec<Tournament>,
+    pub invitations: Vec<Tournament>,
+}
+
+impl Digest {
+    pub fn is_empty(&self) -> bool {
+        self.your_turn.is_empty()
+            && self.time_running_low.is_empty()
+            && self.tournaments_starting.is_empty()
+            && self.invitations.is_empty()
+    }
+}
+
+// Which e-mail digests a user opted into, users without a row get none
+#[derive(Insertable, Identifiable, Queryable, Selectable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = notification_settings)]
+#[diesel(primary_key(user_id))]
+pub struct NotificationSettings {
+    pub user_id: Uuid,
+    pub your_turn: bool,
+    pub time_running_low: bool,
+    pub tournament_starting: bool,
+    pub tournament_invitation: bool,
+    pub last_digest_at: DateTime<Utc>,
+}
+
+impl NotificationSettings {
+    pub fn new(
+        user_id: Uuid,
+        your_turn: bool,
+        time_running_low: bool,
+        tournament_starting: bool,
+        tournament_invitation: bool,
+    ) -> Self {
+        Self {
+            user_id,
+            your_turn,
+            time_running_low,
+            tournament_starting,
+            tournament_invitation,
+            last_digest_at: Utc::now(),
+        }
+    }
+
+    pub async fn for_user(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        Ok(notification_settings_table
+            .find(user_id)
+            .first(conn)
+            .await
+            .optional()?
+            .unwrap_or_else(|| Self::new(*user_id, false, false, false, false)))
+    }
+
+    pub async fn save(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        Ok(self
+            .insert_into(notification_settings_table)
+            .on_conflict(notification_settings::user_id)
+            .do_update()
+            .set((
+                notification_settings::your_turn.eq(excluded(notification_settings::your_turn)),
+                notification_settings::time_running_low
+                    .eq(excluded(notification_settings::time_running_low)),
+                notification_settings::tournament_starting
+                    .eq(excluded(notification_settings::tournament_starting)),
+                notification_settings::tournament_invitation
+                    .eq(excluded(notification_settings::tournament_invitation)),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Users with a verified address who want at least one kind of digest
+    pub async fn subscribed(conn: &mut DbConn<'_>) -> Result<Vec<(User, Self)>, DbError> {
+        Ok(users::table
+            .inner_join(notification_settings_table)
+            .filter(users::email_verified.eq(true))
+            .filter(
+                notification_settings::your_turn
+                    .or(notification_settings::time_running_low)
+                    .or(notification_settings::tournament_starting)
+                    .or(notification_settings::tournament_invitation),
+            )
+            .select((User::as_select(), Self::as_select()))
+            .load(conn)
+            .await?)
+    }
+
+    pub async fn digest(
+        &self,
+        now: DateTime<Utc>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Digest, DbError> {
+        let mut digest = Digest::default();
+        let since = self.last_digest_at;
+        if self.your_turn || self.time_running_low {
+            let games: Vec<Game> = games::table
+                .filter(games::current_player_id.eq(self.user_id))
+                .filter(games::finished.eq(false))
+                .filter(games::time_mode.ne(TimeMode::RealTime.to_string()))
+                .load(conn)
+                .await?;
+            for game in games {
+                let deadline = game.get_time_left().ok().and_then(|(white, black)| {
+                    let left = if game.turn % 2 == 0 { white } else { black };
+                    Some(now + Duration::from_std(left).ok()?)
+                });
+                let low = game.time_mode == TimeMode::Correspondence.to_string()
+                    && deadline
+                        .is_some_and(|deadline| deadline - now < SOON && deadline - since >= SOON);
+                if self.time_running_low && low {
+                    digest.time_running_low.push(game);
+                } else if self.your_turn && game.updated_at > since {
+                    digest.your_turn.push(game);
+                }
+            }
+        }
+        if self.tournament_starting {
+            let joined: Vec<Tournament> = tournaments_users::table
+                .inner_join(tournaments::table)
+                .filter(tournaments_users::user_id.eq(self.user_id))
+                .select(Tournament::as_select())
+                .load(conn)
+                .await?;
+            digest.tournaments_starting = joined
+                .into_iter()
+                .filter(|tournament| {
+                    let started = tournament.started_at.is_some_and(|started| started > since);
+                    let starting_soon = tournament.status
+                        == TournamentStatus::NotStarted.to_string()
+                        && tournament.starts_at.is_some_and(|starts| {
+                            starts > now && starts - now < SOON && starts - since >= SOON
+                        });
+                    started || starting_soon
+                })
+                .collect();
+        }
+        if self.tournament_invitation {
+            digest.invitations = tournaments_invitations::table
+                .inner_join(tournaments::table)
+                .filter(tournaments_invitations::invitee_id.eq(self.user_id))
+                .filter(tournaments_invitations::created_at.gt(since))
+                .select(Tournament::as_select())
+                .load(conn)
+                .await?;
+        }
+        Ok(digest)
+    }
+
+    pub async fn digest_sent(
+        &self,
+        at: DateTime<Utc>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<(), DbError> {
+        diesel::update(notification_settings_table.find(self.user_id))
+            .set(notification_settings::last_digest_at.eq(at))
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,27 +1,63 @@
 use crate::{
     db_error::DbError,
-    models::User,
-    schema::ratings::{self, dsl::ratings as ratings_table, *},
+    models::{Game, NewRatingHistory, User},
+    schema::{
+        games, pending_rebuilds, rating_history,
+        ratings::{
+            self, deviation, draw, dsl::ratings as ratings_table, last_rated_game_at, lost, played,
+            rated_at, rating, speed, updated_at, user_uid, volatility, won,
+        },
+    },
     DbConn,
 };
 use bb8::PooledConnection;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
     prelude::*, AsChangeset, Associations, Identifiable, Insertable, Queryable, Selectable,
 };
 use diesel_async::{
     pooled_connection::AsyncDieselConnectionManager, AsyncPgConnection, RunQueryDsl,
 };
-use hive_lib::{Color, GameResult};
+use hive_lib::{Color, GameResult, GameStatus, GameType};
 use serde::{Deserialize, Serialize};
-use shared_types::GameSpeed;
+use shared_types::{rated_game_type, GameSpeed};
 use skillratings::{
     glicko2::{glicko2, Glicko2Config, Glicko2Rating},
     Outcomes,
 };
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 use uuid::Uuid;
 
+const NEW_RATING: f64 = 1500.0;
+const NEW_DEVIATION: f64 = 500.0;
+const NEW_VOLATILITY: f64 = 0.09;
+const GLICKO2_SCALE: f64 = 173.7178;
+// Every rating period without a rated game inflates the deviation
+pub const RATING_PERIOD_DAYS: i64 = 7;
+const RATINGS_REBUILD: &str = "ratings";
+
+fn glicko2_config() -> Glicko2Config {
+    Glicko2Config {
+        tau: 0.75,
+        ..Default::default()
+    }
+}
+
+// Glicko-2 step 6 for players that did not compete, applied once per missed
+// period. The deviation never decays past the one of a new player.
+fn decay_deviation(rating_deviation: f64, rating_volatility: f64, periods: i64) -> f64 {
+    if periods <= 0 || rating_deviation >= NEW_DEVIATION {
+        return rating_deviation;
+    }
+    let phi = rating_deviation / GLICKO2_SCALE;
+    let decayed = (phi.powi(2) + periods as f64 * rating_volatility.powi(2)).sqrt() * GLICKO2_SCALE;
+    decayed.min(NEW_DEVIATION)
+}
+
+fn missed_periods(since: DateTime<Utc>, until: DateTime<Utc>) -> i64 {
+    (until - since).num_days() / RATING_PERIOD_DAYS
+}
+
 #[derive(Insertable, Debug)]
 #[diesel(table_name = ratings)]
 pub struct NewRating {
@@ -36,22 +72,28 @@ pub struct NewRating {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub speed: String,
+    pub game_type: String,
+    pub rated_at: DateTime<Utc>,
+    pub last_rated_game_at: Option<DateTime<Utc>>,
 }
 
 impl NewRating {
-    pub fn for_uuid(uuid: &Uuid, game_speed: GameSpeed) -> Self {
+    pub fn for_uuid(uuid: &Uuid, game_speed: GameSpeed, game_type: GameType) -> Self {
         Self {
             user_uid: uuid.to_owned(),
             played: 0,
             won: 0,
             lost: 0,
             draw: 0,
-            rating: 1500.0,
-            deviation: 500.0,
-            volatility: 0.09,
+            rating: NEW_RATING,
+            deviation: NEW_DEVIATION,
+            volatility: NEW_VOLATILITY,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             speed: game_speed.to_string(),
+            game_type: game_type.to_string(),
+            rated_at: Utc::now(),
+            last_rated_game_at: None,
         }
     }
 }
@@ -84,12 +126,33 @@ pub struct Rating {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub speed: String,
+    pub game_type: String,
+    pub rated_at: DateTime<Utc>,
+    pub last_rated_game_at: Option<DateTime<Utc>>,
 }
 
 impl Rating {
+    pub fn glicko(&self, at: DateTime<Utc>) -> Glicko2Rating {
+        Glicko2Rating {
+            rating: self.rating,
+            deviation: decay_deviation(
+                self.deviation,
+                self.volatility,
+                missed_periods(self.rated_at, at),
+            ),
+            volatility: self.volatility,
+        }
+    }
+
+    // The deviation including the periods that have not been applied yet
+    pub fn current_deviation(&self) -> f64 {
+        self.glicko(Utc::now()).deviation
+    }
+
     pub async fn for_uuid(
         uuid: &Uuid,
         game_speed: &GameSpeed,
+        game_type: &GameType,
         conn: &mut DbConn<'_>,
     ) -> Result<Self, DbError> {
         let game_speed = match game_speed {
@@ -98,31 +161,22 @@ impl Rating {
         };
         Ok(ratings_table
             .filter(user_uid.eq(uuid).and(speed.eq(game_speed)))
+            .filter(ratings::game_type.eq(rated_game_type(game_type).to_string()))
             .first(conn)
             .await?)
     }
 
     pub async fn update(
-        rated: bool,
-        mut game_speed: String,
-        white_id: Uuid,
-        black_id: Uuid,
+        game: &Game,
         game_result: GameResult,
         conn: &mut DbConn<'_>,
     ) -> Result<(f64, f64, Option<f64>, Option<f64>), DbError> {
-        if GameSpeed::from_str(&game_speed).expect("Valid GameSpeed") == GameSpeed::Untimed {
-            game_speed = GameSpeed::Correspondence.to_string()
-        }
-        let white_rating: Rating = ratings_table
-            .filter(user_uid.eq(white_id))
-            .filter(speed.eq(game_speed.clone()))
-            .first(conn)
-            .await?;
-        let black_rating: Rating = ratings_table
-            .filter(user_uid.eq(black_id))
-            .filter(speed.eq(game_speed))
-            .first(conn)
-            .await?;
+        let (rated, game_id, white_id, black_id) =
+            (game.rated, game.id, game.white_id, game.black_id);
+        let game_speed = GameSpeed::from_str(&game.speed).expect("Valid GameSpeed");
+        let game_type = GameType::from_str(&game.game_type).expect("Valid GameType");
+        let white_rating = Rating::for_uuid(&white_id, &game_speed, &game_type, conn).await?;
+        let black_rating = Rating::for_uuid(&black_id, &game_speed, &game_type, conn).await?;
 
         let (white_change, black_change) = match game_result {
             GameResult::Draw => Rating::draw(rated, &white_rating, &black_rating, conn).await,
@@ -133,6 +187,14 @@ impl Rating {
                 "This function should not be called when there's no concrete game result"
             ),
         }?;
+        if let (Some(white_change), Some(black_change)) = (white_change, black_change) {
+            NewRatingHistory::new(white_id, game_id, &white_rating, white_change)
+                .insert(conn)
+                .await?;
+            NewRatingHistory::new(black_id, game_id, &black_rating, black_change)
+                .insert(conn)
+                .await?;
+        }
         Ok((
             white_rating.rating,
             black_rating.rating,
@@ -141,27 +203,68 @@ impl Rating {
         ))
     }
 
+    // Undoes what update did for a finished game. The counters go back for every game, the
+    // rating only for rated ones and by the change stored on the game. Deviation and volatility
+    // stay as they are, they can't be taken back without replaying all later games.
+    pub async fn revert(game: &Game, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        let game_result = match GameStatus::from_str(&game.game_status) {
+            Ok(GameStatus::Finished(GameResult::Unknown)) | Err(_) => return Ok(()),
+            Ok(GameStatus::Finished(result)) => result,
+            Ok(_) => return Ok(()),
+        };
+        let game_speed = GameSpeed::from_str(&game.speed).expect("Valid GameSpeed");
+        let game_type = GameType::from_str(&game.game_type).expect("Valid GameType");
+        for (color, user, change) in [
+            (Color::White, game.white_id, game.white_rating_change),
+            (Color::Black, game.black_id, game.black_rating_change),
+        ] {
+            let (wins, losses, draws) = match game_result {
+                GameResult::Draw => (0, 0, 1),
+                GameResult::Winner(winner) if winner == color => (1, 0, 0),
+                _ => (0, 1, 0),
+            };
+            let change = if game.rated {
+                change.unwrap_or_default()
+            } else {
+                0.0
+            };
+            let player_rating = Rating::for_uuid(&user, &game_speed, &game_type, conn).await?;
+            diesel::update(ratings::table.find(player_rating.id))
+                .set((
+                    updated_at.eq(Utc::now()),
+                    played.eq(played - 1),
+                    won.eq(won - wins),
+                    lost.eq(lost - losses),
+                    draw.eq(draw - draws),
+                    rating.eq(rating - change),
+                ))
+                .execute(conn)
+                .await?;
+        }
+        diesel::delete(rating_history::table.filter(rating_history::game_id.eq(game.id)))
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
     fn calculate_glicko2(
         white_rating: &Rating,
         black_rating: &Rating,
         game_result: GameResult,
     ) -> (Glicko2Rating, Glicko2Rating, f64, f64) {
-        let white_glicko = Glicko2Rating {
-            rating: white_rating.rating,
-            deviation: white_rating.deviation,
-            volatility: white_rating.volatility,
-        };
-
-        let black_glicko = Glicko2Rating {
-            rating: black_rating.rating,
-            deviation: black_rating.deviation,
-            volatility: black_rating.volatility,
-        };
+        let now = Utc::now();
+        Rating::glicko2_result(
+            &white_rating.glicko(now),
+            &black_rating.glicko(now),
+            game_result,
+        )
+    }
 
-        let config = Glicko2Config {
-            tau: 0.75,
-            ..Default::default()
-        };
+    fn glicko2_result(
+        white_glicko: &Glicko2Rating,
+        black_glicko: &Glicko2Rating,
+        game_result: GameResult,
+    ) -> (Glicko2Rating, Glicko2Rating, f64, f64) {
         let outcome = match game_result {
             GameResult::Winner(winner) => {
                 if winner == Color::White {
@@ -174,7 +277,7 @@ impl Rating {
             GameResult::Unknown => unreachable!(),
         };
         let (white_glicko_new, black_glicko_new) =
-            glicko2(&white_glicko, &black_glicko, &outcome, &config);
+            glicko2(white_glicko, black_glicko, &outcome, &glicko2_config());
         (
             white_glicko_new,
             black_glicko_new,
@@ -183,6 +286,176 @@ impl Rating {
         )
     }
 
+    pub async fn decay_inactive(conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        let now = Utc::now();
+        let inactive: Vec<Rating> = ratings_table
+            .filter(rated_at.le(now - TimeDelta::days(RATING_PERIOD_DAYS)))
+            .filter(deviation.lt(NEW_DEVIATION))
+            .get_results(conn)
+            .await?;
+        for inactive_rating in inactive.iter() {
+            let periods = missed_periods(inactive_rating.rated_at, now);
+            diesel::update(ratings::table.find(inactive_rating.id))
+                .set((
+                    deviation.eq(inactive_rating.glicko(now).deviation),
+                    rated_at
+                        .eq(inactive_rating.rated_at
+                            + TimeDelta::days(periods * RATING_PERIOD_DAYS)),
+                ))
+                .execute(conn)
+                .await?;
+        }
+        Ok(inactive.len())
+    }
+
+    // Migrations that can't rebuild the ratings or their history in SQL ask for a replay, which runs once on the
+    // next start. Returns how many games got replayed, if any.
+    pub async fn recompute_if_pending(conn: &mut DbConn<'_>) -> Result<Option<usize>, DbError> {
+        let requested = diesel::delete(pending_rebuilds::table.find(RATINGS_REBUILD))
+            .execute(conn)
+            .await?;
+        if requested == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Rating::recompute_all(conn).await?))
+    }
+
+    // Replays every finished game in order and rebuilds all the ratings, the
+    // rating history and the ratings stored on the games from scratch
+    pub async fn recompute_all(conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        let finished_games: Vec<Game> = games::table
+            .filter(games::finished.eq(true))
+            .filter(games::annulled.eq(false))
+            .order_by((games::finished_at.asc(), games::created_at.asc()))
+            .get_results(conn)
+            .await?;
+        let mut all_ratings: Vec<Rating> = ratings_table.get_results(conn).await?;
+        for reset in all_ratings.iter_mut() {
+            reset.played = 0;
+            reset.won = 0;
+            reset.lost = 0;
+            reset.draw = 0;
+            reset.rating = NEW_RATING;
+            reset.deviation = NEW_DEVIATION;
+            reset.volatility = NEW_VOLATILITY;
+            reset.rated_at = reset.created_at;
+            reset.last_rated_game_at = None;
+        }
+        let index: HashMap<(Uuid, String, String), usize> = all_ratings
+            .iter()
+            .enumerate()
+            .map(|(i, r)| ((r.user_uid, r.game_type.clone(), r.speed.clone()), i))
+            .collect();
+        let mut history = Vec::new();
+        let mut replayed = 0;
+        for game in finished_games.iter() {
+            let game_result = match GameStatus::from_str(&game.game_status) {
+                Ok(GameStatus::Finished(GameResult::Unknown)) | Err(_) => continue,
+                Ok(GameStatus::Finished(result)) => result,
+                Ok(_) => continue,
+            };
+            let game_speed = match GameSpeed::from_str(&game.speed) {
+                Ok(GameSpeed::Untimed) => GameSpeed::Correspondence.to_string(),
+                Ok(game_speed) => game_speed.to_string(),
+                Err(_) => continue,
+            };
+            let Ok(game_type) = GameType::from_str(&game.game_type) else {
+                continue;
+            };
+            let game_type = rated_game_type(&game_type).to_string();
+            let white_key = (game.white_id, game_type.clone(), game_speed.clone());
+            let black_key = (game.black_id, game_type.clone(), game_speed.clone());
+            let (Some(&white), Some(&black)) = (index.get(&white_key), index.get(&black_key))
+            else {
+                continue;
+            };
+            replayed += 1;
+            all_ratings[white].played += 1;
+            all_ratings[black].played += 1;
+            match game_result {
+                GameResult::Draw => {
+                    all_ratings[white].draw += 1;
+                    all_ratings[black].draw += 1;
+                }
+                GameResult::Winner(Color::White) => {
+                    all_ratings[white].won += 1;
+                    all_ratings[black].lost += 1;
+                }
+                GameResult::Winner(Color::Black) => {
+                    all_ratings[white].lost += 1;
+                    all_ratings[black].won += 1;
+                }
+                GameResult::Unknown => unreachable!(),
+            }
+            if !game.rated {
+                continue;
+            }
+            let at = game.finished_at.unwrap_or(game.updated_at);
+            let white_before = all_ratings[white].rating;
+            let black_before = all_ratings[black].rating;
+            let (white_glicko, black_glicko, white_change, black_change) = Rating::glicko2_result(
+                &all_ratings[white].glicko(at),
+                &all_ratings[black].glicko(at),
+                game_result,
+            );
+            for (i, glicko) in [(white, white_glicko), (black, black_glicko)] {
+                all_ratings[i].rating = glicko.rating;
+                all_ratings[i].deviation = glicko.deviation;
+                all_ratings[i].volatility = glicko.volatility;
+                all_ratings[i].rated_at = at;
+                all_ratings[i].last_rated_game_at = Some(at);
+            }
+            for (user, glicko, change) in [
+                (game.white_id, white_glicko, white_change),
+                (game.black_id, black_glicko, black_change),
+            ] {
+                history.push(NewRatingHistory {
+                    user_uid: user,
+                    game_id: game.id,
+                    game_type: game_type.clone(),
+                    speed: game_speed.clone(),
+                    rating: glicko.rating,
+                    rating_change: change,
+                    created_at: at,
+                });
+            }
+            diesel::update(games::table.find(game.id))
+                .set((
+                    games::white_rating.eq(Some(white_before)),
+                    games::black_rating.eq(Some(black_before)),
+                    games::white_rating_change.eq(Some(white_change)),
+                    games::black_rating_change.eq(Some(black_change)),
+                ))
+                .execute(conn)
+                .await?;
+        }
+        diesel::delete(rating_history::table).execute(conn).await?;
+        for chunk in history.chunks(1000) {
+            diesel::insert_into(rating_history::table)
+                .values(chunk)
+                .execute(conn)
+                .await?;
+        }
+        for recomputed in all_ratings.iter() {
+            diesel::update(ratings::table.find(recomputed.id))
+                .set((
+                    updated_at.eq(Utc::now()),
+                    played.eq(recomputed.played),
+                    won.eq(recomputed.won),
+                    lost.eq(recomputed.lost),
+                    draw.eq(recomputed.draw),
+                    rating.eq(recomputed.rating),
+                    deviation.eq(recomputed.deviation),
+                    volatility.eq(recomputed.volatility),
+                    rated_at.eq(recomputed.rated_at),
+                    last_rated_game_at.eq(recomputed.last_rated_game_at),
+                ))
+                .execute(conn)
+                .await?;
+        }
+        Ok(replayed)
+    }
+
     async fn draw(
         rated: bool,
         white_rating: &Rating,
@@ -200,6 +473,8 @@ impl Rating {
                     rating.eq(black_glicko.rating),
                     deviation.eq(black_glicko.deviation),
                     volatility.eq(black_glicko.volatility),
+                    rated_at.eq(Utc::now()),
+                    last_rated_game_at.eq(Some(Utc::now())),
                 ))
                 .execute(conn)
                 .await?;
@@ -212,6 +487,8 @@ impl Rating {
                     rating.eq(white_glicko.rating),
                     deviation.eq(white_glicko.deviation),
                     volatility.eq(white_glicko.volatility),
+                    rated_at.eq(Utc::now()),
+                    last_rated_game_at.eq(Some(Utc::now())),
                 ))
                 .execute(conn)
                 .await?;
@@ -266,6 +543,8 @@ impl Rating {
                     rating.eq(white_glicko.rating),
                     deviation.eq(white_glicko.deviation),
                     volatility.eq(white_glicko.volatility),
+                    rated_at.eq(Utc::now()),
+                    last_rated_game_at.eq(Some(Utc::now())),
                 ))
                 .execute(conn)
                 .await?;
@@ -279,6 +558,8 @@ impl Rating {
                     rating.eq(black_glicko.rating),
                     deviation.eq(black_glicko.deviation),
                     volatility.eq(black_glicko.volatility),
+                    rated_at.eq(Utc::now()),
+                    last_rated_game_at.eq(Some(Utc::now())),
                 ))
                 .execute(conn)
                 .await?;
@@ -305,3 +586,35 @@ impl Rating {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_no_decay_inside_a_period() {
+        let rated = Utc::now();
+        let periods = missed_periods(rated, rated + TimeDelta::days(RATING_PERIOD_DAYS - 1));
+        assert_eq!(periods, 0);
+        assert_eq!(decay_deviation(60.0, 0.06, periods), 60.0);
+    }
+
+    #[test]
+    fn tests_decay_over_missed_periods() {
+        let rated = Utc::now();
+        let periods = missed_periods(rated, rated + TimeDelta::days(RATING_PERIOD_DAYS * 3 + 2));
+        assert_eq!(periods, 3);
+        let one = decay_deviation(60.0, 0.06, 1);
+        let three = decay_deviation(60.0, 0.06, periods);
+        assert!(60.0 < one && one < three);
+        // Three periods at once are the same as one after another
+        let stepwise = decay_deviation(decay_deviation(one, 0.06, 1), 0.06, 1);
+        assert!((three - stepwise).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tests_decay_is_capped() {
+        assert_eq!(decay_deviation(60.0, 0.06, 1_000_000), NEW_DEVIATION);
+        assert_eq!(decay_deviation(NEW_DEVIATION, 0.06, 5), NEW_DEVIATION);
+    }
+}
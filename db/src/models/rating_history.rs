@@ -0,0 +1,144 @@
+use crate::{
+    db_error::DbError,
+    models::{Game, Rating, User},
+    schema::{
+        rating_history::{
+            self, created_at as created_at_column, dsl::rating_history as rating_history_table,
+            game_type as game_type_column, rating_change as rating_change_column,
+            speed as speed_column, user_uid as user_uid_column,
+        },
+        users,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+    dsl::count_star, prelude::*, Associations, Identifiable, Insertable, Queryable, Selectable,
+};
+use diesel_async::RunQueryDsl;
+use hive_lib::GameType;
+use serde::{Deserialize, Serialize};
+use shared_types::{rated_game_type, GameSpeed};
+use uuid::Uuid;
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = rating_history)]
+pub struct NewRatingHistory {
+    pub user_uid: Uuid,
+    pub game_id: Uuid,
+    pub game_type: String,
+    pub speed: String,
+    pub rating: f64,
+    pub rating_change: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NewRatingHistory {
+    pub fn new(user_uid: Uuid, game_id: Uuid, rating: &Rating, rating_change: f64) -> Self {
+        Self {
+            user_uid,
+            game_id,
+            game_type: rating.game_type.clone(),
+            speed: rating.speed.clone(),
+            rating: rating.rating + rating_change,
+            rating_change,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub async fn insert(&self, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        self.insert_into(rating_history_table).execute(conn).await?;
+        Ok(())
+    }
+}
+
+#[derive(
+    Associations, Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone,
+)]
+#[diesel(belongs_to(User, foreign_key = user_uid))]
+#[diesel(belongs_to(Game))]
+#[diesel(table_name = rating_history)]
+#[diesel(primary_key(id))]
+pub struct RatingHistory {
+    pub id: i32,
+    pub user_uid: Uuid,
+    pub game_id: Uuid,
+    pub game_type: String,
+    pub speed: String,
+    pub rating: f64,
+    pub rating_change: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RatingHistory {
+    pub async fn for_uuid(
+        uuid: &Uuid,
+        game_type: &GameType,
+        game_speed: &GameSpeed,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<Self>, DbError> {
+        let game_speed = match game_speed {
+            GameSpeed::Untimed => GameSpeed::Correspondence,
+            _ => game_speed.clone(),
+        };
+        Ok(rating_history_table
+            .filter(user_uid_column.eq(uuid))
+            .filter(game_type_column.eq(rated_game_type(game_type).to_string()))
+            .filter(speed_column.eq(game_speed.to_string()))
+            .order_by(created_at_column.asc())
+            .get_results(conn)
+            .await?)
+    }
+
+    // Players with the most rated games since then, which leaves out guests
+    pub async fn most_games_since(
+        game_type: &GameType,
+        game_speed: &GameSpeed,
+        since: DateTime<Utc>,
+        limit: i64,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<(User, i64)>, DbError> {
+        Ok(rating_history_table
+            .inner_join(users::table)
+            .filter(game_type_column.eq(rated_game_type(game_type).to_string()))
+            .filter(speed_column.eq(game_speed.to_string()))
+            .filter(created_at_column.ge(since))
+            .filter(users::deleted.eq(false))
+            .filter(users::banned.eq(false))
+            .group_by(users::id)
+            .order_by(count_star().desc())
+            .limit(limit)
+            .select((User::as_select(), count_star()))
+            .load(conn)
+            .await?)
+    }
+
+    // Players whose rating went up the most since then, only counting those who gained
+    pub async fn biggest_gains_since(
+        game_type: &GameType,
+        game_speed: &GameSpeed,
+        since: DateTime<Utc>,
+        limit: i64,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<(User, f64)>, DbError> {
+        let gain = diesel::dsl::sum(rating_change_column);
+        let gains: Vec<(User, Option<f64>)> = rating_history_table
+            .inner_join(users::table)
+            .filter(game_type_column.eq(rated_game_type(game_type).to_string()))
+            .filter(speed_column.eq(game_speed.to_string()))
+            .filter(created_at_column.ge(since))
+            .filter(users::deleted.eq(false))
+            .filter(users::banned.eq(false))
+            .group_by(users::id)
+            .having(gain.gt(0.0))
+            .order_by(gain.desc())
+            .limit(limit)
+            .select((User::as_select(), gain))
+            .load(conn)
+            .await?;
+        Ok(gains
+            .into_iter()
+            .map(|(user, gain)| (user, gain.unwrap_or_default()))
+            .collect())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    db_error::DbError,
+    models::{email_token::hash_token, User},
+    schema::recovery_codes::{
+        self, code_hash, dsl::recovery_codes as recovery_codes_table, used_at,
+        user_id as user_id_column,
+    },
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, Associations, Identifiable, Insertable, Queryable, Selectable};
+use diesel_async::RunQueryDsl;
+use nanoid::nanoid;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const RECOVERY_CODES: usize = 10;
+const RECOVERY_CODE_ALPHABET: [char; 32] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'j', 'k', 'm',
+    'n', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '-',
+];
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = recovery_codes)]
+pub struct NewRecoveryCode {
+    pub user_id: Uuid,
+    pub code_hash: String,
+}
+
+#[derive(
+    Associations, Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone,
+)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = recovery_codes)]
+#[diesel(primary_key(id))]
+pub struct RecoveryCode {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+impl RecoveryCode {
+    // Replaces the user's recovery codes with new ones and returns them in plain text
+    pub async fn generate(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<Vec<String>, DbError> {
+        Self::delete_for_user(user_id, conn).await?;
+        let codes = (0..RECOVERY_CODES)
+            .map(|_| nanoid!(10, &RECOVERY_CODE_ALPHABET))
+            .collect::<Vec<_>>();
+        let new_codes = codes
+            .iter()
+            .map(|code| NewRecoveryCode {
+                user_id: *user_id,
+                code_hash: hash_token(code),
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(recovery_codes_table)
+            .values(&new_codes)
+            .execute(conn)
+            .await?;
+        Ok(codes)
+    }
+
+    // Uses up one of the user's codes, each of them works only once
+    pub async fn redeem(user_id: &Uuid, code: &str, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        let code = code.trim().to_lowercase();
+        diesel::update(
+            recovery_codes_table
+                .filter(user_id_column.eq(user_id))
+                .filter(code_hash.eq(hash_token(&code)))
+                .filter(used_at.is_null()),
+        )
+        .set(used_at.eq(Utc::now()))
+        .returning(recovery_codes::id)
+        .get_result::<i32>(conn)
+        .await
+        .optional()?
+        .map(|_| ())
+        .ok_or(DbError::Unauthorized)
+    }
+
+    pub async fn delete_for_user(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<(), DbError> {
+        diesel::delete(recovery_codes_table.filter(user_id_column.eq(user_id)))
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
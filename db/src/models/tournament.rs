@@ -20,7 +20,11 @@ use diesel_async::RunQueryDsl;
 use itertools::Itertools;
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
-use shared_types::{TimeMode, TournamentDetails, TournamentId, TournamentStatus};
+use shared_types::{
+    ClockType, ScoringMode, Standings, Tiebreaker, TimeMode, TournamentDetails,
+    TournamentGameResult, TournamentId, TournamentStatus,
+};
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[derive(Insertable, Debug)]
@@ -50,6 +54,9 @@ pub struct NewTournament {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub series: Option<Uuid>,
+    pub armageddon: bool,
+    pub clock_type: String,
+    pub repetition_rule: String,
 }
 
 impl NewTournament {
@@ -74,6 +81,12 @@ impl NewTournament {
             });
         }
 
+        if details.time_mode != TimeMode::RealTime && details.clock_type != ClockType::Fischer {
+            return Err(DbError::InvalidTournamentDetails {
+                info: String::from("Only real time tournaments can use a different clock"),
+            });
+        }
+
         if details.seats < details.min_seats {
             return Err(DbError::InvalidTournamentDetails {
                 info: String::from("Seats is less than minimun number of seats"),
@@ -86,6 +99,12 @@ impl NewTournament {
             });
         }
 
+        if details.armageddon && details.scoring != ScoringMode::Match {
+            return Err(DbError::InvalidTournamentDetails {
+                info: String::from("Armageddon tiebreaks need match scoring"),
+            });
+        }
+
         if details.rounds > 16 {
             return Err(DbError::InvalidTournamentDetails {
                 info: String::from("Number of rounds needs to <= 16"),
@@ -122,6 +141,9 @@ impl NewTournament {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             series: details.series,
+            armageddon: details.armageddon,
+            clock_type: details.clock_type.to_string(),
+            repetition_rule: details.repetition_rule.to_string(),
         })
     }
 }
@@ -157,6 +179,9 @@ pub struct Tournament {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub series: Option<Uuid>,
+    pub armageddon: bool,
+    pub clock_type: String,
+    pub repetition_rule: String,
 }
 
 impl Tournament {
@@ -490,6 +515,88 @@ impl Tournament {
         Ok(games)
     }
 
+    pub fn standings(&self, games: &[Game]) -> Result<Standings, DbError> {
+        let mut standings = Standings::new();
+        let scoring =
+            ScoringMode::from_str(&self.scoring).map_err(|err| DbError::InvalidInput {
+                info: String::from("Tournament has an invalid scoring mode"),
+                error: err.to_string(),
+            })?;
+        standings.set_scoring(scoring.clone());
+        // Games keep ranking on raw points only, matches break ties on their match points
+        if scoring == ScoringMode::Match {
+            for tiebreaker in self.tiebreaker.iter().flatten() {
+                if let Ok(tiebreaker) = Tiebreaker::from_str(tiebreaker) {
+                    if tiebreaker != Tiebreaker::RawPoints {
+                        standings.add_tiebreaker(tiebreaker);
+                    }
+                }
+            }
+        }
+        for game in games {
+            let result =
+                TournamentGameResult::from_str(&game.tournament_game_result).map_err(|err| {
+                    DbError::InvalidInput {
+                        info: String::from("Game has an invalid tournament result"),
+                        error: err.to_string(),
+                    }
+                })?;
+            let white_elo = game.white_rating.unwrap_or(0.0);
+            let black_elo = game.black_rating.unwrap_or(0.0);
+            if game.armageddon {
+                standings.add_armageddon_result(
+                    game.white_id,
+                    game.black_id,
+                    white_elo,
+                    black_elo,
+                    result,
+                );
+            } else {
+                standings.add_result(game.white_id, game.black_id, white_elo, black_elo, result);
+            }
+        }
+        Ok(standings)
+    }
+
+    // Plays off a tied match as soon as the result of its last game is in, the tournament stays
+    // locked so two games of the match ending at once can't both create one
+    pub async fn armageddon_after(
+        game: &Game,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Option<(Tournament, Game)>, DbError> {
+        let Some(id) = game.tournament_id else {
+            return Ok(None);
+        };
+        if game.armageddon {
+            return Ok(None);
+        }
+        let tournament: Tournament = tournaments::table.find(id).for_update().first(conn).await?;
+        if !tournament.armageddon || tournament.status != TournamentStatus::InProgress.to_string() {
+            return Ok(None);
+        }
+        let games = tournament.games(conn).await?;
+        let standings = tournament.standings(&games)?;
+        let tied = standings
+            .match_between(game.white_id, game.black_id)
+            .is_some_and(|score| score.needs_armageddon());
+        if !tied {
+            return Ok(None);
+        }
+        // The player who had black in the last game of the match gets white
+        let last = games
+            .iter()
+            .filter(|other| !other.armageddon)
+            .filter(|other| {
+                (other.white_id == game.white_id && other.black_id == game.black_id)
+                    || (other.white_id == game.black_id && other.black_id == game.white_id)
+            })
+            .max_by_key(|other| other.created_at)
+            .unwrap_or(game);
+        let new_game = NewGame::new_armageddon(last.black_id, last.white_id, &tournament);
+        let armageddon = Game::create(new_game, conn).await?;
+        Ok(Some((tournament, armageddon)))
+    }
+
     pub async fn get_all(conn: &mut DbConn<'_>) -> Result<Vec<Tournament>, DbError> {
         Ok(tournaments::table.get_results(conn).await?)
     }
@@ -1,37 +1,62 @@
 use super::rating::Rating;
 use crate::{
     db_error::DbError,
-    models::{Game, GameUser, NewRating},
+    models::{
+        Avatar, Game, GameUser, NewRating, RatingHistory, RecoveryCode, Tournament, TournamentUser,
+    },
     schema::{
+        challenges, conditional_moves, email_tokens,
         games::{self, current_player_id, finished, game_status, tournament_id},
+        notification_settings, rating_history,
         ratings::{self, rating},
+        tournaments, tournaments_invitations, tournaments_users,
         users::{
             self,
             dsl::{
-                email as email_field, normalized_username, password as password_field, updated_at,
+                admin, banned, bio, country, deleted, email as email_field, email_verified, guest,
+                links, normalized_username, password as password_field, patreon,
+                preferred_game_type, session_version, suspended_until, totp_enabled,
+                totp_failed_attempts, totp_last_step, totp_locked_until, totp_secret, updated_at,
                 users as users_table,
             },
         },
     },
     DbConn,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use diesel::{
     dsl::exists, query_dsl::BelongingToDsl, select, BoolExpressionMethods, ExpressionMethods,
     Identifiable, Insertable, PgTextExpressionMethods, QueryDsl, Queryable, Selectable,
     SelectableHelper,
 };
 use diesel_async::RunQueryDsl;
+use hive_lib::{GameControl, GameType};
 use lazy_static::lazy_static;
+use nanoid::nanoid;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use shared_types::{GameId, GameSpeed};
+use shared_types::{
+    country_name, rated_game_type, rated_game_types, valid_link, GameId, GameSpeed,
+    TournamentStatus, ACTIVE_DAYS, MAX_BIO_LENGTH, MAX_LINKS,
+};
 use uuid::Uuid;
 
 const MAX_USERNAME_LENGTH: usize = 20;
 const MIN_USERNAME_LENGTH: usize = 2;
 const VALID_USERNAME_CHARS: &str = "-_";
 const BANNED_USERNAMES: [&str; 3] = ["black", "white", "admin"];
+const GUEST_PREFIX: &str = "Guest-";
+const DELETED_PREFIX: &str = "Deleted-";
+const GENERATED_NAME_ALPHABET: [char; 36] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i',
+    'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+];
+// Guests who never got to play are removed after this long
+const GUEST_LIFETIME: Duration = Duration::days(1);
+// Guests who played are retired once their last game is this old
+const IDLE_GUEST_LIFETIME: Duration = Duration::days(30);
+const MAX_TOTP_ATTEMPTS: i32 = 5;
+const TOTP_LOCKOUT: Duration = Duration::minutes(15);
 
 lazy_static! {
     static ref EMAIL_RE: Regex = Regex::new(r"^[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}$").unwrap();
@@ -74,7 +99,11 @@ fn validate_username(username: &str) -> Result<(), DbError> {
             error: reason,
         });
     }
-    if BANNED_USERNAMES.contains(&username.to_lowercase().as_str()) {
+    if BANNED_USERNAMES.contains(&username.to_lowercase().as_str())
+        || [GUEST_PREFIX, DELETED_PREFIX]
+            .iter()
+            .any(|prefix| username.to_lowercase().starts_with(&prefix.to_lowercase()))
+    {
         return Err(DbError::InvalidInput {
             info: String::from("Pick another username."),
             error: "Username is not allowed.".to_string(),
@@ -83,16 +112,49 @@ fn validate_username(username: &str) -> Result<(), DbError> {
     Ok(())
 }
 
+fn validate_profile(
+    new_bio: &str,
+    new_country: Option<&str>,
+    new_links: &[String],
+) -> Result<(), DbError> {
+    if new_bio.chars().count() > MAX_BIO_LENGTH {
+        return Err(DbError::InvalidInput {
+            info: format!("Bios can be at most {MAX_BIO_LENGTH} characters long"),
+            error: String::from("bio too long"),
+        });
+    }
+    if new_country.is_some_and(|code| country_name(code).is_none()) {
+        return Err(DbError::InvalidInput {
+            info: String::from("Unknown country"),
+            error: format!("invalid country code: {:?}", new_country),
+        });
+    }
+    if new_links.len() > MAX_LINKS {
+        return Err(DbError::InvalidInput {
+            info: format!("At most {MAX_LINKS} links are allowed"),
+            error: format!("{} links", new_links.len()),
+        });
+    }
+    if let Some(link) = new_links.iter().find(|link| !valid_link(link)) {
+        return Err(DbError::InvalidInput {
+            info: String::from("Links have to be http(s) or mailto addresses"),
+            error: format!("invalid link: {:?}", link),
+        });
+    }
+    Ok(())
+}
+
 #[derive(Insertable, Debug)]
 #[diesel(table_name = users)]
 pub struct NewUser {
     pub username: String,
     pub password: String,
-    pub email: String,
+    pub email: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub normalized_username: String,
     pub patreon: bool,
+    pub guest: bool,
 }
 
 impl NewUser {
@@ -102,13 +164,29 @@ impl NewUser {
         Ok(Self {
             username: username.to_owned(),
             password: hashed_password.to_owned(),
-            email: email.to_owned(),
+            email: Some(email.to_owned()),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             normalized_username: username.to_lowercase(),
             patreon: false,
+            guest: false,
         })
     }
+
+    // Guests have no e-mail address and a password that is not a valid hash, so nobody can log
+    // into them, they only live as long as the session they were created in
+    fn guest(username: &str) -> Self {
+        Self {
+            username: username.to_owned(),
+            password: String::new(),
+            email: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            normalized_username: username.to_lowercase(),
+            patreon: false,
+            guest: true,
+        }
+    }
 }
 
 #[derive(Queryable, Identifiable, Serialize, Selectable, Deserialize, Debug, Clone)]
@@ -117,12 +195,27 @@ pub struct User {
     pub id: Uuid,
     pub username: String,
     pub password: String,
-    pub email: String,
+    pub email: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub normalized_username: String,
     pub patreon: bool,
     pub admin: bool,
+    pub email_verified: bool,
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    pub guest: bool,
+    pub deleted: bool,
+    pub banned: bool,
+    pub suspended_until: Option<DateTime<Utc>>,
+    pub bio: String,
+    pub country: Option<String>,
+    pub preferred_game_type: Option<String>,
+    pub links: Vec<Option<String>>,
+    pub totp_failed_attempts: i32,
+    pub totp_locked_until: Option<DateTime<Utc>>,
+    pub totp_last_step: Option<i64>,
+    pub session_version: i32,
 }
 
 impl User {
@@ -131,15 +224,124 @@ impl User {
             .values(new_user)
             .get_result(conn)
             .await?;
-        for game_speed in GameSpeed::all_rated().into_iter() {
-            diesel::insert_into(ratings::table)
-                .values(NewRating::for_uuid(&user.id, game_speed))
-                .execute(conn)
-                .await?;
+        for game_type in rated_game_types().into_iter() {
+            for game_speed in GameSpeed::all_rated().into_iter() {
+                diesel::insert_into(ratings::table)
+                    .values(NewRating::for_uuid(&user.id, game_speed, game_type))
+                    .execute(conn)
+                    .await?;
+            }
         }
         Ok(user)
     }
 
+    async fn unused_username(
+        prefix: &str,
+        length: usize,
+        conn: &mut DbConn<'_>,
+    ) -> Result<String, DbError> {
+        // Taken names are rare, retrying with a longer suffix makes another clash even rarer
+        let longer = length + 2;
+        let mut name = format!("{prefix}{}", nanoid!(length, &GENERATED_NAME_ALPHABET));
+        while User::username_exists(&name, conn).await? {
+            name = format!("{prefix}{}", nanoid!(longer, &GENERATED_NAME_ALPHABET));
+        }
+        Ok(name)
+    }
+
+    pub async fn create_guest(conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        let name = User::unused_username(GUEST_PREFIX, 4, conn).await?;
+        User::create(NewUser::guest(&name), conn).await
+    }
+
+    // Turns a guest into a registered user, the games played as a guest stay theirs
+    pub async fn claim_guest(
+        &self,
+        new_user: NewUser,
+        conn: &mut DbConn<'_>,
+    ) -> Result<User, DbError> {
+        if !self.guest {
+            return Err(DbError::InvalidAction {
+                info: String::from("Only guests can register"),
+            });
+        }
+        Ok(diesel::update(self)
+            .set((
+                users::username.eq(new_user.username),
+                normalized_username.eq(new_user.normalized_username),
+                password_field.eq(new_user.password),
+                email_field.eq(new_user.email),
+                guest.eq(false),
+                updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Removes guests that are past their session and have no games to claim
+    pub async fn delete_stale_guests(conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        let stale = users_table
+            .filter(guest.eq(true))
+            .filter(users::created_at.lt(Utc::now() - GUEST_LIFETIME))
+            .filter(diesel::dsl::not(exists(
+                games::table.filter(
+                    games::white_id
+                        .eq(users::id)
+                        .or(games::black_id.eq(users::id)),
+                ),
+            )))
+            .select(users::id)
+            .load::<Uuid>(conn)
+            .await?;
+        diesel::delete(
+            challenges::table.filter(
+                challenges::challenger_id
+                    .eq_any(&stale)
+                    .or(challenges::opponent_id.eq_any(&stale)),
+            ),
+        )
+        .execute(conn)
+        .await?;
+        Ok(diesel::delete(users_table.filter(users::id.eq_any(&stale)))
+            .execute(conn)
+            .await?)
+    }
+
+    // Retires guests whose games are all finished and who haven't played in a while. Their
+    // games stay as they are, but the guest can't come back to play or claim them anymore.
+    pub async fn retire_idle_guests(conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        let cutoff = Utc::now() - IDLE_GUEST_LIFETIME;
+        let idle = users_table
+            .filter(guest.eq(true))
+            .filter(deleted.eq(false))
+            .filter(users::created_at.lt(cutoff))
+            .filter(diesel::dsl::not(exists(
+                games::table
+                    .filter(
+                        games::white_id
+                            .eq(users::id)
+                            .or(games::black_id.eq(users::id)),
+                    )
+                    .filter(games::finished.eq(false).or(games::updated_at.gt(cutoff))),
+            )))
+            .select(users::id)
+            .load::<Uuid>(conn)
+            .await?;
+        diesel::delete(
+            challenges::table.filter(
+                challenges::challenger_id
+                    .eq_any(&idle)
+                    .or(challenges::opponent_id.eq_any(&idle)),
+            ),
+        )
+        .execute(conn)
+        .await?;
+        Ok(diesel::update(users_table.filter(users::id.eq_any(&idle)))
+            .set((deleted.eq(true), updated_at.eq(Utc::now())))
+            .execute(conn)
+            .await?)
+    }
+
     pub async fn edit(
         &self,
         new_password: &str,
@@ -150,7 +352,11 @@ impl User {
             (true, true) => users_table.find(&self.id).first(conn).await?,
             (true, false) => {
                 diesel::update(self)
-                    .set((email_field.eq(new_email), updated_at.eq(Utc::now())))
+                    .set((
+                        email_field.eq(new_email),
+                        email_verified.eq(false),
+                        updated_at.eq(Utc::now()),
+                    ))
                     .get_result(conn)
                     .await?
             }
@@ -165,6 +371,7 @@ impl User {
                     .set((
                         password_field.eq(new_password),
                         email_field.eq(new_email),
+                        email_verified.eq(false),
                         updated_at.eq(Utc::now()),
                     ))
                     .get_result(conn)
@@ -173,6 +380,224 @@ impl User {
         })
     }
 
+    pub async fn edit_profile(
+        &self,
+        new_bio: &str,
+        new_country: Option<&str>,
+        new_preferred_game_type: Option<GameType>,
+        new_links: &[String],
+        conn: &mut DbConn<'_>,
+    ) -> Result<User, DbError> {
+        validate_profile(new_bio, new_country, new_links)?;
+        Ok(diesel::update(self)
+            .set((
+                bio.eq(new_bio),
+                country.eq(new_country),
+                preferred_game_type
+                    .eq(new_preferred_game_type.map(|game_type| game_type.to_string())),
+                links.eq(new_links.iter().cloned().map(Some).collect::<Vec<_>>()),
+                updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub fn links(&self) -> Vec<String> {
+        self.links.iter().flatten().cloned().collect()
+    }
+
+    // A new password ends every session signed in with the old one
+    pub async fn set_password(
+        &self,
+        new_password: &str,
+        conn: &mut DbConn<'_>,
+    ) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set((
+                password_field.eq(new_password),
+                session_version.eq(session_version + 1),
+                updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn verify_email(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set(email_verified.eq(true))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Admin rights only apply once two-factor authentication is on
+    pub fn is_admin(&self) -> bool {
+        self.admin && self.totp_enabled
+    }
+
+    // Banned users and users serving a suspension can neither log in nor play
+    pub fn is_banned(&self) -> bool {
+        self.banned || self.suspended_until.is_some_and(|until| until > Utc::now())
+    }
+
+    pub async fn ban(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set((banned.eq(true), updated_at.eq(Utc::now())))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn suspend(
+        &self,
+        until: DateTime<Utc>,
+        conn: &mut DbConn<'_>,
+    ) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set((suspended_until.eq(until), updated_at.eq(Utc::now())))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Lifts a ban as well as a running suspension
+    pub async fn unban(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set((
+                banned.eq(false),
+                suspended_until.eq(None::<DateTime<Utc>>),
+                updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Removes the challenges the user created or was sent, returns their nanoids
+    pub async fn close_challenges(&self, conn: &mut DbConn<'_>) -> Result<Vec<String>, DbError> {
+        Ok(diesel::delete(
+            challenges::table.filter(
+                challenges::challenger_id
+                    .eq(self.id)
+                    .or(challenges::opponent_id.eq(self.id)),
+            ),
+        )
+        .returning(challenges::nanoid)
+        .get_results(conn)
+        .await?)
+    }
+
+    // Takes the user out of tournaments that have not started yet and resigns their open games
+    // in running ones, returns the tournaments that changed and the games it resigned
+    pub async fn withdraw_from_tournaments(
+        &self,
+        conn: &mut DbConn<'_>,
+    ) -> Result<(Vec<Tournament>, Vec<Game>), DbError> {
+        diesel::delete(
+            tournaments_invitations::table.filter(tournaments_invitations::invitee_id.eq(self.id)),
+        )
+        .execute(conn)
+        .await?;
+        let mut withdrawn = Vec::new();
+        let mut resigned = Vec::new();
+        for tournament in self.get_tournaments(conn).await? {
+            if tournament.status == TournamentStatus::NotStarted.to_string() {
+                TournamentUser::delete(tournament.id, self.id, conn).await?;
+                withdrawn.push(tournament);
+            } else if tournament.status == TournamentStatus::InProgress.to_string() {
+                for game in tournament.games(conn).await? {
+                    if let (false, Some(color)) = (game.finished, game.user_color(self.id)) {
+                        resigned.push(game.resign(&GameControl::Resign(color), conn).await?);
+                    }
+                }
+                withdrawn.push(tournament);
+            }
+        }
+        Ok((withdrawn, resigned))
+    }
+
+    // Stores the secret of a pending enrolment, it only counts once confirmed
+    pub async fn set_totp_secret(
+        &self,
+        secret: &str,
+        conn: &mut DbConn<'_>,
+    ) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set((
+                totp_secret.eq(secret),
+                totp_enabled.eq(false),
+                totp_last_step.eq(None::<i64>),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn enable_totp(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        if self.totp_secret.is_none() {
+            return Err(DbError::InvalidAction {
+                info: String::from("Two-factor authentication has not been set up"),
+            });
+        }
+        Ok(diesel::update(self)
+            .set(totp_enabled.eq(true))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn disable_totp(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        RecoveryCode::delete_for_user(&self.id, conn).await?;
+        Ok(diesel::update(self)
+            .set((
+                totp_secret.eq(None::<String>),
+                totp_enabled.eq(false),
+                totp_failed_attempts.eq(0),
+                totp_locked_until.eq(None::<DateTime<Utc>>),
+                totp_last_step.eq(None::<i64>),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Too many wrong second factors lock them out for a while, whichever session they come from
+    pub fn is_totp_locked(&self) -> bool {
+        self.totp_locked_until
+            .is_some_and(|until| until > Utc::now())
+    }
+
+    pub async fn record_totp_failure(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        let user: User = diesel::update(self)
+            .set(totp_failed_attempts.eq(totp_failed_attempts + 1))
+            .get_result(conn)
+            .await?;
+        if user.totp_failed_attempts < MAX_TOTP_ATTEMPTS {
+            return Ok(user);
+        }
+        Ok(diesel::update(self)
+            .set((
+                totp_failed_attempts.eq(0),
+                totp_locked_until.eq(Some(Utc::now() + TOTP_LOCKOUT)),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    pub async fn reset_totp_failures(&self, conn: &mut DbConn<'_>) -> Result<User, DbError> {
+        Ok(diesel::update(self)
+            .set(totp_failed_attempts.eq(0))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Accepts the time step of a valid code only if it is newer than the last one used, so a
+    // code that was seen can't be played again. Succeeding also clears the failed attempts.
+    pub async fn use_totp_step(&self, step: i64, conn: &mut DbConn<'_>) -> Result<bool, DbError> {
+        let updated = diesel::update(
+            users_table
+                .filter(users::id.eq(self.id))
+                .filter(totp_last_step.is_null().or(totp_last_step.lt(step))),
+        )
+        .set((totp_last_step.eq(step), totp_failed_attempts.eq(0)))
+        .execute(conn)
+        .await?;
+        Ok(updated == 1)
+    }
+
     pub async fn find_by_uuid(uuid: &Uuid, conn: &mut DbConn<'_>) -> Result<User, DbError> {
         Ok(users_table.find(uuid).first(conn).await?)
     }
@@ -193,6 +618,7 @@ impl User {
         }
         Ok(users_table
             .filter(normalized_username.ilike(format!("%{}%", pattern)))
+            .filter(deleted.eq(false))
             .load(conn)
             .await?)
     }
@@ -218,6 +644,118 @@ impl User {
             .await?)
     }
 
+    // Deleting an account keeps the row so finished games and tournaments stay intact, but
+    // everything that identifies the person behind it is removed. Returns the games that got
+    // resigned along with the account, their players still need to hear about it.
+    pub async fn anonymize(&self, conn: &mut DbConn<'_>) -> Result<(User, Vec<Game>), DbError> {
+        // Opponents shouldn't be left waiting on a player who is gone
+        let unfinished: Vec<Game> = GameUser::belonging_to(self)
+            .inner_join(games::table)
+            .filter(games::finished.eq(false))
+            .select(Game::as_select())
+            .get_results(conn)
+            .await?;
+        let mut resigned = Vec::new();
+        for game in unfinished {
+            if let Some(color) = game.user_color(self.id) {
+                resigned.push(game.resign(&GameControl::Resign(color), conn).await?);
+            }
+        }
+        diesel::delete(
+            challenges::table.filter(
+                challenges::challenger_id
+                    .eq(self.id)
+                    .or(challenges::opponent_id.eq(self.id)),
+            ),
+        )
+        .execute(conn)
+        .await?;
+        diesel::delete(
+            tournaments_invitations::table.filter(tournaments_invitations::invitee_id.eq(self.id)),
+        )
+        .execute(conn)
+        .await?;
+        let unstarted = tournaments::table
+            .filter(tournaments::status.eq(TournamentStatus::NotStarted.to_string()))
+            .select(tournaments::id);
+        diesel::delete(
+            tournaments_users::table
+                .filter(tournaments_users::user_id.eq(self.id))
+                .filter(tournaments_users::tournament_id.eq_any(unstarted)),
+        )
+        .execute(conn)
+        .await?;
+        diesel::delete(conditional_moves::table.filter(conditional_moves::user_id.eq(self.id)))
+            .execute(conn)
+            .await?;
+        diesel::delete(email_tokens::table.filter(email_tokens::user_id.eq(self.id)))
+            .execute(conn)
+            .await?;
+        diesel::delete(
+            notification_settings::table.filter(notification_settings::user_id.eq(self.id)),
+        )
+        .execute(conn)
+        .await?;
+        RecoveryCode::delete_for_user(&self.id, conn).await?;
+        Avatar::delete_for_user(&self.id, conn).await?;
+        let name = User::unused_username(DELETED_PREFIX, 8, conn).await?;
+        let user = diesel::update(self)
+            .set((
+                users::username.eq(&name),
+                normalized_username.eq(name.to_lowercase()),
+                password_field.eq(""),
+                email_field.eq(None::<String>),
+                email_verified.eq(false),
+                totp_secret.eq(None::<String>),
+                totp_enabled.eq(false),
+                totp_last_step.eq(None::<i64>),
+                admin.eq(false),
+                patreon.eq(false),
+                bio.eq(""),
+                country.eq(None::<String>),
+                preferred_game_type.eq(None::<String>),
+                links.eq(Vec::<Option<String>>::new()),
+                deleted.eq(true),
+                updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await?;
+        Ok((user, resigned))
+    }
+
+    pub async fn get_all_games(&self, conn: &mut DbConn<'_>) -> Result<Vec<Game>, DbError> {
+        Ok(GameUser::belonging_to(self)
+            .inner_join(games::table)
+            .select(Game::as_select())
+            .order_by(games::created_at.asc())
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn get_ratings(&self, conn: &mut DbConn<'_>) -> Result<Vec<Rating>, DbError> {
+        Ok(Rating::belonging_to(self).get_results(conn).await?)
+    }
+
+    pub async fn get_rating_history(
+        &self,
+        conn: &mut DbConn<'_>,
+    ) -> Result<Vec<RatingHistory>, DbError> {
+        Ok(RatingHistory::belonging_to(self)
+            .order_by(rating_history::created_at.asc())
+            .get_results(conn)
+            .await?)
+    }
+
+    pub async fn get_tournaments(&self, conn: &mut DbConn<'_>) -> Result<Vec<Tournament>, DbError> {
+        Ok(tournaments_users::table
+            .inner_join(tournaments::table)
+            .filter(tournaments_users::user_id.eq(self.id))
+            .select(Tournament::as_select())
+            .order_by(tournaments::created_at.asc())
+            .get_results(conn)
+            .await?)
+    }
+
     pub async fn get_games_with_notifications(
         &self,
         conn: &mut DbConn<'_>,
@@ -246,15 +784,22 @@ impl User {
             .collect())
     }
 
+    // Rankable players who played a rated game lately, best rated first
     pub async fn get_top_users(
+        game_type: &GameType,
         game_speed: &GameSpeed,
         limit: i64,
         conn: &mut DbConn<'_>,
     ) -> Result<Vec<(User, Rating)>, DbError> {
         Ok(users::table
             .inner_join(ratings::table)
-            .filter(ratings::deviation.le(shared_types::RANKABLE_DEVIATION))
+            .filter(ratings::deviation.lt(shared_types::RANKABLE_DEVIATION))
+            .filter(ratings::last_rated_game_at.gt(Utc::now() - Duration::days(ACTIVE_DAYS)))
+            .filter(users::guest.eq(false))
+            .filter(users::deleted.eq(false))
+            .filter(users::banned.eq(false))
             .filter(ratings::speed.eq(game_speed.to_string()))
+            .filter(ratings::game_type.eq(rated_game_type(game_type).to_string()))
             .order_by(rating.desc())
             .limit(limit)
             .load::<(User, Rating)>(conn)
@@ -0,0 +1,141 @@
+use crate::{
+    db_error::DbError,
+    models::{Game, User},
+    schema::{
+        games,
+        user_statistics::{self, dsl::user_statistics as user_statistics_table},
+    },
+    DbConn,
+};
+use chrono::{DateTime, Utc};
+use diesel::{prelude::*, upsert::excluded, Identifiable, Insertable, Queryable};
+use diesel_async::RunQueryDsl;
+use hive_lib::{Color, GameResult, GameStatus, GameType, History, State};
+use shared_types::{Conclusion, GameSpeed, Outcome, Statistics, StatisticsGame};
+use std::str::FromStr;
+use uuid::Uuid;
+
+// Moves that make up an opening, by then both players placed two pieces
+const OPENING_TURNS: usize = 4;
+
+// A player's statistics as of their last finished game. Finishing a game only marks them stale,
+// they are computed again the next time someone looks.
+#[derive(Insertable, Identifiable, Queryable, Selectable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = user_statistics)]
+#[diesel(primary_key(user_id))]
+pub struct UserStatistics {
+    pub user_id: Uuid,
+    pub statistics: serde_json::Value,
+    pub stale: bool,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl UserStatistics {
+    pub async fn for_user(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<Statistics, DbError> {
+        let cached: Option<Self> = user_statistics_table
+            .find(user_id)
+            .first(conn)
+            .await
+            .optional()?;
+        if let Some(cached) = cached.filter(|cached| !cached.stale) {
+            if let Ok(statistics) = serde_json::from_value(cached.statistics) {
+                return Ok(statistics);
+            }
+        }
+        let statistics = Self::compute(user_id, conn).await?;
+        Self {
+            user_id: *user_id,
+            statistics: serde_json::to_value(&statistics).map_err(|_| DbError::InternalError)?,
+            stale: false,
+            computed_at: Utc::now(),
+        }
+        .save(conn)
+        .await?;
+        Ok(statistics)
+    }
+
+    async fn compute(user_id: &Uuid, conn: &mut DbConn<'_>) -> Result<Statistics, DbError> {
+        let finished_games: Vec<Game> = games::table
+            .filter(games::white_id.eq(user_id).or(games::black_id.eq(user_id)))
+            .filter(games::finished.eq(true))
+            .filter(games::annulled.eq(false))
+            .order_by((games::finished_at.asc(), games::created_at.asc()))
+            .get_results(conn)
+            .await?;
+        let games = finished_games
+            .iter()
+            .filter_map(|game| Self::statistics_game(game, *user_id))
+            .collect::<Vec<_>>();
+        Ok(Statistics::from_games(&games))
+    }
+
+    fn statistics_game(game: &Game, user_id: Uuid) -> Option<StatisticsGame> {
+        let color = game.user_color(user_id)?;
+        let outcome = match GameStatus::from_str(&game.game_status).ok()? {
+            GameStatus::Finished(GameResult::Winner(winner)) if winner == color => Outcome::Win,
+            GameStatus::Finished(GameResult::Winner(_)) => Outcome::Loss,
+            GameStatus::Finished(GameResult::Draw) => Outcome::Draw,
+            _ => return None,
+        };
+        let game_type = GameType::from_str(&game.game_type).ok()?;
+        let opponent_rating = match (game.rated, color) {
+            (false, _) => None,
+            (true, Color::White) => game.black_rating,
+            (true, Color::Black) => game.white_rating,
+        };
+        Some(StatisticsGame {
+            color,
+            outcome,
+            game_type,
+            speed: GameSpeed::from_str(&game.speed).ok()?,
+            opponent_rating,
+            turns: game.turn as usize,
+            conclusion: Conclusion::from_str(&game.conclusion).unwrap_or(Conclusion::Unknown),
+            opening: Self::opening(game, game_type),
+        })
+    }
+
+    // The position after the opening is hashed the same way however the board is turned or
+    // mirrored, so openings that only differ by symmetry are counted together
+    fn opening(game: &Game, game_type: GameType) -> Option<(u64, String)> {
+        let history = History::new_from_str(&game.history).ok()?;
+        let moves = history.moves.get(..OPENING_TURNS)?;
+        let mut state = State::new(game_type, game.tournament_queen_rule);
+        for (piece, position) in moves {
+            state.play_turn_from_history(piece, position).ok()?;
+        }
+        let hash = *state.hashes.get(OPENING_TURNS - 1)?;
+        let moves = moves
+            .iter()
+            .map(|(piece, position)| format!("{piece} {position}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some((hash, moves))
+    }
+
+    async fn save(&self, conn: &mut DbConn<'_>) -> Result<Self, DbError> {
+        Ok(self
+            .insert_into(user_statistics_table)
+            .on_conflict(user_statistics::user_id)
+            .do_update()
+            .set((
+                user_statistics::statistics.eq(excluded(user_statistics::statistics)),
+                user_statistics::stale.eq(excluded(user_statistics::stale)),
+                user_statistics::computed_at.eq(excluded(user_statistics::computed_at)),
+            ))
+            .get_result(conn)
+            .await?)
+    }
+
+    // Called whenever a game ends or is annulled, both players' statistics changed
+    pub async fn mark_stale(game: &Game, conn: &mut DbConn<'_>) -> Result<usize, DbError> {
+        Ok(diesel::update(
+            user_statistics_table
+                .filter(user_statistics::user_id.eq_any([game.white_id, game.black_id])),
+        )
+        .set(user_statistics::stale.eq(true))
+        .execute(conn)
+        .await?)
+    }
+}
@@ -1,5 +1,26 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    admin_actions (id) {
+        id -> Int4,
+        admin_id -> Uuid,
+        action -> Text,
+        target_user_id -> Nullable<Uuid>,
+        target_game_id -> Nullable<Uuid>,
+        reason -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    avatars (user_id) {
+        user_id -> Uuid,
+        content_type -> Text,
+        image -> Bytea,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     challenges (id) {
         id -> Uuid,
@@ -17,6 +38,44 @@ diesel::table! {
         time_increment -> Nullable<Int4>,
         band_upper -> Nullable<Int4>,
         band_lower -> Nullable<Int4>,
+        clock_type -> Text,
+        repetition_rule -> Text,
+    }
+}
+
+diesel::table! {
+    conditional_moves (id) {
+        id -> Int4,
+        game_id -> Uuid,
+        user_id -> Uuid,
+        moves -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    email_tokens (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        purpose -> Text,
+        token_hash -> Text,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    fair_play_reports (user_id) {
+        user_id -> Uuid,
+        games -> Int4,
+        moves -> Int4,
+        engine_match_rate -> Nullable<Float8>,
+        move_time_variation -> Nullable<Float8>,
+        performance_gap -> Nullable<Float8>,
+        flags -> Array<Nullable<Text>>,
+        flagged -> Bool,
+        analysed_at -> Timestamptz,
     }
 }
 
@@ -53,6 +112,13 @@ diesel::table! {
         tournament_id -> Nullable<Uuid>,
         tournament_game_result -> Text,
         game_start -> Text,
+        armageddon -> Bool,
+        clock_type -> Text,
+        move_times -> Array<Nullable<Int8>>,
+        move_dates -> Array<Nullable<Timestamptz>>,
+        repetition_rule -> Text,
+        annulled -> Bool,
+        finished_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -63,6 +129,37 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    notification_settings (user_id) {
+        user_id -> Uuid,
+        your_turn -> Bool,
+        time_running_low -> Bool,
+        tournament_starting -> Bool,
+        tournament_invitation -> Bool,
+        last_digest_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    pending_rebuilds (name) {
+        name -> Text,
+        requested_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    rating_history (id) {
+        id -> Int4,
+        user_uid -> Uuid,
+        game_id -> Uuid,
+        game_type -> Text,
+        speed -> Text,
+        rating -> Float8,
+        rating_change -> Float8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     ratings (id) {
         id -> Int4,
@@ -77,6 +174,18 @@ diesel::table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         speed -> Text,
+        game_type -> Text,
+        rated_at -> Timestamptz,
+        last_rated_game_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    recovery_codes (id) {
+        id -> Int4,
+        user_id -> Uuid,
+        code_hash -> Text,
+        used_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -125,6 +234,9 @@ diesel::table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         series -> Nullable<Uuid>,
+        armageddon -> Bool,
+        clock_type -> Text,
+        repetition_rule -> Text,
     }
 }
 
@@ -150,23 +262,58 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_statistics (user_id) {
+        user_id -> Uuid,
+        statistics -> Jsonb,
+        stale -> Bool,
+        computed_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Uuid,
         username -> Text,
         password -> Text,
-        email -> Text,
+        email -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         normalized_username -> Text,
         patreon -> Bool,
         admin -> Bool,
+        email_verified -> Bool,
+        totp_secret -> Nullable<Text>,
+        totp_enabled -> Bool,
+        guest -> Bool,
+        deleted -> Bool,
+        banned -> Bool,
+        suspended_until -> Nullable<Timestamptz>,
+        bio -> Text,
+        country -> Nullable<Text>,
+        preferred_game_type -> Nullable<Text>,
+        links -> Array<Nullable<Text>>,
+        totp_failed_attempts -> Int4,
+        totp_locked_until -> Nullable<Timestamptz>,
+        totp_last_step -> Nullable<Int8>,
+        session_version -> Int4,
     }
 }
 
+diesel::joinable!(admin_actions -> games (target_game_id));
+diesel::joinable!(admin_actions -> users (admin_id));
+diesel::joinable!(avatars -> users (user_id));
+diesel::joinable!(conditional_moves -> games (game_id));
+diesel::joinable!(conditional_moves -> users (user_id));
+diesel::joinable!(email_tokens -> users (user_id));
+diesel::joinable!(fair_play_reports -> users (user_id));
 diesel::joinable!(games_users -> games (game_id));
 diesel::joinable!(games_users -> users (user_id));
+diesel::joinable!(notification_settings -> users (user_id));
+diesel::joinable!(rating_history -> games (game_id));
+diesel::joinable!(rating_history -> users (user_uid));
 diesel::joinable!(ratings -> users (user_uid));
+diesel::joinable!(recovery_codes -> users (user_id));
 diesel::joinable!(tournament_series_organizers -> tournament_series (tournament_series_id));
 diesel::joinable!(tournament_series_organizers -> users (organizer_id));
 diesel::joinable!(tournaments -> tournament_series (series));
@@ -176,17 +323,28 @@ diesel::joinable!(tournaments_organizers -> tournaments (tournament_id));
 diesel::joinable!(tournaments_organizers -> users (organizer_id));
 diesel::joinable!(tournaments_users -> tournaments (tournament_id));
 diesel::joinable!(tournaments_users -> users (user_id));
+diesel::joinable!(user_statistics -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    admin_actions,
+    avatars,
     challenges,
+    conditional_moves,
+    email_tokens,
+    fair_play_reports,
     games,
     games_users,
+    notification_settings,
+    pending_rebuilds,
+    rating_history,
     ratings,
+    recovery_codes,
     tournament_series,
     tournament_series_organizers,
     tournaments,
     tournaments_invitations,
     tournaments_organizers,
     tournaments_users,
+    user_statistics,
     users,
 );
@@ -0,0 +1,146 @@
+use crate::{
+    bug::Bug, color::Color, game_result::GameResult, game_status::GameStatus, piece::Piece,
+    position::Position, state::State, turn::Turn,
+};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+const WIN: f64 = 1000.0;
+// Every piece around a queen counts far more than having a few more moves
+const QUEEN_NEIGHBOR: f64 = 10.0;
+const MOBILITY: f64 = 1.0;
+
+// A greedy one ply engine. It is no match for a strong player, but it always picks the same
+// moves in the same position, which makes it a fixed yardstick to compare players against.
+pub struct Ai;
+
+impl Ai {
+    // Every turn the player to move can make, a shutout when there is nothing else
+    pub fn legal_turns(state: &State) -> Vec<Turn> {
+        if let GameStatus::Finished(_) = state.game_status {
+            return Vec::new();
+        }
+        let color = state.turn_color;
+        let mut turns = Vec::new();
+        for ((piece, _), targets) in state.board.moves(color) {
+            turns.extend(targets.into_iter().map(|target| Turn::Move(piece, target)));
+        }
+        let positions = state
+            .board
+            .spawnable_positions(color)
+            .collect::<HashSet<Position>>();
+        let queen_required = state.board.queen_required(state.turn, color);
+        for (bug, pieces) in state.current_reserve() {
+            if (bug == Bug::Queen && !state.queen_allowed())
+                || (bug != Bug::Queen && queen_required)
+            {
+                continue;
+            }
+            // Pieces of a kind are spawned in order, so only the first one counts
+            let Some(piece) = pieces.first().and_then(|piece| Piece::from_str(piece).ok()) else {
+                continue;
+            };
+            turns.extend(positions.iter().map(|target| Turn::Move(piece, *target)));
+        }
+        if turns.is_empty() {
+            turns.push(Turn::Shutout);
+        }
+        turns
+    }
+
+    // The legal turns with the evaluation of the position they lead to, best first
+    pub fn ranked_turns(state: &State) -> Vec<(Turn, f64)> {
+        let color = state.turn_color;
+        let mut ranked = Ai::legal_turns(state)
+            .into_iter()
+            .filter_map(|turn| {
+                let mut next = state.clone();
+                if let Turn::Move(piece, target) = turn {
+                    next.play_turn_from_position(piece, target).ok()?;
+                }
+                let score = Ai::evaluate(&next, color);
+                Some((turn, score))
+            })
+            .collect::<Vec<_>>();
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        ranked
+    }
+
+    // How good the position is for color, positive means better than for the opponent
+    pub fn evaluate(state: &State, color: Color) -> f64 {
+        match state.game_status {
+            GameStatus::Finished(GameResult::Winner(winner)) if winner == color => return WIN,
+            GameStatus::Finished(GameResult::Winner(_)) => return -WIN,
+            GameStatus::Finished(GameResult::Draw) => return 0.0,
+            _ => {}
+        }
+        let opponent = color.opposite_color();
+        let surrounded = |color: Color| {
+            state
+                .board
+                .position_of_piece(Piece::new_from(Bug::Queen, color, 0))
+                .map_or(0, |position| state.board.neighbors(position).count()) as f64
+        };
+        let mobility = |color: Color| state.board.moves(color).len() as f64;
+        QUEEN_NEIGHBOR * (surrounded(opponent) - surrounded(color))
+            + MOBILITY * (mobility(color) - mobility(opponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_type::GameType;
+
+    #[test]
+    fn tests_first_turn_spawns_every_bug_but_the_queen() {
+        let state = State::new(GameType::MLP, true);
+        let turns = Ai::legal_turns(&state);
+        assert_eq!(turns.len(), 7);
+        assert!(turns.iter().all(|turn| match turn {
+            Turn::Move(piece, position) => {
+                piece.bug() != Bug::Queen && *position == Position::initial_spawn_position()
+            }
+            Turn::Shutout => false,
+        }));
+    }
+
+    #[test]
+    fn tests_queen_is_required_on_the_fourth_turn() {
+        let mut state = State::new(GameType::Base, false);
+        for (piece, position) in [
+            ("wA1", ""),
+            ("bA1", "wA1-"),
+            ("wA2", "-wA1"),
+            ("bA2", "bA1-"),
+            ("wA3", "-wA2"),
+            ("bA3", "bA2-"),
+        ] {
+            state.play_turn_from_history(piece, position).unwrap();
+        }
+        let turns = Ai::legal_turns(&state);
+        assert!(!turns.is_empty());
+        assert!(turns.iter().all(|turn| match turn {
+            Turn::Move(piece, _) => piece.bug() == Bug::Queen,
+            Turn::Shutout => false,
+        }));
+    }
+
+    #[test]
+    fn tests_ranked_turns_are_playable_and_sorted() {
+        let mut state = State::new(GameType::Base, false);
+        state.play_turn_from_history("wQ", "").unwrap();
+        state.play_turn_from_history("bQ", "wQ-").unwrap();
+        let ranked = Ai::ranked_turns(&state);
+        assert_eq!(ranked.len(), Ai::legal_turns(&state).len());
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn tests_winning_is_the_best_evaluation() {
+        let mut state = State::new(GameType::Base, false);
+        state.game_status = GameStatus::Finished(GameResult::Winner(Color::Black));
+        assert_eq!(Ai::evaluate(&state, Color::Black), WIN);
+        assert_eq!(Ai::evaluate(&state, Color::White), -WIN);
+    }
+}
@@ -7,6 +7,8 @@ use std::str::FromStr;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum GameControl {
     Abort(Color),
+    ClaimRepetition(Color),
+    ClaimVictory(Color),
     DrawAccept(Color),
     DrawOffer(Color),
     DrawReject(Color),
@@ -20,6 +22,8 @@ impl GameControl {
     pub fn color(&self) -> Color {
         *match self {
             GameControl::Abort(color) => color,
+            GameControl::ClaimRepetition(color) => color,
+            GameControl::ClaimVictory(color) => color,
             GameControl::DrawAccept(color) => color,
             GameControl::DrawOffer(color) => color,
             GameControl::DrawReject(color) => color,
@@ -42,6 +46,8 @@ impl fmt::Display for GameControl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let game_control = match self {
             GameControl::Abort(color) => format!("Abort({color})"),
+            GameControl::ClaimRepetition(color) => format!("ClaimRepetition({color})"),
+            GameControl::ClaimVictory(color) => format!("ClaimVictory({color})"),
             GameControl::DrawAccept(color) => format!("DrawAccept({color})"),
             GameControl::DrawOffer(color) => format!("DrawOffer({color})"),
             GameControl::DrawReject(color) => format!("DrawReject({color})"),
@@ -61,6 +67,10 @@ impl FromStr for GameControl {
         match s {
             "Abort(w)" => Ok(GameControl::Abort(Color::White)),
             "Abort(b)" => Ok(GameControl::Abort(Color::Black)),
+            "ClaimRepetition(w)" => Ok(GameControl::ClaimRepetition(Color::White)),
+            "ClaimRepetition(b)" => Ok(GameControl::ClaimRepetition(Color::Black)),
+            "ClaimVictory(w)" => Ok(GameControl::ClaimVictory(Color::White)),
+            "ClaimVictory(b)" => Ok(GameControl::ClaimVictory(Color::Black)),
             "DrawAccept(w)" => Ok(GameControl::DrawAccept(Color::White)),
             "DrawAccept(b)" => Ok(GameControl::DrawAccept(Color::Black)),
             "DrawOffer(w)" => Ok(GameControl::DrawOffer(Color::White)),
@@ -91,6 +101,8 @@ mod tests {
     fn tests_game_controls() {
         for gc in [
             GameControl::Abort(Color::White),
+            GameControl::ClaimRepetition(Color::White),
+            GameControl::ClaimVictory(Color::White),
             GameControl::DrawAccept(Color::White),
             GameControl::DrawOffer(Color::White),
             GameControl::DrawReject(Color::White),
@@ -99,6 +111,8 @@ mod tests {
             GameControl::TakebackReject(Color::White),
             GameControl::TakebackRequest(Color::White),
             GameControl::Abort(Color::Black),
+            GameControl::ClaimRepetition(Color::Black),
+            GameControl::ClaimVictory(Color::Black),
             GameControl::DrawAccept(Color::Black),
             GameControl::DrawOffer(Color::Black),
             GameControl::DrawReject(Color::Black),
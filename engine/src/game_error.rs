@@ -48,6 +48,22 @@ pub enum GameError {
     NotPlayer { username: String, game: String },
     #[error("Cannot abort tournament game")]
     TournamentAbort,
+    #[error("{game} isn't over yet")]
+    GameNotOver { game: String },
+    #[error("Cannot rematch tournament game")]
+    TournamentRematch,
+    #[error("There is no rematch offer to answer on {game}")]
+    NoRematchOffer { game: String },
+    #[error("There already is a rematch of {game}")]
+    AlreadyRematched { game: String },
+    #[error("Premoves are only available in bullet and blitz, not on {game}")]
+    NoPremoves { game: String },
+    #[error("Premoves can only be queued on the opponent's turn on {game}")]
+    NotPremoveTime { game: String },
+    #[error("Victory can only be claimed in realtime games once the opponent left, not on {game}")]
+    NoVictoryClaim { game: String },
+    #[error("The current position of {game} hasn't occurred three times")]
+    NoRepetition { game: String },
 }
 
 impl GameError {
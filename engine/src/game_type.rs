@@ -3,7 +3,9 @@ use serde::Serialize;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq, Copy, Default, serde_with::DeserializeFromStr)]
+#[derive(
+    Debug, Clone, Serialize, PartialEq, Eq, Hash, Copy, Default, serde_with::DeserializeFromStr,
+)]
 pub enum GameType {
     #[default]
     Base,
@@ -142,13 +142,18 @@ impl History {
         lazy_static! {
             static ref TURN: Regex = Regex::new(r"\d+").expect("This regex should compile");
         }
+        // Anything after a comment, like a move's clock, is not part of the turn
+        let tokens = tokens
+            .iter()
+            .take_while(|token| !token.starts_with('{'))
+            .collect::<Vec<_>>();
         if let Some(token) = tokens.first() {
             if TURN.is_match(token) {
                 if let Some(piece) = tokens.get(1) {
                     if let Some(position) = tokens.get(2) {
                         self.moves.push((piece.to_string(), position.to_string()));
                     } else {
-                        match *piece {
+                        match **piece {
                             "pass" => {
                                 self.moves.push(("pass".to_string(), "".to_string()));
                             }
@@ -172,7 +177,7 @@ impl History {
     pub fn from_filepath(file_path: &str) -> Result<Self, GameError> {
         let mut history = History::new();
         lazy_static! {
-            static ref HEADER: Regex = Regex::new(r"\[.*").expect("This regex should compile");
+            static ref HEADER: Regex = Regex::new(r"^\[.*").expect("This regex should compile");
         }
         lazy_static! {
             static ref RESULT: Regex = Regex::new(r"\[Result").expect("This regex should compile");
@@ -211,7 +216,7 @@ impl History {
     pub fn from_pgn_str(string: String) -> Result<Self, GameError> {
         let mut history = History::new();
         lazy_static! {
-            static ref HEADER: Regex = Regex::new(r"\[.*").expect("This regex should compile");
+            static ref HEADER: Regex = Regex::new(r"^\[.*").expect("This regex should compile");
         }
         lazy_static! {
             static ref RESULT: Regex = Regex::new(r"\[Result").expect("This regex should compile");
@@ -241,3 +246,22 @@ impl History {
         Ok(history)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_pgn_clock_comments() {
+        let pgn = "[GameType \"Base+MLP\"]\n\n1. wL {[%clk 0:05:00]}\n2. bL -wL {[%clk 0:04:58]}\n3. pass {[%clk 0:04:50]}\n";
+        let history = History::from_pgn_str(pgn.to_string()).unwrap();
+        assert_eq!(
+            history.moves,
+            vec![
+                ("wL".to_string(), "".to_string()),
+                ("bL".to_string(), "-wL".to_string()),
+                ("pass".to_string(), "".to_string()),
+            ]
+        );
+    }
+}
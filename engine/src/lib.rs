@@ -1,3 +1,4 @@
+mod ai;
 mod board;
 mod bug;
 mod bug_stack;
@@ -19,6 +20,7 @@ mod state;
 mod torus_array;
 mod turn;
 
+pub use ai::Ai;
 pub use board::Board;
 pub use bug::Bug;
 pub use bug_stack::BugStack;
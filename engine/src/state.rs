@@ -10,6 +10,7 @@ use crate::history::History;
 use crate::piece::Piece;
 use crate::player::Player;
 use crate::position::Position;
+use crate::turn::Turn;
 use crate::{board::Board, game_type::GameType};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -26,6 +27,8 @@ pub struct State {
     pub game_type: GameType,
     pub tournament: bool,
     pub repeating_moves: Vec<usize>,
+    // Whether a threefold repetition ends the game or has to be claimed
+    pub automatic_repetition: bool,
 }
 
 impl State {
@@ -43,6 +46,7 @@ impl State {
             game_type,
             tournament,
             repeating_moves: Vec::new(),
+            automatic_repetition: true,
         }
     }
 
@@ -51,9 +55,17 @@ impl State {
     }
 
     pub fn new_from_str(moves: &str, game_type: &str) -> Result<Self, GameError> {
+        Self::new_from_str_with(moves, game_type, true)
+    }
+
+    pub fn new_from_str_with(
+        moves: &str,
+        game_type: &str,
+        automatic_repetition: bool,
+    ) -> Result<Self, GameError> {
         let game_type = GameType::from_str(game_type)?;
         let history = History::new_from_str(moves)?;
-        let mut state = State::new_from_history(&history)?;
+        let mut state = State::new_from_history_with(&history, automatic_repetition)?;
         state.game_type = game_type;
         Ok(state)
     }
@@ -66,12 +78,23 @@ impl State {
             .map(|(piece, mov)| format!("{piece} {mov}"))
             .collect::<Vec<String>>()
             .join(";");
-        if let Ok(new) = Self::new_from_str(&moves, &self.game_type.to_string()) {
+        if let Ok(new) = Self::new_from_str_with(
+            &moves,
+            &self.game_type.to_string(),
+            self.automatic_repetition,
+        ) {
             *self = new;
         }
     }
 
     pub fn new_from_history(history: &History) -> Result<Self, GameError> {
+        Self::new_from_history_with(history, true)
+    }
+
+    pub fn new_from_history_with(
+        history: &History,
+        automatic_repetition: bool,
+    ) -> Result<Self, GameError> {
         let mut tournament = true;
         // Did white open with a Queen?
         if let Some((piece_str, _)) = history.moves.first() {
@@ -88,6 +111,7 @@ impl State {
             }
         }
         let mut state = State::new(history.game_type, tournament);
+        state.automatic_repetition = automatic_repetition;
         for (piece, pos) in history.moves.iter() {
             state.play_turn_from_history(piece, pos)?;
         }
@@ -143,6 +167,27 @@ impl State {
         Ok(())
     }
 
+    // Resolves a move written in history notation into a Turn on the current
+    // board without playing it
+    pub fn turn_from_history(&self, piece: &str, position: &str) -> Result<Turn, GameError> {
+        if piece == "pass" {
+            return Ok(Turn::Shutout);
+        }
+        let piece: Piece = piece.parse()?;
+        let target_position = if is_absolute_position(position) {
+            let destination_piece = Piece::from_str(position)?;
+            self.board
+                .position_of_piece(destination_piece)
+                .ok_or(GameError::ParsingError {
+                    found: position.to_string(),
+                    typ: "position of a piece on the board".to_string(),
+                })?
+        } else {
+            Position::from_string(position, &self.board)?
+        };
+        Ok(Turn::Move(piece, target_position))
+    }
+
     pub fn play_turn_from_position(
         &mut self,
         piece: Piece,
@@ -314,20 +359,30 @@ impl State {
         self.hashes.push(hash);
         *self.hashes_count.entry(hash).or_default() += 1;
         self.history.record_hash(hash);
+        // Keeps track of the moves which led to the current position once it repeats
+        self.repeating_moves.clear();
         if let Some(count) = self.hashes_count.get(&hash) {
-            if *count > 2 {
-                self.game_status = GameStatus::Finished(GameResult::Draw);
-                let mut moves = Vec::new();
+            if *count > 1 {
                 for (index, history_hash) in self.history.hashes.iter().enumerate() {
                     if hash == *history_hash {
-                        moves.push(index);
+                        self.repeating_moves.push(index);
                     }
                 }
-                self.repeating_moves = moves;
+            }
+            if *count > 2 && self.automatic_repetition {
+                self.game_status = GameStatus::Finished(GameResult::Draw);
             }
         }
     }
 
+    // The current position occurred at least three times
+    pub fn repetition_claimable(&self) -> bool {
+        self.hashes
+            .last()
+            .and_then(|hash| self.hashes_count.get(hash))
+            .is_some_and(|count| *count > 2)
+    }
+
     pub fn check_board(&self) -> bool {
         // This function can be used to perform checks on the engine and for debugging engine
         // issues on every turn
@@ -371,4 +426,36 @@ mod tests {
         }
         assert_eq!(h.len(), 8);
     }
+
+    #[test]
+    fn tests_turn_from_history() {
+        let mut s = State::new(GameType::MLP, false);
+        s.play_turn_from_history("wS1", "").unwrap();
+        s.play_turn_from_history("bS1", "wS1-").unwrap();
+        let turn = s.turn_from_history("wQ", "-wS1").unwrap();
+        let mut played = s.clone();
+        played.play_turn_from_history("wQ", "-wS1").unwrap();
+        let wq = Piece::from_str("wQ").unwrap();
+        assert_eq!(
+            turn,
+            Turn::Move(wq, played.board.position_of_piece(wq).unwrap())
+        );
+        assert_eq!(s.turn_from_history("pass", ""), Ok(Turn::Shutout));
+        assert!(s.turn_from_history("wQ", "bA1").is_err());
+    }
+
+    #[test]
+    fn tests_repetition_rules() {
+        let moves = "wS1;bS1 wS1-;wQ -wS1;bQ bS1-;wQ \\wS1;bQ bS1/;wQ -wS1;bQ bS1-;\
+            wQ \\wS1;bQ bS1/;wQ -wS1;bQ bS1-;";
+        let automatic = State::new_from_str(moves, "MLP").unwrap();
+        assert_eq!(
+            automatic.game_status,
+            GameStatus::Finished(GameResult::Draw)
+        );
+        let claimed = State::new_from_str_with(moves, "MLP", false).unwrap();
+        assert_eq!(claimed.game_status, GameStatus::InProgress);
+        assert!(claimed.repetition_claimable());
+        assert_eq!(claimed.repeating_moves.len(), 3);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::TimeMode;
+use crate::{ClockType, RepetitionRule, TimeMode};
 use hive_lib::{ColorChoice, GameType};
 use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
@@ -38,6 +38,8 @@ pub struct ChallengeDetails {
     pub time_mode: TimeMode,
     pub time_base: Option<i32>,
     pub time_increment: Option<i32>,
+    pub clock_type: ClockType,
+    pub repetition_rule: RepetitionRule,
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
 }
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// How a real time clock treats time_increment, correspondence and untimed games are always Fischer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ClockType {
+    #[default]
+    Fischer, // time_increment gets added after every move
+    Delay,     // the clock only starts running after time_increment
+    Bronstein, // the time used gets added back, up to time_increment
+    Hourglass, // the time used gets added to the opponent, time_increment is unused
+}
+
+impl ClockType {
+    pub fn all() -> Vec<ClockType> {
+        use ClockType::*;
+        vec![Fischer, Delay, Bronstein, Hourglass]
+    }
+
+    pub fn uses_increment(&self) -> bool {
+        !matches!(self, ClockType::Hourglass)
+    }
+
+    // Short description of the clock, base is in minutes and increment in seconds
+    pub fn describe(&self, base: i32, increment: i32) -> String {
+        match self {
+            ClockType::Fischer => format!("{base}m + {increment}s"),
+            ClockType::Delay => format!("{base}m d{increment}s"),
+            ClockType::Bronstein => format!("{base}m b{increment}s"),
+            ClockType::Hourglass => format!("{base}m hourglass"),
+        }
+    }
+}
+
+impl fmt::Display for ClockType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clock = match self {
+            ClockType::Fischer => "Fischer",
+            ClockType::Delay => "Delay",
+            ClockType::Bronstein => "Bronstein",
+            ClockType::Hourglass => "Hourglass",
+        };
+        write!(f, "{}", clock)
+    }
+}
+
+#[derive(Error, Debug, Clone, Serialize, Deserialize)]
+pub enum ClockTypeError {
+    #[error("{found} is not a valid ClockType")]
+    Invalid { found: String },
+}
+
+impl FromStr for ClockType {
+    type Err = ClockTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Fischer" => Ok(ClockType::Fischer),
+            "Delay" => Ok(ClockType::Delay),
+            "Bronstein" => Ok(ClockType::Bronstein),
+            "Hourglass" => Ok(ClockType::Hourglass),
+            s => Err(ClockTypeError::Invalid {
+                found: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_clock_type() {
+        for clock in ClockType::all().into_iter() {
+            assert_eq!(clock, ClockType::from_str(&format!("{clock}")).unwrap());
+        }
+    }
+}
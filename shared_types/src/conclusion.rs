@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::time::Duration;
+
+// How long the opponent of a realtime game has to be gone before the win can be claimed, unless
+// the server sets CLAIM_VICTORY_SECONDS
+pub const CLAIM_VICTORY_AFTER: Duration = Duration::from_secs(60);
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum Conclusion {
@@ -9,6 +14,7 @@ pub enum Conclusion {
     Draw,
     Board,
     Repetition,
+    Forfeit,
 }
 
 impl PrettyString for Conclusion {
@@ -19,6 +25,7 @@ impl PrettyString for Conclusion {
             Conclusion::Resigned => String::from("Resigned"),
             Conclusion::Timeout => String::from("Timeout"),
             Conclusion::Repetition => String::from("3 move repetition"),
+            Conclusion::Forfeit => String::from("Opponent left"),
             Conclusion::Unknown => String::from("Unknown"),
         }
     }
@@ -33,6 +40,7 @@ impl fmt::Display for Conclusion {
             Conclusion::Draw => "Draw",
             Conclusion::Board => "Board",
             Conclusion::Repetition => "Repetition",
+            Conclusion::Forfeit => "Forfeit",
         };
         write!(f, "{}", time)
     }
@@ -58,6 +66,7 @@ impl std::str::FromStr for Conclusion {
             "Draw" => Ok(Conclusion::Draw),
             "Board" => Ok(Conclusion::Board),
             "Repetition" => Ok(Conclusion::Repetition),
+            "Forfeit" => Ok(Conclusion::Forfeit),
             s => Err(ConclusionError::InvalidConclusion {
                 found: s.to_string(),
             }),
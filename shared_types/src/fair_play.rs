@@ -0,0 +1,210 @@
+use crate::PrettyString;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+// Below these amounts a statistic says too little about a player to flag them
+pub const MIN_ENGINE_MOVES: usize = 100;
+pub const MIN_TIMED_MOVES: usize = 50;
+pub const MIN_RATED_GAMES: usize = 10;
+
+const MOVE_TIME_VARIATION: f64 = 0.2;
+const PERFORMANCE_GAP: f64 = 400.0;
+
+// Why an account deserves a closer look, none of these is proof of anything on its own
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum FairPlayFlag {
+    UniformMoveTimes,
+    PerformanceOutlier,
+}
+
+impl PrettyString for FairPlayFlag {
+    fn pretty_string(&self) -> String {
+        match self {
+            FairPlayFlag::UniformMoveTimes => String::from("Uniform move times"),
+            FairPlayFlag::PerformanceOutlier => String::from("Performs far above rating"),
+        }
+    }
+}
+
+impl fmt::Display for FairPlayFlag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flag = match self {
+            FairPlayFlag::UniformMoveTimes => "UniformMoveTimes",
+            FairPlayFlag::PerformanceOutlier => "PerformanceOutlier",
+        };
+        write!(f, "{flag}")
+    }
+}
+
+impl FromStr for FairPlayFlag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "UniformMoveTimes" => Ok(FairPlayFlag::UniformMoveTimes),
+            "PerformanceOutlier" => Ok(FairPlayFlag::PerformanceOutlier),
+            any => Err(format!("Unknown fair-play flag: {any}")),
+        }
+    }
+}
+
+// What the analysis found in one game, seen from the analysed player's side
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FairPlayGame {
+    pub moves_analysed: usize,
+    pub engine_matches: usize,
+    pub think_times: Vec<f64>, // Seconds per move, only for realtime games
+    pub rating: Option<f64>,   // Both ratings before the game, only for rated games
+    pub opponent_rating: Option<f64>,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FairPlayStats {
+    pub games: usize,
+    pub moves: usize,
+    pub engine_match_rate: Option<f64>,
+    pub move_time_variation: Option<f64>, // Standard deviation of the move times over their mean
+    pub performance_gap: Option<f64>,     // Performance rating minus the actual rating
+}
+
+impl FairPlayStats {
+    pub fn from_games(games: &[FairPlayGame]) -> Self {
+        let moves = games.iter().map(|game| game.moves_analysed).sum::<usize>();
+        let matches = games.iter().map(|game| game.engine_matches).sum::<usize>();
+        let engine_match_rate = (moves >= MIN_ENGINE_MOVES).then(|| matches as f64 / moves as f64);
+
+        let times = games
+            .iter()
+            .flat_map(|game| game.think_times.iter().copied())
+            .collect::<Vec<_>>();
+        let move_time_variation = (times.len() >= MIN_TIMED_MOVES)
+            .then(|| {
+                let mean = times.iter().sum::<f64>() / times.len() as f64;
+                let variance = times.iter().map(|time| (time - mean).powi(2)).sum::<f64>()
+                    / times.len() as f64;
+                (mean > 0.0).then(|| variance.sqrt() / mean)
+            })
+            .flatten();
+
+        let rated = games
+            .iter()
+            .filter_map(|game| Some((game.rating?, game.opponent_rating?, game.score)))
+            .collect::<Vec<_>>();
+        // The linear approximation of the performance rating, every point above 50% is worth
+        // 400 rating points over the average opponent
+        let performance_gap = (rated.len() >= MIN_RATED_GAMES).then(|| {
+            let count = rated.len() as f64;
+            let rating = rated.iter().map(|(rating, _, _)| rating).sum::<f64>() / count;
+            let opponents = rated.iter().map(|(_, opponent, _)| opponent).sum::<f64>() / count;
+            let score = rated.iter().map(|(_, _, score)| score).sum::<f64>();
+            opponents + 400.0 * (2.0 * score - count) / count - rating
+        });
+
+        Self {
+            games: games.len(),
+            moves,
+            engine_match_rate,
+            move_time_variation,
+            performance_gap,
+        }
+    }
+
+    // The engine match rate is only reported, the moves are compared with a greedy one ply
+    // heuristic rather than a real engine, so a high rate can just as well mean solid play
+    pub fn flags(&self) -> Vec<FairPlayFlag> {
+        let mut flags = Vec::new();
+        if self
+            .move_time_variation
+            .is_some_and(|variation| variation <= MOVE_TIME_VARIATION)
+        {
+            flags.push(FairPlayFlag::UniformMoveTimes);
+        }
+        if self
+            .performance_gap
+            .is_some_and(|gap| gap >= PERFORMANCE_GAP)
+        {
+            flags.push(FairPlayFlag::PerformanceOutlier);
+        }
+        flags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(moves_analysed: usize, engine_matches: usize) -> FairPlayGame {
+        FairPlayGame {
+            moves_analysed,
+            engine_matches,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tests_too_little_data_flags_nobody() {
+        let stats = FairPlayStats::from_games(&[game(20, 20)]);
+        assert_eq!(stats.engine_match_rate, None);
+        assert_eq!(stats.move_time_variation, None);
+        assert_eq!(stats.performance_gap, None);
+        assert!(stats.flags().is_empty());
+    }
+
+    #[test]
+    fn tests_engine_match_rate() {
+        let stats = FairPlayStats::from_games(&[game(60, 50), game(60, 40)]);
+        assert_eq!(stats.moves, 120);
+        assert_eq!(stats.engine_match_rate, Some(0.75));
+        assert!(stats.flags().is_empty());
+    }
+
+    #[test]
+    fn tests_move_time_variation() {
+        let uniform = FairPlayGame {
+            think_times: vec![5.0; MIN_TIMED_MOVES],
+            ..Default::default()
+        };
+        let stats = FairPlayStats::from_games(&[uniform]);
+        assert_eq!(stats.move_time_variation, Some(0.0));
+        assert_eq!(stats.flags(), vec![FairPlayFlag::UniformMoveTimes]);
+        let human = FairPlayGame {
+            think_times: (0..MIN_TIMED_MOVES)
+                .map(|i| (i % 10) as f64 * 3.0)
+                .collect(),
+            ..Default::default()
+        };
+        assert!(FairPlayStats::from_games(&[human]).flags().is_empty());
+    }
+
+    #[test]
+    fn tests_performance_gap() {
+        let win = FairPlayGame {
+            rating: Some(1500.0),
+            opponent_rating: Some(1900.0),
+            score: 1.0,
+            ..Default::default()
+        };
+        let stats = FairPlayStats::from_games(&vec![win; MIN_RATED_GAMES]);
+        assert_eq!(stats.performance_gap, Some(800.0));
+        assert_eq!(stats.flags(), vec![FairPlayFlag::PerformanceOutlier]);
+        let even = FairPlayGame {
+            rating: Some(1500.0),
+            opponent_rating: Some(1500.0),
+            score: 0.5,
+            ..Default::default()
+        };
+        let stats = FairPlayStats::from_games(&vec![even; MIN_RATED_GAMES]);
+        assert_eq!(stats.performance_gap, Some(0.0));
+    }
+
+    #[test]
+    fn tests_flag_round_trip() {
+        for flag in [
+            FairPlayFlag::UniformMoveTimes,
+            FairPlayFlag::PerformanceOutlier,
+        ] {
+            assert_eq!(FairPlayFlag::from_str(&flag.to_string()), Ok(flag));
+        }
+    }
+}
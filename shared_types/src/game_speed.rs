@@ -1,3 +1,4 @@
+use crate::ClockType;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -36,8 +37,25 @@ impl GameSpeed {
         ]
     }
 
-    pub fn from_base_increment(base: Option<i32>, increment: Option<i32>) -> GameSpeed {
-        let total = base.unwrap_or(0) + 40 * increment.unwrap_or(0);
+    // Only the fast games let players queue a move on their opponent's turn
+    pub fn allows_premoves(&self) -> bool {
+        matches!(self, GameSpeed::Bullet | GameSpeed::Blitz)
+    }
+
+    // Estimates the length of a game of 40 moves. A delay can at most save as
+    // much time as the same increment would add and an hourglass never adds
+    // time to the board
+    pub fn from_base_increment(
+        base: Option<i32>,
+        increment: Option<i32>,
+        clock: ClockType,
+    ) -> GameSpeed {
+        let increment = if clock.uses_increment() {
+            increment.unwrap_or(0)
+        } else {
+            0
+        };
+        let total = base.unwrap_or(0) + 40 * increment;
         if total == 0 {
             GameSpeed::Untimed
         } else if total < 180 {
@@ -94,3 +112,17 @@ impl std::str::FromStr for GameSpeed {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_from_base_increment() {
+        let speed = |clock| GameSpeed::from_base_increment(Some(60), Some(5), clock);
+        assert_eq!(speed(ClockType::Fischer), GameSpeed::Blitz);
+        assert_eq!(speed(ClockType::Delay), GameSpeed::Blitz);
+        assert_eq!(speed(ClockType::Bronstein), GameSpeed::Blitz);
+        assert_eq!(speed(ClockType::Hourglass), GameSpeed::Bullet);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::PrettyString;
+use serde::{Deserialize, Serialize};
+
+// Players without a rated game in this many days drop off the top rated board
+pub const ACTIVE_DAYS: i64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LeaderboardKind {
+    TopRated,
+    // The monthly boards only count rated games of the current calendar month
+    MostGames,
+    RatingGain,
+}
+
+impl LeaderboardKind {
+    pub fn all() -> Vec<LeaderboardKind> {
+        vec![
+            LeaderboardKind::TopRated,
+            LeaderboardKind::MostGames,
+            LeaderboardKind::RatingGain,
+        ]
+    }
+}
+
+impl PrettyString for LeaderboardKind {
+    fn pretty_string(&self) -> String {
+        match self {
+            LeaderboardKind::TopRated => String::from("Top rated"),
+            LeaderboardKind::MostGames => String::from("Most games this month"),
+            LeaderboardKind::RatingGain => String::from("Biggest gain this month"),
+        }
+    }
+}
@@ -1,15 +1,24 @@
 mod certainty;
 mod challenge;
 mod chat_message;
+mod clock_type;
 mod conclusion;
+mod country;
+mod fair_play;
 mod game_speed;
 mod game_start;
+mod leaderboard;
 mod newtypes;
 mod pretty_string;
+mod profile;
+mod quick_pair;
+mod rated_game_type;
+mod repetition_rule;
 mod scoring_mode;
 mod simple_user;
 mod standings;
 mod start_mode;
+mod statistics;
 mod tiebreaker;
 mod time_info;
 mod time_mode;
@@ -20,15 +29,28 @@ mod tournament_status;
 pub use certainty::{Certainty, RANKABLE_DEVIATION};
 pub use challenge::{ChallengeDetails, ChallengeError, ChallengeVisibility};
 pub use chat_message::{ChatDestination, ChatMessage, ChatMessageContainer, SimpleDestination};
-pub use conclusion::Conclusion;
+pub use clock_type::{ClockType, ClockTypeError};
+pub use conclusion::{Conclusion, CLAIM_VICTORY_AFTER};
+pub use country::{country_flag, country_name, COUNTRIES};
+pub use fair_play::{FairPlayFlag, FairPlayGame, FairPlayStats};
 pub use game_speed::GameSpeed;
 pub use game_start::GameStart;
+pub use leaderboard::{LeaderboardKind, ACTIVE_DAYS};
 pub use newtypes::{ApisId, ChallengeId, GameId, Password, TournamentId};
 pub use pretty_string::PrettyString;
+pub use profile::{bio_html, valid_link, MAX_BIO_LENGTH, MAX_LINKS, MAX_LINK_LENGTH};
+pub use quick_pair::QuickPairDetails;
+pub use rated_game_type::{rated_game_type, rated_game_types};
+pub use repetition_rule::{RepetitionRule, RepetitionRuleError};
 pub use scoring_mode::ScoringMode;
 pub use simple_user::SimpleUser;
+pub use standings::MatchScore;
+pub use standings::PlayerScores;
 pub use standings::Standings;
 pub use start_mode::StartMode;
+pub use statistics::{
+    OpeningStats, Outcome, Score, Statistics, StatisticsGame, OPENINGS_SHOWN, RATING_BUCKET,
+};
 pub use tiebreaker::Tiebreaker;
 pub use time_info::TimeInfo;
 pub use time_mode::{CorrespondenceMode, TimeMode};
@@ -36,4 +58,3 @@ pub use tournament_details::TournamentDetails;
 pub use tournament_game_result::TournamentGameResult;
 pub use tournament_mode::TournamentMode;
 pub use tournament_status::TournamentStatus;
-pub use standings::PlayerScores;
@@ -0,0 +1,85 @@
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd};
+
+pub const MAX_BIO_LENGTH: usize = 1000;
+pub const MAX_LINKS: usize = 3;
+pub const MAX_LINK_LENGTH: usize = 200;
+
+const SAFE_SCHEMES: [&str; 3] = ["https://", "http://", "mailto:"];
+
+pub fn valid_link(link: &str) -> bool {
+    link.len() <= MAX_LINK_LENGTH
+        && !link.chars().any(char::is_whitespace)
+        && SAFE_SCHEMES
+            .iter()
+            .any(|scheme| link.to_lowercase().starts_with(scheme) && link.len() > scheme.len())
+}
+
+// Renders a bio written in markdown. Raw html is shown as text, images only leave their alt
+// text and links that could run scripts lose their target, so the result is safe to inject.
+pub fn bio_html(bio: &str) -> String {
+    let mut in_unsafe_link = false;
+    let events =
+        Parser::new_ext(bio, Options::ENABLE_STRIKETHROUGH).filter_map(|event| match event {
+            Event::Html(text) | Event::InlineHtml(text) => Some(Event::Text(text)),
+            Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => None,
+            Event::Start(Tag::Link { ref dest_url, .. }) if !valid_link(dest_url) => {
+                in_unsafe_link = true;
+                None
+            }
+            Event::End(TagEnd::Link) if in_unsafe_link => {
+                in_unsafe_link = false;
+                None
+            }
+            event => Some(event),
+        });
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, events);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_bio_markdown() {
+        assert_eq!(
+            bio_html("Hello *hive*\n\n[me](https://hivegame.com)"),
+            "<p>Hello <em>hive</em></p>\n<p><a href=\"https://hivegame.com\">me</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn tests_bio_html_is_escaped() {
+        assert_eq!(
+            bio_html("<script>alert(1)</script>"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+        assert_eq!(
+            bio_html("a <b onclick=\"x\">b</b>"),
+            "<p>a &lt;b onclick=\"x\"&gt;b&lt;/b&gt;</p>\n"
+        );
+    }
+
+    #[test]
+    fn tests_bio_unsafe_links_and_images() {
+        assert_eq!(bio_html("[click](javascript:alert(1))"), "<p>click</p>\n");
+        assert_eq!(
+            bio_html("![a bee](https://x.com/bee.png)"),
+            "<p>a bee</p>\n"
+        );
+    }
+
+    #[test]
+    fn tests_valid_link() {
+        assert!(valid_link("https://boardgamegeek.com/user/someone"));
+        assert!(valid_link("mailto:someone@example.com"));
+        assert!(!valid_link("https://"));
+        assert!(!valid_link("javascript:alert(1)"));
+        assert!(!valid_link("https://a.com/with space"));
+        assert!(!valid_link(&format!(
+            "https://{}",
+            "a".repeat(MAX_LINK_LENGTH)
+        )));
+    }
+}
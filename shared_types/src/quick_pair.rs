@@ -0,0 +1,42 @@
+use crate::{
+    ChallengeDetails, ChallengeVisibility, ClockType, GameSpeed, RepetitionRule, TimeMode,
+};
+use hive_lib::{ColorChoice, GameType};
+use serde::{Deserialize, Serialize};
+
+// What a player is looking for when joining the quick pairing pool, only
+// players looking for exactly the same thing get paired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuickPairDetails {
+    pub game_type: GameType,
+    pub rated: bool,
+    pub time_base: i32,      // Seconds
+    pub time_increment: i32, // Seconds
+}
+
+impl QuickPairDetails {
+    pub fn speed(&self) -> GameSpeed {
+        GameSpeed::from_base_increment(
+            Some(self.time_base),
+            Some(self.time_increment),
+            ClockType::Fischer,
+        )
+    }
+
+    pub fn challenge_details(&self) -> ChallengeDetails {
+        ChallengeDetails {
+            rated: self.rated,
+            game_type: self.game_type,
+            visibility: ChallengeVisibility::Private,
+            opponent: None,
+            color_choice: ColorChoice::Random,
+            time_mode: TimeMode::RealTime,
+            time_base: Some(self.time_base),
+            time_increment: Some(self.time_increment),
+            clock_type: ClockType::Fischer,
+            repetition_rule: RepetitionRule::Automatic,
+            band_upper: None,
+            band_lower: None,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use hive_lib::GameType;
+
+// Base games and games with expansions are rated separately, every expansion
+// counts towards the MLP rating
+pub fn rated_game_types() -> Vec<GameType> {
+    vec![GameType::Base, GameType::MLP]
+}
+
+pub fn rated_game_type(game_type: &GameType) -> GameType {
+    match game_type {
+        GameType::Base => GameType::Base,
+        _ => GameType::MLP,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn tests_rated_game_type() {
+        assert_eq!(rated_game_type(&GameType::Base), GameType::Base);
+        for game_type in ["M", "L", "P", "ML", "LP", "MP", "MLP"] {
+            let game_type = GameType::from_str(game_type).unwrap();
+            assert_eq!(rated_game_type(&game_type), GameType::MLP);
+        }
+    }
+}
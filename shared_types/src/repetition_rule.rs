@@ -0,0 +1,74 @@
+use crate::PrettyString;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// What happens once a position occurs for the third time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum RepetitionRule {
+    #[default]
+    Automatic, // the game ends in a draw right away
+    Claimed, // either player may claim the draw, otherwise play goes on
+}
+
+impl RepetitionRule {
+    pub fn all() -> Vec<RepetitionRule> {
+        vec![RepetitionRule::Automatic, RepetitionRule::Claimed]
+    }
+
+    pub fn is_automatic(&self) -> bool {
+        *self == RepetitionRule::Automatic
+    }
+}
+
+impl PrettyString for RepetitionRule {
+    fn pretty_string(&self) -> String {
+        match self {
+            RepetitionRule::Automatic => String::from("Automatic draw"),
+            RepetitionRule::Claimed => String::from("Draw on claim"),
+        }
+    }
+}
+
+impl fmt::Display for RepetitionRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rule = match self {
+            RepetitionRule::Automatic => "Automatic",
+            RepetitionRule::Claimed => "Claimed",
+        };
+        write!(f, "{}", rule)
+    }
+}
+
+#[derive(Error, Debug, Clone, Serialize, Deserialize)]
+pub enum RepetitionRuleError {
+    #[error("{found} is not a valid RepetitionRule")]
+    Invalid { found: String },
+}
+
+impl FromStr for RepetitionRule {
+    type Err = RepetitionRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Automatic" => Ok(RepetitionRule::Automatic),
+            "Claimed" => Ok(RepetitionRule::Claimed),
+            s => Err(RepetitionRuleError::Invalid {
+                found: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests_repetition_rule() {
+        for rule in RepetitionRule::all().into_iter() {
+            assert_eq!(rule, RepetitionRule::from_str(&format!("{rule}")).unwrap());
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Tiebreaker, TournamentGameResult};
+use crate::{ScoringMode, Tiebreaker, TournamentGameResult};
 use hive_lib::Color;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -15,9 +15,21 @@ pub struct Pairing {
     white_elo: f64,
     black_elo: f64,
     result: TournamentGameResult,
+    armageddon: bool,
 }
 
 impl Pairing {
+    // In an armageddon game a draw counts as a win for black
+    pub fn armageddon_winner(&self) -> Option<Uuid> {
+        match self.result {
+            TournamentGameResult::Winner(Color::White) => Some(self.white_uuid),
+            TournamentGameResult::Winner(Color::Black) | TournamentGameResult::Draw => {
+                Some(self.black_uuid)
+            }
+            _ => None,
+        }
+    }
+
     pub fn other(&self, player: Uuid) -> Option<Uuid> {
         if self.white_uuid == player {
             return Some(self.black_uuid);
@@ -28,8 +40,43 @@ impl Pairing {
         None
     }
 }
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct MatchScore {
+    pub one: Uuid,
+    pub two: Uuid,
+    pub one_game_points: f32,
+    pub two_game_points: f32,
+    pub armageddons: usize,
+    pub armageddon_winner: Option<Uuid>,
+    // None until all the games of the match have a result
+    pub match_points: Option<(f32, f32)>,
+}
+
+impl MatchScore {
+    pub fn finished(&self) -> bool {
+        self.match_points.is_some()
+    }
+
+    pub fn needs_armageddon(&self) -> bool {
+        self.armageddons == 0 && self.match_points == Some((0.5, 0.5))
+    }
+
+    pub fn points_for(&self, player: Uuid) -> Option<f32> {
+        let (one, two) = self.match_points?;
+        if player == self.one {
+            return Some(one);
+        }
+        if player == self.two {
+            return Some(two);
+        }
+        None
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Standings {
+    pub scoring: ScoringMode,
     pub players: HashSet<Uuid>,
     pub players_scores: HashMap<Uuid, PlayerScores>,
     pub pairings: HashMap<Uuid, Vec<Pairing>>,
@@ -40,6 +87,7 @@ pub struct Standings {
 impl Standings {
     pub fn new() -> Self {
         Self {
+            scoring: ScoringMode::Game,
             players: HashSet::new(),
             players_scores: HashMap::new(),
             pairings: HashMap::new(),
@@ -52,6 +100,10 @@ impl Standings {
         self.tiebreakers.push(tiebreaker);
     }
 
+    pub fn set_scoring(&mut self, scoring: ScoringMode) {
+        self.scoring = scoring;
+    }
+
     pub fn enforce_tiebreakers(&mut self) {
         for tiebreaker in self.tiebreakers.clone() {
             match tiebreaker {
@@ -170,12 +222,24 @@ impl Standings {
         let mut opponents = self.players.clone();
         opponents.remove(&player);
         for opponent in opponents {
+            let mut opponent_points = 0.0;
+            if let Some(scores) = self.players_scores.get(&opponent) {
+                if let Some(op) = scores.get(&Tiebreaker::RawPoints) {
+                    opponent_points = *op;
+                }
+            }
+            if self.scoring == ScoringMode::Match {
+                if let Some(won) = self
+                    .match_between(player, opponent)
+                    .and_then(|score| score.points_for(player))
+                {
+                    points += won * opponent_points;
+                }
+                continue;
+            }
             for pairing in self.pairings_between(player, opponent) {
-                let mut opponent_points = 0.0;
-                if let Some(scores) = self.players_scores.get(&opponent) {
-                    if let Some(op) = scores.get(&Tiebreaker::RawPoints) {
-                        opponent_points = *op;
-                    }
+                if pairing.armageddon {
+                    continue;
                 }
                 match pairing.result {
                     TournamentGameResult::Draw => {
@@ -210,6 +274,7 @@ impl Standings {
         if let Some(pairings) = self.pairings.get(&black) {
             for pairing in pairings {
                 if pairing.black_uuid == black
+                    && !pairing.armageddon
                     && pairing.result == TournamentGameResult::Winner(Color::Black)
                 {
                     wins += 1.0;
@@ -220,9 +285,15 @@ impl Standings {
     }
 
     pub fn head_to_head_pair(&self, one: Uuid, two: Uuid) -> (f32, f32) {
+        if self.scoring == ScoringMode::Match {
+            return self
+                .match_between(one, two)
+                .and_then(|score| Some((score.points_for(one)?, score.points_for(two)?)))
+                .unwrap_or((0.0, 0.0));
+        }
         let mut results = HashMap::new();
         let pairings = self.pairings_between(one, two);
-        for pairing in pairings {
+        for pairing in pairings.iter().filter(|pairing| !pairing.armageddon) {
             match pairing.result {
                 TournamentGameResult::Unknown | TournamentGameResult::DoubeForfeit => {}
                 TournamentGameResult::Draw => {
@@ -255,6 +326,86 @@ impl Standings {
         results
     }
 
+    pub fn match_between(&self, one: Uuid, two: Uuid) -> Option<MatchScore> {
+        let (armageddons, regular): (Vec<Pairing>, Vec<Pairing>) = self
+            .pairings_between(one, two)
+            .into_iter()
+            .partition(|pairing| pairing.armageddon);
+        if regular.is_empty() {
+            return None;
+        }
+        let mut one_game_points = 0.0;
+        let mut two_game_points = 0.0;
+        for pairing in regular.iter() {
+            match pairing.result {
+                TournamentGameResult::Draw => {
+                    one_game_points += 0.5;
+                    two_game_points += 0.5;
+                }
+                TournamentGameResult::Winner(Color::White) if pairing.white_uuid == one => {
+                    one_game_points += 1.0;
+                }
+                TournamentGameResult::Winner(Color::Black) if pairing.black_uuid == one => {
+                    one_game_points += 1.0;
+                }
+                TournamentGameResult::Winner(_) => {
+                    two_game_points += 1.0;
+                }
+                _ => {}
+            }
+        }
+        let armageddon_winner = armageddons
+            .iter()
+            .rev()
+            .find_map(|pairing| pairing.armageddon_winner());
+        let armageddon_pending = armageddons
+            .iter()
+            .any(|pairing| pairing.result == TournamentGameResult::Unknown);
+        let regular_finished = regular
+            .iter()
+            .all(|pairing| pairing.result != TournamentGameResult::Unknown);
+        let forfeited = regular
+            .iter()
+            .all(|pairing| pairing.result == TournamentGameResult::DoubeForfeit);
+        let match_points = if !regular_finished {
+            None
+        } else if forfeited {
+            Some((0.0, 0.0))
+        } else if one_game_points > two_game_points {
+            Some((1.0, 0.0))
+        } else if two_game_points > one_game_points {
+            Some((0.0, 1.0))
+        } else if let Some(winner) = armageddon_winner {
+            if winner == one {
+                Some((1.0, 0.0))
+            } else {
+                Some((0.0, 1.0))
+            }
+        } else if armageddon_pending {
+            None
+        } else {
+            Some((0.5, 0.5))
+        };
+        Some(MatchScore {
+            one,
+            two,
+            one_game_points,
+            two_game_points,
+            armageddons: armageddons.len(),
+            armageddon_winner,
+            match_points,
+        })
+    }
+
+    pub fn matches(&self) -> Vec<MatchScore> {
+        self.players
+            .iter()
+            .sorted()
+            .combinations(2)
+            .filter_map(|pair| self.match_between(*pair[0], *pair[1]))
+            .collect()
+    }
+
     pub fn raw_points(&mut self) {
         for player in &self.players {
             let wins = self.get_raw_points(*player);
@@ -268,8 +419,19 @@ impl Standings {
 
     pub fn get_raw_points(&self, player: Uuid) -> f32 {
         let mut points = 0.0;
+        if self.scoring == ScoringMode::Match {
+            for opponent in self.players.iter().filter(|opponent| **opponent != player) {
+                if let Some(won) = self
+                    .match_between(player, *opponent)
+                    .and_then(|score| score.points_for(player))
+                {
+                    points += won;
+                }
+            }
+            return points;
+        }
         if let Some(pairings) = self.pairings.get(&player) {
-            for pairing in pairings {
+            for pairing in pairings.iter().filter(|pairing| !pairing.armageddon) {
                 match pairing.result {
                     TournamentGameResult::Draw => {
                         points += 0.5;
@@ -298,6 +460,29 @@ impl Standings {
         white_elo: f64,
         black_elo: f64,
         result: TournamentGameResult,
+    ) {
+        self.add_pairing(white_uuid, black_uuid, white_elo, black_elo, result, false);
+    }
+
+    pub fn add_armageddon_result(
+        &mut self,
+        white_uuid: Uuid,
+        black_uuid: Uuid,
+        white_elo: f64,
+        black_elo: f64,
+        result: TournamentGameResult,
+    ) {
+        self.add_pairing(white_uuid, black_uuid, white_elo, black_elo, result, true);
+    }
+
+    fn add_pairing(
+        &mut self,
+        white_uuid: Uuid,
+        black_uuid: Uuid,
+        white_elo: f64,
+        black_elo: f64,
+        result: TournamentGameResult,
+        armageddon: bool,
     ) {
         self.players.insert(white_uuid);
         self.players.insert(black_uuid);
@@ -307,6 +492,7 @@ impl Standings {
             result: result.clone(),
             black_elo,
             white_elo,
+            armageddon,
         };
         self.pairings
             .entry(white_uuid)
@@ -476,4 +662,101 @@ mod tests {
             2.25
         );
     }
+
+    #[test]
+    fn tests_match_points() {
+        let mut s = Standings::new();
+        s.set_scoring(ScoringMode::Match);
+        let one = Uuid::new_v4();
+        let two = Uuid::new_v4();
+        let three = Uuid::new_v4();
+        // one beats two 1½-½
+        s.add_result(
+            one,
+            two,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        s.add_result(two, one, 0.0, 0.0, TournamentGameResult::Draw);
+        // one and three tie 1-1
+        s.add_result(
+            one,
+            three,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        s.add_result(
+            three,
+            one,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        // three sweeps two but the second game is still running
+        s.add_result(
+            two,
+            three,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::Black),
+        );
+        s.add_result(three, two, 0.0, 0.0, TournamentGameResult::Unknown);
+        assert_eq!(s.get_raw_points(one), 1.5);
+        assert_eq!(s.get_raw_points(two), 0.0);
+        assert_eq!(s.get_raw_points(three), 0.5);
+        let tied = s.match_between(one, three).unwrap();
+        assert_eq!(tied.one_game_points, 1.0);
+        assert!(tied.needs_armageddon());
+        assert!(!s.match_between(two, three).unwrap().finished());
+        assert_eq!(s.matches().len(), 3);
+    }
+
+    #[test]
+    fn tests_armageddon() {
+        let mut s = Standings::new();
+        s.set_scoring(ScoringMode::Match);
+        let one = Uuid::new_v4();
+        let two = Uuid::new_v4();
+        s.add_result(
+            one,
+            two,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        s.add_result(
+            two,
+            one,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        s.add_armageddon_result(one, two, 0.0, 0.0, TournamentGameResult::Unknown);
+        let pending = s.match_between(one, two).unwrap();
+        assert!(!pending.finished());
+        assert!(!pending.needs_armageddon());
+        let mut s = Standings::new();
+        s.set_scoring(ScoringMode::Match);
+        s.add_result(
+            one,
+            two,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        s.add_result(
+            two,
+            one,
+            0.0,
+            0.0,
+            TournamentGameResult::Winner(Color::White),
+        );
+        // a draw in armageddon goes to black
+        s.add_armageddon_result(one, two, 0.0, 0.0, TournamentGameResult::Draw);
+        s.enforce_tiebreakers();
+        assert_eq!(vec![vec![two], vec![one]], s.players_standings);
+        assert_eq!(s.get_wins_as_black(two), 0.0);
+    }
 }
@@ -0,0 +1,255 @@
+use crate::{Conclusion, GameSpeed};
+use hive_lib::{Color, GameType};
+use serde::{Deserialize, Serialize};
+
+// Opponents are grouped by rating in steps of this size
+pub const RATING_BUCKET: u32 = 200;
+pub const OPENINGS_SHOWN: usize = 10;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+// A finished game, seen from the side of the player the statistics are for
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticsGame {
+    pub color: Color,
+    pub outcome: Outcome,
+    pub game_type: GameType,
+    pub speed: GameSpeed,
+    pub opponent_rating: Option<f64>, // Before the game, only for rated games
+    pub turns: usize,
+    pub conclusion: Conclusion,
+    pub opening: Option<(u64, String)>, // Hash of the position after the opening and its moves
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Score {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Score {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    // Share of the points won, a draw is worth half a point
+    pub fn percentage(&self) -> Option<f64> {
+        (self.games() > 0)
+            .then(|| (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64 * 100.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpeningStats {
+    pub moves: String, // The first game it was played in, every symmetric version counts too
+    pub score: Score,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Statistics {
+    pub score: Score,
+    pub by_color: Vec<(Color, Score)>,
+    pub by_game_type: Vec<(GameType, Score)>,
+    pub by_speed: Vec<(GameSpeed, Score)>,
+    pub by_opponent_rating: Vec<(u32, Score)>, // Lowest rating of the bucket
+    pub openings: Vec<OpeningStats>,
+    pub average_turns: Option<f64>,
+    pub conclusions: Vec<(Conclusion, usize)>,
+    pub longest_win_streak: usize,
+    pub longest_losing_streak: usize,
+}
+
+fn tally<K: PartialEq>(scores: &mut Vec<(K, Score)>, key: K, outcome: Outcome) {
+    match scores.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, score)) => score.add(outcome),
+        None => {
+            let mut score = Score::default();
+            score.add(outcome);
+            scores.push((key, score));
+        }
+    }
+}
+
+fn most_played_first<K>(scores: &mut [(K, Score)]) {
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(score.games()));
+}
+
+impl Statistics {
+    // The games have to be in the order they were played for the streaks to be right
+    pub fn from_games(games: &[StatisticsGame]) -> Self {
+        let mut statistics = Statistics::default();
+        let mut openings: Vec<(u64, OpeningStats)> = Vec::new();
+        let (mut wins, mut losses) = (0, 0);
+        for game in games {
+            statistics.score.add(game.outcome);
+            tally(&mut statistics.by_color, game.color, game.outcome);
+            tally(&mut statistics.by_game_type, game.game_type, game.outcome);
+            tally(&mut statistics.by_speed, game.speed.clone(), game.outcome);
+            if let Some(rating) = game.opponent_rating {
+                let bucket = (rating.max(0.0) as u32) / RATING_BUCKET * RATING_BUCKET;
+                tally(&mut statistics.by_opponent_rating, bucket, game.outcome);
+            }
+            if let Some((hash, moves)) = &game.opening {
+                match openings.iter_mut().find(|(existing, _)| existing == hash) {
+                    Some((_, opening)) => opening.score.add(game.outcome),
+                    None => {
+                        let mut score = Score::default();
+                        score.add(game.outcome);
+                        openings.push((
+                            *hash,
+                            OpeningStats {
+                                moves: moves.clone(),
+                                score,
+                            },
+                        ));
+                    }
+                }
+            }
+            match statistics
+                .conclusions
+                .iter_mut()
+                .find(|(conclusion, _)| *conclusion == game.conclusion)
+            {
+                Some((_, count)) => *count += 1,
+                None => statistics.conclusions.push((game.conclusion.clone(), 1)),
+            }
+            (wins, losses) = match game.outcome {
+                Outcome::Win => (wins + 1, 0),
+                Outcome::Loss => (0, losses + 1),
+                Outcome::Draw => (0, 0),
+            };
+            statistics.longest_win_streak = statistics.longest_win_streak.max(wins);
+            statistics.longest_losing_streak = statistics.longest_losing_streak.max(losses);
+        }
+        statistics.by_color.sort_by_key(|(color, _)| *color as u8);
+        most_played_first(&mut statistics.by_game_type);
+        most_played_first(&mut statistics.by_speed);
+        statistics
+            .by_opponent_rating
+            .sort_by_key(|(bucket, _)| *bucket);
+        statistics
+            .conclusions
+            .sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        openings.sort_by_key(|(_, opening)| std::cmp::Reverse(opening.score.games()));
+        statistics.openings = openings
+            .into_iter()
+            .take(OPENINGS_SHOWN)
+            .map(|(_, opening)| opening)
+            .collect();
+        statistics.average_turns = (!games.is_empty()).then(|| {
+            games.iter().map(|game| game.turns).sum::<usize>() as f64 / games.len() as f64
+        });
+        statistics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(color: Color, outcome: Outcome) -> StatisticsGame {
+        StatisticsGame {
+            color,
+            outcome,
+            game_type: GameType::MLP,
+            speed: GameSpeed::Blitz,
+            opponent_rating: None,
+            turns: 20,
+            conclusion: Conclusion::Board,
+            opening: None,
+        }
+    }
+
+    #[test]
+    fn tests_empty() {
+        let statistics = Statistics::from_games(&[]);
+        assert_eq!(statistics.score.games(), 0);
+        assert_eq!(statistics.score.percentage(), None);
+        assert_eq!(statistics.average_turns, None);
+    }
+
+    #[test]
+    fn tests_scores_by_color() {
+        let statistics = Statistics::from_games(&[
+            game(Color::Black, Outcome::Win),
+            game(Color::White, Outcome::Draw),
+            game(Color::White, Outcome::Win),
+        ]);
+        assert_eq!(statistics.score.games(), 3);
+        assert_eq!(statistics.by_color[0].1.percentage(), Some(75.0));
+        assert_eq!(
+            statistics.by_color,
+            vec![
+                (
+                    Color::White,
+                    Score {
+                        wins: 1,
+                        draws: 1,
+                        losses: 0
+                    }
+                ),
+                (
+                    Color::Black,
+                    Score {
+                        wins: 1,
+                        draws: 0,
+                        losses: 0
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tests_rating_buckets() {
+        let mut low = game(Color::White, Outcome::Win);
+        low.opponent_rating = Some(1399.0);
+        let mut high = game(Color::White, Outcome::Loss);
+        high.opponent_rating = Some(1600.0);
+        let statistics = Statistics::from_games(&[high, low, game(Color::White, Outcome::Win)]);
+        let buckets = statistics
+            .by_opponent_rating
+            .iter()
+            .map(|(bucket, score)| (*bucket, score.games()))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets, vec![(1200, 1), (1600, 1)]);
+    }
+
+    #[test]
+    fn tests_streaks() {
+        use Outcome::*;
+        let games = [Win, Win, Loss, Win, Win, Win, Draw, Loss, Loss]
+            .map(|outcome| game(Color::White, outcome));
+        let statistics = Statistics::from_games(&games);
+        assert_eq!(statistics.longest_win_streak, 3);
+        assert_eq!(statistics.longest_losing_streak, 2);
+    }
+
+    #[test]
+    fn tests_openings_are_grouped_by_hash() {
+        let mut first = game(Color::White, Outcome::Win);
+        first.opening = Some((1, String::from("wA1")));
+        let mut mirrored = game(Color::White, Outcome::Loss);
+        mirrored.opening = Some((1, String::from("wA1 mirrored")));
+        let mut other = game(Color::White, Outcome::Win);
+        other.opening = Some((2, String::from("wG1")));
+        let statistics = Statistics::from_games(&[other, first, mirrored]);
+        assert_eq!(statistics.openings.len(), 2);
+        assert_eq!(statistics.openings[0].moves, "wA1");
+        assert_eq!(statistics.openings[0].score.games(), 2);
+    }
+}
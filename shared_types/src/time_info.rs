@@ -1,8 +1,9 @@
-use crate::TimeMode;
+use crate::{ClockType, TimeMode};
 
 #[derive(Clone, PartialEq)]
 pub struct TimeInfo {
     pub mode: TimeMode,
     pub base: Option<i32>,
     pub increment: Option<i32>,
+    pub clock: ClockType,
 }
@@ -1,4 +1,4 @@
-use crate::{ScoringMode, StartMode, Tiebreaker, TimeMode};
+use crate::{ClockType, RepetitionRule, ScoringMode, StartMode, Tiebreaker, TimeMode};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -8,6 +8,7 @@ pub struct TournamentDetails {
     pub name: String,
     pub description: String,
     pub scoring: ScoringMode,
+    pub armageddon: bool,
     pub tiebreakers: Vec<Option<Tiebreaker>>,
     pub invitees: Vec<Option<Uuid>>,
     pub seats: i32,
@@ -18,6 +19,8 @@ pub struct TournamentDetails {
     pub time_mode: TimeMode,
     pub time_base: Option<i32>,
     pub time_increment: Option<i32>,
+    pub clock_type: ClockType,
+    pub repetition_rule: RepetitionRule,
     pub band_upper: Option<i32>,
     pub band_lower: Option<i32>,
     pub start_mode: StartMode,